@@ -1,3 +1,47 @@
+use std::io::Cursor;
 
-fn main() {
+use rust_av1_dec::{detect_format, AnnexBReader, BitstreamFormat, IvfReader, ObuReader, IVF_SIGNATURE, OBU};
+
+fn print_obu(obu: &OBU) {
+    println!("  {:?} obu_size={}", obu.obu_type(), obu.obu_size());
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .ok_or("usage: simple <file.ivf|file.obu>")?;
+    let data = std::fs::read(&path)?;
+
+    if data.starts_with(&IVF_SIGNATURE) {
+        let ivf = IvfReader::new(Cursor::new(&data))?;
+        let header = ivf.header().clone();
+        println!("IVF {}x{} timebase {:?}", header.width(), header.height(), header.timebase());
+        for frame in ivf {
+            let frame = frame?;
+            println!("temporal unit pts={} ({:.3}s)", frame.pts(), frame.timestamp());
+            for obu in frame.obus() {
+                print_obu(&obu?);
+            }
+        }
+        return Ok(());
+    }
+
+    match detect_format(&data) {
+        BitstreamFormat::LowOverhead => {
+            for obu in ObuReader::new(&data) {
+                print_obu(&obu?);
+            }
+        }
+        BitstreamFormat::AnnexB => {
+            for temporal_unit in AnnexBReader::new(&data) {
+                println!("temporal unit");
+                for frame_unit in temporal_unit?.frame_units() {
+                    for obu in frame_unit?.obus() {
+                        print_obu(&obu?);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
 }
@@ -0,0 +1,171 @@
+use std::io::Cursor;
+
+use bitstream_io::{BigEndian, BitReader, FromBitStream};
+
+use crate::{consts::OBU_TYPE, leb_128, obu::OBU_Header, Error, OBU};
+
+use super::{AnnexBReader, AnnexB_Frame_Unit, AnnexB_Frame_Units, AnnexB_OBUs, AnnexB_Temporal_Unit, BitstreamFormat, SizedUnits};
+
+impl<'a> SizedUnits<'a> {
+    // `element` names the size field in errors
+    fn new(data: &'a [u8], element: &'static str) -> Self {
+        Self { data, position: 0, element }
+    }
+
+    // Returns the unit size and the number of bytes used by the leb128 (Leb128Bytes)
+    fn read_size(data: &[u8], element: &'static str) -> Result<(usize, usize), Error> {
+        let mut r = BitReader::endian(Cursor::new(data), BigEndian);
+        let size = leb_128::from_reader(&mut r)?;
+        let leb128_bytes = (r.position_in_bits()? / 8) as usize;
+        let size = usize::try_from(size.value()).map_err(|_| Error::InvalidSyntax { element, value: size.value() })?;
+        Ok((size, leb128_bytes))
+    }
+}
+
+impl<'a> Iterator for SizedUnits<'a> {
+    type Item = Result<&'a [u8], Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.data.len() {
+            return None;
+        }
+
+        let remaining = &self.data[self.position..];
+        let unit = Self::read_size(remaining, self.element).and_then(|(size, leb128_bytes)| {
+            remaining
+                .get(leb128_bytes..leb128_bytes + size)
+                .map(|unit| (unit, leb128_bytes + size))
+                .ok_or(Error::UnexpectedEof)
+        });
+
+        match unit {
+            Ok((unit, consumed)) => {
+                self.position += consumed;
+                Some(Ok(unit))
+            }
+            Err(e) => {
+                self.position = self.data.len();
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<'a> AnnexBReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { units: SizedUnits::new(data, "temporal_unit_size") }
+    }
+
+    // Byte offset of the next temporal unit
+    pub fn position(&self) -> usize {
+        self.units.position
+    }
+}
+
+impl<'a> Iterator for AnnexBReader<'a> {
+    type Item = Result<AnnexB_Temporal_Unit<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.units.next().map(|unit| unit.map(|data| AnnexB_Temporal_Unit { data }))
+    }
+}
+
+impl<'a> AnnexB_Temporal_Unit<'a> {
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn frame_units(&self) -> AnnexB_Frame_Units<'a> {
+        AnnexB_Frame_Units { units: SizedUnits::new(self.data, "frame_unit_size") }
+    }
+}
+
+impl<'a> Iterator for AnnexB_Frame_Units<'a> {
+    type Item = Result<AnnexB_Frame_Unit<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.units.next().map(|unit| unit.map(|data| AnnexB_Frame_Unit { data }))
+    }
+}
+
+impl<'a> AnnexB_Frame_Unit<'a> {
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn obus(&self) -> AnnexB_OBUs<'a> {
+        AnnexB_OBUs { units: SizedUnits::new(self.data, "obu_length"), obu_index: 0 }
+    }
+}
+
+impl<'a> Iterator for AnnexB_OBUs<'a> {
+    type Item = Result<OBU<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // open_bitstream_unit( obu_length )
+        let obu_index = self.obu_index;
+        self.obu_index += 1;
+        self.units
+            .next()
+            .map(|unit| unit.and_then(open_obu).map_err(|e| e.with_obu_index(obu_index)))
+    }
+}
+
+// An OBU that carries obu_size as well has to end within its obu_length
+fn open_obu(data: &[u8]) -> Result<OBU<'_>, Error> {
+    let (header, obu_size, header_size) = OBU::open_header(data)?;
+    if header_size as u64 + obu_size.value() > data.len() as u64 {
+        return Err(Error::ConformanceViolation {
+            requirement: "obu_size fits within obu_length",
+        }
+        .at(0, header_size as u64 * 8));
+    }
+    OBU::with_payload(header, obu_size, header_size, data)
+}
+
+// A low-overhead stream starts with a temporal delimiter carrying obu_size,
+// an Annex B stream with a temporal unit whose sizes add up exactly.
+pub fn detect_format(data: &[u8]) -> BitstreamFormat {
+    if !starts_with_temporal_delimiter(data) && is_annexb_temporal_unit(data) {
+        BitstreamFormat::AnnexB
+    } else {
+        BitstreamFormat::LowOverhead
+    }
+}
+
+fn starts_with_temporal_delimiter(data: &[u8]) -> bool {
+    let mut r = BitReader::endian(Cursor::new(data), BigEndian);
+    match OBU_Header::from_reader(&mut r) {
+        Ok(header) => {
+            header.obu_type() == OBU_TYPE::OBU_TEMPORAL_DELIMITER
+                && header.obu_has_size_field() == 1
+                && header.obu_forbidden_bit() == 0
+        }
+        Err(_) => false,
+    }
+}
+
+fn is_annexb_temporal_unit(data: &[u8]) -> bool {
+    let Some(Ok(temporal_unit)) = AnnexBReader::new(data).next() else {
+        return false;
+    };
+
+    let mut frame_units = 0usize;
+    for frame_unit in temporal_unit.frame_units() {
+        let Ok(frame_unit) = frame_unit else {
+            return false;
+        };
+        let mut obus = 0usize;
+        for obu in frame_unit.obus() {
+            match obu {
+                Ok(obu) if obu.obu_header().obu_forbidden_bit() == 0 => obus += 1,
+                _ => return false,
+            }
+        }
+        if obus == 0 {
+            return false;
+        }
+        frame_units += 1;
+    }
+    frame_units > 0
+}
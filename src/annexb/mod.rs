@@ -0,0 +1,47 @@
+mod impls;
+
+pub use impls::detect_format;
+
+// Annex B length delimited bitstream format
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BitstreamFormat {
+    LowOverhead, // Section 5, obu_size inside every OBU
+    AnnexB,      // temporal_unit_size / frame_unit_size / obu_length
+}
+
+// B.2 bitstream( )
+#[derive(Debug, Clone)]
+pub struct AnnexBReader<'a> {
+    units: SizedUnits<'a>,
+}
+
+// B.2 temporal_unit( sz )
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AnnexB_Temporal_Unit<'a> {
+    data: &'a [u8], // temporal_unit_size bytes
+}
+
+// B.2 frame_unit( sz )
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AnnexB_Frame_Unit<'a> {
+    data: &'a [u8], // frame_unit_size bytes
+}
+
+#[derive(Debug, Clone)]
+pub struct AnnexB_Frame_Units<'a> {
+    units: SizedUnits<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnnexB_OBUs<'a> {
+    units: SizedUnits<'a>,
+    obu_index: usize,
+}
+
+// leb128 size followed by that many bytes, repeated until data runs out
+#[derive(Debug, Clone)]
+struct SizedUnits<'a> {
+    data: &'a [u8],
+    position: usize,
+    element: &'static str, // temporal_unit_size, frame_unit_size or obu_length
+}
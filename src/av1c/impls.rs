@@ -0,0 +1,213 @@
+use std::io::Cursor;
+
+use bitstream_io::{BigEndian, BitRead, BitReader, BitWrite, BitWriter, FromBitStream, ToBitStream};
+
+use crate::{
+    consts::{CHROMA_SAMPLE_POSITION, OBU_TYPE},
+    leb_128,
+    obu::{OperatingPoint, OBU_Header, OBU_Sequence_Header},
+    Error, ObuReader,
+};
+
+use super::AV1CodecConfigurationRecord;
+
+impl AV1CodecConfigurationRecord {
+    // Record for a sequence header, configOBUs holds the sequence header OBU itself
+    pub fn from_sequence_header(sequence_header: &OBU_Sequence_Header) -> Result<Self, Error> {
+        let color_config = sequence_header.color_config();
+        let initial_display_delay_minus_1 = sequence_header
+            .initial_display_delay_minus_1()
+            .filter(|_| sequence_header.initial_display_delay_present_for_this_op().first() == Some(&1))
+            .and_then(|delays| delays.first().copied());
+
+        Ok(Self {
+            marker: 1,
+            version: 1,
+            seq_profile: sequence_header.seq_profile(),
+            seq_level_idx_0: sequence_header.seq_level_idx()[0],
+            seq_tier_0: sequence_header.seq_tier()[0],
+            high_bitdepth: color_config.high_bitdepth(),
+            twelve_bit: (color_config.bit_depth() == 12) as u8,
+            monochrome: color_config.mono_chrome(),
+            chroma_subsampling_x: color_config.subsampling_x(),
+            chroma_subsampling_y: color_config.subsampling_y(),
+            chroma_sample_position: color_config.chroma_sample_position(),
+            reserved: 0,
+            initial_presentation_delay_present: initial_display_delay_minus_1.is_some() as u8,
+            initial_presentation_delay_minus_one: initial_display_delay_minus_1.unwrap_or(0),
+            config_obus: sequence_header_obu(sequence_header)?,
+        })
+    }
+
+    // The whole av1C box payload, configOBUs runs to the end of `data`
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        let mut r = BitReader::endian(Cursor::new(data), BigEndian);
+
+        let marker = r.read::<1, u8>()?;
+        if marker != 1 {
+            return Err(Error::InvalidSyntax { element: "marker", value: marker as u64 });
+        }
+        let version = r.read::<7, u8>()?;
+        if version != 1 {
+            return Err(Error::Unsupported { feature: "av1C version other than 1" });
+        }
+
+        Ok(Self {
+            marker,
+            version,
+            seq_profile: r.read::<3, u8>()?,
+            seq_level_idx_0: r.read::<5, u8>()?,
+            seq_tier_0: r.read::<1, u8>()?,
+            high_bitdepth: r.read::<1, u8>()?,
+            twelve_bit: r.read::<1, u8>()?,
+            monochrome: r.read::<1, u8>()?,
+            chroma_subsampling_x: r.read::<1, u8>()?,
+            chroma_subsampling_y: r.read::<1, u8>()?,
+            chroma_sample_position: CHROMA_SAMPLE_POSITION::from_reader(&mut r)?,
+            reserved: r.read::<3, u8>()?,
+            initial_presentation_delay_present: r.read::<1, u8>()?,
+            initial_presentation_delay_minus_one: r.read::<4, u8>()?,
+            config_obus: data[4..].to_vec(),
+        })
+    }
+
+    pub fn seq_profile(&self) -> u8 {
+        self.seq_profile
+    }
+
+    pub fn seq_level_idx_0(&self) -> u8 {
+        self.seq_level_idx_0
+    }
+
+    pub fn seq_tier_0(&self) -> u8 {
+        self.seq_tier_0
+    }
+
+    pub fn high_bitdepth(&self) -> u8 {
+        self.high_bitdepth
+    }
+
+    pub fn twelve_bit(&self) -> u8 {
+        self.twelve_bit
+    }
+
+    pub fn bit_depth(&self) -> u8 {
+        match (self.high_bitdepth, self.twelve_bit) {
+            (1, 1) => 12,
+            (1, _) => 10,
+            _ => 8,
+        }
+    }
+
+    pub fn monochrome(&self) -> u8 {
+        self.monochrome
+    }
+
+    pub fn chroma_subsampling_x(&self) -> u8 {
+        self.chroma_subsampling_x
+    }
+
+    pub fn chroma_subsampling_y(&self) -> u8 {
+        self.chroma_subsampling_y
+    }
+
+    pub fn chroma_sample_position(&self) -> CHROMA_SAMPLE_POSITION {
+        self.chroma_sample_position
+    }
+
+    pub fn initial_presentation_delay_minus_one(&self) -> Option<u8> {
+        (self.initial_presentation_delay_present == 1).then_some(self.initial_presentation_delay_minus_one)
+    }
+
+    pub fn config_obus(&self) -> &[u8] {
+        &self.config_obus
+    }
+
+    pub fn obus(&self) -> ObuReader<'_> {
+        ObuReader::new(&self.config_obus)
+    }
+
+    // First sequence header in configOBUs
+    pub fn sequence_header(&self) -> Result<Option<OBU_Sequence_Header>, Error> {
+        for (obu_index, obu) in self.obus().enumerate() {
+            let obu = obu?;
+            if obu.obu_type() == OBU_TYPE::OBU_SEQUENCE_HEADER {
+                let mut r = BitReader::endian(Cursor::new(obu.payload()), BigEndian);
+                return OBU_Sequence_Header::sequence_header_obu(&mut r, &OperatingPoint::default())
+                    .map(Some)
+                    .map_err(|e| e.at(obu_index, obu.header_size() as u64 * 8 + r.position_in_bits().unwrap_or(0)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl ToBitStream for AV1CodecConfigurationRecord {
+    type Error = std::io::Error;
+
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        w.write::<1, u8>(self.marker)?;
+        w.write::<7, u8>(self.version)?;
+        w.write::<3, u8>(self.seq_profile)?;
+        w.write::<5, u8>(self.seq_level_idx_0)?;
+        w.write::<1, u8>(self.seq_tier_0)?;
+        w.write::<1, u8>(self.high_bitdepth)?;
+        w.write::<1, u8>(self.twelve_bit)?;
+        w.write::<1, u8>(self.monochrome)?;
+        w.write::<1, u8>(self.chroma_subsampling_x)?;
+        w.write::<1, u8>(self.chroma_subsampling_y)?;
+        self.chroma_sample_position.to_writer(w)?;
+        w.write::<3, u8>(self.reserved)?;
+        w.write::<1, u8>(self.initial_presentation_delay_present)?;
+        w.write::<4, u8>(self.initial_presentation_delay_minus_one)?;
+        w.write_bytes(&self.config_obus)
+    }
+}
+
+impl OBU_Sequence_Header {
+    // AV1 Codec ISO Media File Format Binding, Codecs Parameter String
+    // av01.P.LLT.DD.M.CCC.cp.tc.mc.F with every optional field written out
+    pub fn codec_string(&self) -> String {
+        let color_config = self.color_config();
+        let chroma_sample_position = if color_config.subsampling_x() == 1 && color_config.subsampling_y() == 1 {
+            color_config.chroma_sample_position() as u8
+        } else {
+            CHROMA_SAMPLE_POSITION::CSP_UNKNOWN as u8
+        };
+
+        format!(
+            "av01.{}.{:02}{}.{:02}.{}.{}{}{}.{:02}.{:02}.{:02}.{}",
+            self.seq_profile(),
+            self.seq_level_idx()[0],
+            if self.seq_tier()[0] == 0 { 'M' } else { 'H' },
+            color_config.bit_depth(),
+            color_config.mono_chrome(),
+            color_config.subsampling_x(),
+            color_config.subsampling_y(),
+            chroma_sample_position,
+            u8::from(color_config.color_primaries()),
+            u8::from(color_config.transfer_characteristics()),
+            u8::from(color_config.matrix_coefficients()),
+            color_config.color_range(),
+        )
+    }
+}
+
+// 5.3.1 Sequence header OBU with obu_size and trailing bits
+fn sequence_header_obu(sequence_header: &OBU_Sequence_Header) -> Result<Vec<u8>, Error> {
+    let mut payload = BitWriter::endian(Vec::new(), BigEndian);
+    sequence_header.to_writer(&mut payload)?;
+    // trailing_bits( )
+    payload.write_bit(true)?;
+    payload.byte_align()?;
+    let payload = payload.into_writer();
+
+    let mut w = BitWriter::endian(Vec::new(), BigEndian);
+    OBU_Header::new(OBU_TYPE::OBU_SEQUENCE_HEADER, None).to_writer(&mut w)?;
+    leb_128::new(payload.len() as u64).to_writer(&mut w)?;
+    w.write_bytes(&payload)?;
+    Ok(w.into_writer())
+}
@@ -0,0 +1,23 @@
+mod impls;
+
+use crate::consts::CHROMA_SAMPLE_POSITION;
+
+// AV1 Codec ISO Media File Format Binding 2.3.3, payload of the av1C box
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AV1CodecConfigurationRecord {
+    marker: u8,                                     // 1 bit
+    version: u8,                                    // 7 bits
+    seq_profile: u8,                                // 3 bits
+    seq_level_idx_0: u8,                            // 5 bits
+    seq_tier_0: u8,                                 // 1 bit
+    high_bitdepth: u8,                              // 1 bit
+    twelve_bit: u8,                                 // 1 bit
+    monochrome: u8,                                 // 1 bit
+    chroma_subsampling_x: u8,                       // 1 bit
+    chroma_subsampling_y: u8,                       // 1 bit
+    chroma_sample_position: CHROMA_SAMPLE_POSITION, // 2 bits
+    reserved: u8,                                   // 3 bits
+    initial_presentation_delay_present: u8,         // 1 bit
+    initial_presentation_delay_minus_one: u8,       // 4 bits, reserved when not present
+    config_obus: Vec<u8>,                           // rest of the box
+}
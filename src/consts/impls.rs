@@ -1,6 +1,8 @@
 use bitstream_io::{FromBitStream, ToBitStream};
 
-use super::{CHROMA_SAMPLE_POSITION, COLOR_PRIMARIES, MATRIX_COEFFICIENTS, OBU_TYPE, TRANSFER_CHARACTERISTICS};
+use crate::Error;
+
+use super::{CHROMA_SAMPLE_POSITION, COLOR_PRIMARIES, FRAME_RESTORATION_TYPE, FRAME_TYPE, GM_TYPE, INTERPOLATION_FILTER, MATRIX_COEFFICIENTS, OBU_TYPE, TRANSFER_CHARACTERISTICS};
 
 impl ToBitStream for OBU_TYPE {
     type Error = std::io::Error;
@@ -18,6 +20,7 @@ impl ToBitStream for OBU_TYPE {
             Self::OBU_REDUNDANT_FRAME_HEADER => 7u8,
             Self::OBU_TILE_LIST => 8u8,
             Self::OBU_PADDING => 15u8,
+            Self::OBU_RESERVED(v) => *v,
         };
         w.write::<4,u8>(val)?;
         Ok(())
@@ -25,7 +28,7 @@ impl ToBitStream for OBU_TYPE {
 }
 
 impl FromBitStream for OBU_TYPE {
-    type Error = std::io::Error;
+    type Error = Error;
 
     fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
     where
@@ -40,14 +43,45 @@ impl FromBitStream for OBU_TYPE {
                 7 => Ok(Self::OBU_REDUNDANT_FRAME_HEADER),
                 8 => Ok(Self::OBU_TILE_LIST),
                 15 => Ok(Self::OBU_PADDING),
-                _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid OBU_TYPE"))
+                v => Ok(Self::OBU_RESERVED(v)),
             }
         }
 }
 
-impl FromBitStream for COLOR_PRIMARIES {
+impl From<COLOR_PRIMARIES> for u8 {
+    fn from(value: COLOR_PRIMARIES) -> Self {
+        match value {
+            COLOR_PRIMARIES::CP_BT_709 => 1,
+            COLOR_PRIMARIES::CP_UNSPECIFIED => 2,
+            COLOR_PRIMARIES::CP_BT_470_M => 4,
+            COLOR_PRIMARIES::CP_BT_470_B_G => 5,
+            COLOR_PRIMARIES::CP_BT_601 => 6,
+            COLOR_PRIMARIES::CP_SMPTE_240 => 7,
+            COLOR_PRIMARIES::CP_GENERIC_FILM => 8,
+            COLOR_PRIMARIES::CP_BT_2020 => 9,
+            COLOR_PRIMARIES::CP_XYZ => 10,
+            COLOR_PRIMARIES::CP_SMPTE_431 => 11,
+            COLOR_PRIMARIES::CP_SMPTE_432 => 12,
+            COLOR_PRIMARIES::CP_EBU_3213 => 22,
+            COLOR_PRIMARIES::CP_RESERVED(v) => v,
+        }
+    }
+}
+
+impl ToBitStream for COLOR_PRIMARIES {
     type Error = std::io::Error;
 
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized {
+        w.write::<8,u8>(u8::from(*self))?;
+        Ok(())
+    }
+}
+
+impl FromBitStream for COLOR_PRIMARIES {
+    type Error = Error;
+
     fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
     where
         Self: Sized {
@@ -64,22 +98,56 @@ impl FromBitStream for COLOR_PRIMARIES {
             11 => Ok(Self::CP_SMPTE_431),
             12 => Ok(Self::CP_SMPTE_432),
             22 => Ok(Self::CP_EBU_3213),
-            _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid COLOR_PRIMARIES"))
+            v => Ok(Self::CP_RESERVED(v)),
         }
     }
 }
 
-impl FromBitStream for TRANSFER_CHARACTERISTICS {
+impl From<TRANSFER_CHARACTERISTICS> for u8 {
+    fn from(value: TRANSFER_CHARACTERISTICS) -> Self {
+        match value {
+            TRANSFER_CHARACTERISTICS::TC_BT_709 => 1,
+            TRANSFER_CHARACTERISTICS::TC_UNSPECIFIED => 2,
+            TRANSFER_CHARACTERISTICS::TC_BT_470_M => 4,
+            TRANSFER_CHARACTERISTICS::TC_BT_470_B_G => 5,
+            TRANSFER_CHARACTERISTICS::TC_BT_601 => 6,
+            TRANSFER_CHARACTERISTICS::TC_SMPTE_240 => 7,
+            TRANSFER_CHARACTERISTICS::TC_LINEAR => 8,
+            TRANSFER_CHARACTERISTICS::TC_LOG_100 => 9,
+            TRANSFER_CHARACTERISTICS::TC_LOG_100_SQRT10 => 10,
+            TRANSFER_CHARACTERISTICS::TC_IEC_61966 => 11,
+            TRANSFER_CHARACTERISTICS::TC_BT_1361 => 12,
+            TRANSFER_CHARACTERISTICS::TC_SRGB => 13,
+            TRANSFER_CHARACTERISTICS::TC_BT_2020_10_BIT => 14,
+            TRANSFER_CHARACTERISTICS::TC_BT_2020_12_BIT => 15,
+            TRANSFER_CHARACTERISTICS::TC_SMPTE_2084 => 16,
+            TRANSFER_CHARACTERISTICS::TC_SMPTE_428 => 17,
+            TRANSFER_CHARACTERISTICS::TC_HLG => 18,
+            TRANSFER_CHARACTERISTICS::TC_RESERVED(v) => v,
+        }
+    }
+}
+
+impl ToBitStream for TRANSFER_CHARACTERISTICS {
     type Error = std::io::Error;
 
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized {
+        w.write::<8,u8>(u8::from(*self))?;
+        Ok(())
+    }
+}
+
+impl FromBitStream for TRANSFER_CHARACTERISTICS {
+    type Error = Error;
+
     fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
     where
         Self: Sized {
         match r.read::<8,u8>()? {
-            0 => Ok(Self::TC_RESERVED_0),
             1 => Ok(Self::TC_BT_709),
             2 => Ok(Self::TC_UNSPECIFIED),
-            3 => Ok(Self::TC_RESERVED_3),
             4 => Ok(Self::TC_BT_470_M),
             5 => Ok(Self::TC_BT_470_B_G),
             6 => Ok(Self::TC_BT_601),
@@ -95,14 +163,47 @@ impl FromBitStream for TRANSFER_CHARACTERISTICS {
             16 => Ok(Self::TC_SMPTE_2084),
             17 => Ok(Self::TC_SMPTE_428),
             18 => Ok(Self::TC_HLG),
-            _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid TRANSFER_CHARACTERISTICS"))
+            v => Ok(Self::TC_RESERVED(v)),
         }
     }
 }
 
-impl FromBitStream for MATRIX_COEFFICIENTS {
+impl From<MATRIX_COEFFICIENTS> for u8 {
+    fn from(value: MATRIX_COEFFICIENTS) -> Self {
+        match value {
+            MATRIX_COEFFICIENTS::MC_IDENTITY => 0,
+            MATRIX_COEFFICIENTS::MC_BT_709 => 1,
+            MATRIX_COEFFICIENTS::MC_UNSPECIFIED => 2,
+            MATRIX_COEFFICIENTS::MC_FCC => 4,
+            MATRIX_COEFFICIENTS::MC_BT_470_B_G => 5,
+            MATRIX_COEFFICIENTS::MC_BT_601 => 6,
+            MATRIX_COEFFICIENTS::MC_SMPTE_240 => 7,
+            MATRIX_COEFFICIENTS::MC_SMPTE_YCGCO => 8,
+            MATRIX_COEFFICIENTS::MC_BT_2020_NCL => 9,
+            MATRIX_COEFFICIENTS::MC_BT_2020_CL => 10,
+            MATRIX_COEFFICIENTS::MC_SMPTE_2085 => 11,
+            MATRIX_COEFFICIENTS::MC_CHROMAT_NCL => 12,
+            MATRIX_COEFFICIENTS::MC_CHROMAT_CL => 13,
+            MATRIX_COEFFICIENTS::MC_ICTCP => 14,
+            MATRIX_COEFFICIENTS::MC_RESERVED(v) => v,
+        }
+    }
+}
+
+impl ToBitStream for MATRIX_COEFFICIENTS {
     type Error = std::io::Error;
 
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized {
+        w.write::<8,u8>(u8::from(*self))?;
+        Ok(())
+    }
+}
+
+impl FromBitStream for MATRIX_COEFFICIENTS {
+    type Error = Error;
+
     fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
     where
         Self: Sized {
@@ -110,7 +211,6 @@ impl FromBitStream for MATRIX_COEFFICIENTS {
             0 => Ok(Self::MC_IDENTITY),
             1 => Ok(Self::MC_BT_709),
             2 => Ok(Self::MC_UNSPECIFIED),
-            3 => Ok(Self::MC_RESERVED_3),
             4 => Ok(Self::MC_FCC),
             5 => Ok(Self::MC_BT_470_B_G),
             6 => Ok(Self::MC_BT_601),
@@ -122,14 +222,25 @@ impl FromBitStream for MATRIX_COEFFICIENTS {
             12 => Ok(Self::MC_CHROMAT_NCL),
             13 => Ok(Self::MC_CHROMAT_CL),
             14 => Ok(Self::MC_ICTCP),
-            _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid MATRIX_COEFFICIENTS"))
+            v => Ok(Self::MC_RESERVED(v)),
         }
     }
 }
 
-impl FromBitStream for CHROMA_SAMPLE_POSITION {
+impl ToBitStream for CHROMA_SAMPLE_POSITION {
     type Error = std::io::Error;
 
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized {
+        w.write::<2,u8>(*self as u8)?;
+        Ok(())
+    }
+}
+
+impl FromBitStream for CHROMA_SAMPLE_POSITION {
+    type Error = Error;
+
     fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
     where
         Self: Sized {
@@ -138,7 +249,81 @@ impl FromBitStream for CHROMA_SAMPLE_POSITION {
             1 => Ok(Self::CSP_VERTICAL),
             2 => Ok(Self::CSP_COLOCATED),
             3 => Ok(Self::CSP_RESERVED),
-            _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid CHROMA_SAMPLE_POSITION"))
+            v => Err(Error::InvalidSyntax { element: "chroma_sample_position", value: v as u64 })
+        }
+    }
+}
+
+impl FromBitStream for FRAME_TYPE {
+    type Error = Error;
+
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: Sized {
+        match r.read::<2,u8>()? {
+            0 => Ok(Self::KEY_FRAME),
+            1 => Ok(Self::INTER_FRAME),
+            2 => Ok(Self::INTRA_ONLY_FRAME),
+            3 => Ok(Self::SWITCH_FRAME),
+            v => Err(Error::InvalidSyntax { element: "frame_type", value: v as u64 })
+        }
+    }
+}
+
+impl FromBitStream for INTERPOLATION_FILTER {
+    type Error = Error;
+
+    // 5.9.10 Interpolation filter syntax
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: Sized {
+        // is_filter_switchable
+        if r.read::<1,u8>()? == 1 {
+            return Ok(Self::SWITCHABLE);
+        }
+        match r.read::<2,u8>()? {
+            0 => Ok(Self::EIGHTTAP),
+            1 => Ok(Self::EIGHTTAP_SMOOTH),
+            2 => Ok(Self::EIGHTTAP_SHARP),
+            3 => Ok(Self::BILINEAR),
+            v => Err(Error::InvalidSyntax { element: "interpolation_filter", value: v as u64 })
+        }
+    }
+}
+
+impl FromBitStream for FRAME_RESTORATION_TYPE {
+    type Error = Error;
+
+    // lr_type remapped with Remap_Lr_Type
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: Sized {
+        match r.read::<2,u8>()? {
+            0 => Ok(Self::RESTORE_NONE),
+            1 => Ok(Self::RESTORE_SWITCHABLE),
+            2 => Ok(Self::RESTORE_WIENER),
+            _ => Ok(Self::RESTORE_SGRPROJ),
         }
     }
-}
\ No newline at end of file
+}
+
+impl FromBitStream for GM_TYPE {
+    type Error = Error;
+
+    // is_global, is_rot_zoom and is_translation of 5.9.24
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: Sized {
+        if r.read::<1,u8>()? == 0 {
+            return Ok(Self::IDENTITY);
+        }
+        if r.read::<1,u8>()? == 1 {
+            return Ok(Self::ROTZOOM);
+        }
+        if r.read::<1,u8>()? == 1 {
+            Ok(Self::TRANSLATION)
+        } else {
+            Ok(Self::AFFINE)
+        }
+    }
+}
@@ -1,6 +1,50 @@
 mod impls;
 pub const SELECT_SCREEN_CONTENT_TOOLS: u8 = 2u8;
 pub const SELECT_INTEGER_MV: u8 = 2u8;
+pub const NUM_REF_FRAMES: usize = 8;
+pub const REFS_PER_FRAME: usize = 7;
+pub const PRIMARY_REF_NONE: u8 = 7u8;
+pub const SUPERRES_NUM: u32 = 8;
+pub const SUPERRES_DENOM_MIN: u32 = 9;
+pub const SUPERRES_DENOM_BITS: u32 = 3;
+pub const MAX_TILE_WIDTH: u32 = 4096;
+pub const MAX_TILE_AREA: u32 = 4096 * 2304;
+pub const MAX_TILE_ROWS: u32 = 64;
+pub const MAX_TILE_COLS: u32 = 64;
+pub const BUFFER_POOL_MAX_SIZE: usize = 10;
+pub const MAX_SEGMENTS: usize = 8;
+pub const SEG_LVL_MAX: usize = 8;
+pub const SEG_LVL_ALT_Q: usize = 0;
+pub const SEG_LVL_ALT_LF_Y_V: usize = 1;
+pub const SEG_LVL_REF_FRAME: usize = 5;
+pub const SEG_LVL_SKIP: usize = 6;
+pub const SEG_LVL_GLOBALMV: usize = 7;
+pub const MAX_LOOP_FILTER: i16 = 63;
+pub const NUM_QM_LEVELS: u8 = 16;
+pub const TOTAL_REFS_PER_FRAME: usize = 8;
+pub const RESTORATION_TILESIZE_MAX: u32 = 256;
+pub const WARPEDMODEL_PREC_BITS: u32 = 16;
+pub const GM_ABS_TRANS_BITS: u32 = 12;
+pub const GM_ABS_TRANS_ONLY_BITS: u32 = 9;
+pub const GM_ABS_ALPHA_BITS: u32 = 12;
+pub const GM_ALPHA_PREC_BITS: u32 = 15;
+pub const GM_TRANS_PREC_BITS: u32 = 6;
+pub const GM_TRANS_ONLY_PREC_BITS: u32 = 3;
+
+// Reference frame names, indices into loop_filter_ref_deltas
+pub const INTRA_FRAME: usize = 0;
+pub const LAST_FRAME: usize = 1;
+pub const LAST2_FRAME: usize = 2;
+pub const LAST3_FRAME: usize = 3;
+pub const GOLDEN_FRAME: usize = 4;
+pub const BWDREF_FRAME: usize = 5;
+pub const ALTREF2_FRAME: usize = 6;
+pub const ALTREF_FRAME: usize = 7;
+
+// 5.9.14 Segmentation_Feature_Bits, Segmentation_Feature_Signed and Segmentation_Feature_Max
+pub const SEGMENTATION_FEATURE_BITS: [u32; SEG_LVL_MAX] = [8, 6, 6, 6, 6, 3, 0, 0];
+pub const SEGMENTATION_FEATURE_SIGNED: [u8; SEG_LVL_MAX] = [1, 1, 1, 1, 1, 0, 0, 0];
+pub const SEGMENTATION_FEATURE_MAX: [i16; SEG_LVL_MAX] = [255, MAX_LOOP_FILTER, MAX_LOOP_FILTER, MAX_LOOP_FILTER, MAX_LOOP_FILTER, 7, 0, 0];
 
 
 /*
@@ -18,20 +62,21 @@ color_primaries	Name of color primaries	Description
 12	CP_SMPTE_432	SMPTE EG 432-1
 22	CP_EBU_3213	EBU Tech. 3213-E
 */
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum COLOR_PRIMARIES {
-    CP_BT_709 = 1,
-    CP_UNSPECIFIED = 2,
-    CP_BT_470_M = 4,
-    CP_BT_470_B_G = 5,
-    CP_BT_601 = 6,
-    CP_SMPTE_240 = 7,
-    CP_GENERIC_FILM = 8,
-    CP_BT_2020 = 9,
-    CP_XYZ = 10,
-    CP_SMPTE_431 = 11,
-    CP_SMPTE_432 = 12,
-    CP_EBU_3213 = 22,
+    CP_BT_709,
+    CP_UNSPECIFIED,
+    CP_BT_470_M,
+    CP_BT_470_B_G,
+    CP_BT_601,
+    CP_SMPTE_240,
+    CP_GENERIC_FILM,
+    CP_BT_2020,
+    CP_XYZ,
+    CP_SMPTE_431,
+    CP_SMPTE_432,
+    CP_EBU_3213,
+    CP_RESERVED(u8), // 0, 3, 13-21 and 23-255
 }
 
 /*transfer_characteristics is an integer that is defined by the “Transfer characteristics” section of ISO/IEC 23091-4/ITU-T H.273.
@@ -56,27 +101,26 @@ transfer_characteristics	Name of transfer characteristics	Description
 16	TC_SMPTE_2084	SMPTE ST 2084, ITU BT.2100 PQ
 17	TC_SMPTE_428	SMPTE ST 428
 18	TC_HLG	BT.2100 HLG, ARIB STD-B67 */
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum TRANSFER_CHARACTERISTICS {
-    TC_RESERVED_0 = 0,
-    TC_BT_709 = 1,
-    TC_UNSPECIFIED = 2,
-    TC_RESERVED_3 = 3,
-    TC_BT_470_M = 4,
-    TC_BT_470_B_G = 5,
-    TC_BT_601 = 6,
-    TC_SMPTE_240 = 7,
-    TC_LINEAR = 8,
-    TC_LOG_100 = 9,
-    TC_LOG_100_SQRT10 = 10,
-    TC_IEC_61966 = 11,
-    TC_BT_1361 = 12,
-    TC_SRGB = 13,
-    TC_BT_2020_10_BIT = 14,
-    TC_BT_2020_12_BIT = 15,
-    TC_SMPTE_2084 = 16,
-    TC_SMPTE_428 = 17,
-    TC_HLG = 18,
+    TC_BT_709,
+    TC_UNSPECIFIED,
+    TC_BT_470_M,
+    TC_BT_470_B_G,
+    TC_BT_601,
+    TC_SMPTE_240,
+    TC_LINEAR,
+    TC_LOG_100,
+    TC_LOG_100_SQRT10,
+    TC_IEC_61966,
+    TC_BT_1361,
+    TC_SRGB,
+    TC_BT_2020_10_BIT,
+    TC_BT_2020_12_BIT,
+    TC_SMPTE_2084,
+    TC_SMPTE_428,
+    TC_HLG,
+    TC_RESERVED(u8), // 0, 3 and 19-255
 }
 
 
@@ -96,23 +140,23 @@ pub enum TRANSFER_CHARACTERISTICS {
 12	MC_CHROMAT_NCL	Chromaticity-derived non-constant luminance
 13	MC_CHROMAT_CL	Chromaticity-derived constant luminance
 14	MC_ICTCP	BT.2100 ICtCp */
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum MATRIX_COEFFICIENTS {
-    MC_IDENTITY = 0,
-    MC_BT_709 = 1,
-    MC_UNSPECIFIED = 2,
-    MC_RESERVED_3 = 3,
-    MC_FCC = 4,
-    MC_BT_470_B_G = 5,
-    MC_BT_601 = 6,
-    MC_SMPTE_240 = 7,
-    MC_SMPTE_YCGCO = 8,
-    MC_BT_2020_NCL = 9,
-    MC_BT_2020_CL = 10,
-    MC_SMPTE_2085 = 11,
-    MC_CHROMAT_NCL = 12,
-    MC_CHROMAT_CL = 13,
-    MC_ICTCP = 14,
+    MC_IDENTITY,
+    MC_BT_709,
+    MC_UNSPECIFIED,
+    MC_FCC,
+    MC_BT_470_B_G,
+    MC_BT_601,
+    MC_SMPTE_240,
+    MC_SMPTE_YCGCO,
+    MC_BT_2020_NCL,
+    MC_BT_2020_CL,
+    MC_SMPTE_2085,
+    MC_CHROMAT_NCL,
+    MC_CHROMAT_CL,
+    MC_ICTCP,
+    MC_RESERVED(u8), // 3 and 15-255
 }
 
 
@@ -128,17 +172,18 @@ pub enum MATRIX_COEFFICIENTS {
 8	OBU_TILE_LIST	N
 9-14	Reserved	-
 15	OBU_PADDING	Either */
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum OBU_TYPE {
-    OBU_SEQUENCE_HEADER = 1,
-    OBU_TEMPORAL_DELIMITER = 2,
-    OBU_FRAME_HEADER = 3,
-    OBU_TILE_GROUP = 4,
-    OBU_METADATA = 5,
-    OBU_FRAME = 6,
-    OBU_REDUNDANT_FRAME_HEADER = 7,
-    OBU_TILE_LIST = 8,
-    OBU_PADDING = 15,
+    OBU_SEQUENCE_HEADER,
+    OBU_TEMPORAL_DELIMITER,
+    OBU_FRAME_HEADER,
+    OBU_TILE_GROUP,
+    OBU_METADATA,
+    OBU_FRAME,
+    OBU_REDUNDANT_FRAME_HEADER,
+    OBU_TILE_LIST,
+    OBU_PADDING,
+    OBU_RESERVED(u8), // 0 and 9-14
 }
 
 /*chroma_sample_position	Name of chroma sample position	Description
@@ -147,10 +192,88 @@ pub enum OBU_TYPE {
 2	CSP_COLOCATED	co-located with (0, 0) luma sample
 3	CSP_RESERVED	 
  */
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum CHROMA_SAMPLE_POSITION {
     CSP_UNKNOWN = 0,
     CSP_VERTICAL = 1,
     CSP_COLOCATED = 2,
     CSP_RESERVED = 3,
 }
+
+
+/*frame_type	Name of frame_type
+0	KEY_FRAME
+1	INTER_FRAME
+2	INTRA_ONLY_FRAME
+3	SWITCH_FRAME */
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum FRAME_TYPE {
+    #[default]
+    KEY_FRAME = 0,
+    INTER_FRAME = 1,
+    INTRA_ONLY_FRAME = 2,
+    SWITCH_FRAME = 3,
+}
+
+/*interpolation_filter	Name of interpolation_filter
+0	EIGHTTAP
+1	EIGHTTAP_SMOOTH
+2	EIGHTTAP_SHARP
+3	BILINEAR
+4	SWITCHABLE */
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum INTERPOLATION_FILTER {
+    #[default]
+    EIGHTTAP = 0,
+    EIGHTTAP_SMOOTH = 1,
+    EIGHTTAP_SHARP = 2,
+    BILINEAR = 3,
+    SWITCHABLE = 4,
+}
+
+/*lr_type	FrameRestorationType	Name of FrameRestorationType
+0	0	RESTORE_NONE
+1	3	RESTORE_SWITCHABLE
+2	1	RESTORE_WIENER
+3	2	RESTORE_SGRPROJ */
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum FRAME_RESTORATION_TYPE {
+    #[default]
+    RESTORE_NONE = 0,
+    RESTORE_WIENER = 1,
+    RESTORE_SGRPROJ = 2,
+    RESTORE_SWITCHABLE = 3,
+}
+
+/*TxMode	Name of TxMode
+0	ONLY_4X4
+1	TX_MODE_LARGEST
+2	TX_MODE_SELECT */
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum TX_MODE {
+    #[default]
+    ONLY_4X4 = 0,
+    TX_MODE_LARGEST = 1,
+    TX_MODE_SELECT = 2,
+}
+
+/*GmType	Name of GmType
+0	IDENTITY
+1	TRANSLATION
+2	ROTZOOM
+3	AFFINE */
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, PartialOrd, Ord)]
+pub enum GM_TYPE {
+    #[default]
+    IDENTITY = 0,
+    TRANSLATION = 1,
+    ROTZOOM = 2,
+    AFFINE = 3,
+}
+
+pub const METADATA_TYPE_HDR_CLL: u64 = 1;
+pub const METADATA_TYPE_HDR_MDCV: u64 = 2;
+pub const METADATA_TYPE_SCALABILITY: u64 = 3;
+pub const METADATA_TYPE_ITUT_T35: u64 = 4;
+pub const METADATA_TYPE_TIMECODE: u64 = 5;
+pub const SCALABILITY_SS: u8 = 14;
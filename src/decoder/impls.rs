@@ -0,0 +1,191 @@
+use std::io::Cursor;
+
+use bitstream_io::{BigEndian, BitReader};
+
+use crate::{
+    consts::OBU_TYPE,
+    frame_header::{FrameHeader, RefFrameStore},
+    generics::{byte_alignment, trailing_bits},
+    metadata::OBU_Metadata,
+    obu::{OperatingPoint, OBU_Sequence_Header},
+    tile_group::TileGroup,
+    Error, OBU,
+};
+
+use super::{DecodedObu, Decoder};
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Applies to the next sequence header
+    pub fn set_operating_point(&mut self, operating_point: OperatingPoint) {
+        self.operating_point = operating_point;
+    }
+
+    pub fn operating_point(&self) -> &OperatingPoint {
+        &self.operating_point
+    }
+
+    pub fn sequence_header(&self) -> Option<&OBU_Sequence_Header> {
+        self.sequence_header.as_ref()
+    }
+
+    pub fn frame_header(&self) -> Option<&FrameHeader> {
+        self.frame_header.as_ref()
+    }
+
+    pub fn ref_frames(&self) -> &RefFrameStore {
+        &self.ref_frames
+    }
+
+    // Metadata seen since the last temporal delimiter, it applies to the frames of that temporal unit
+    pub fn metadata(&self) -> &[OBU_Metadata] {
+        &self.metadata
+    }
+
+    // 7.5 Ordering of OBUs, parses the payload and updates the decoder state
+    // Errors carry the index of the OBU since the decoder was created and the bit where parsing stopped
+    pub fn decode_obu<'a>(&mut self, obu: &OBU<'a>) -> Result<DecodedObu<'a>, Error> {
+        let obu_index = self.obu_index;
+        self.obu_index += 1;
+
+        if self.drop_obu(obu) {
+            return Ok(DecodedObu::Dropped(obu.obu_type()));
+        }
+
+        let mut r = BitReader::endian(Cursor::new(obu.payload()), BigEndian);
+        self.decode_payload(obu, &mut r).map_err(|e| {
+            let bit_offset = obu.header_size() as u64 * 8 + r.position_in_bits().unwrap_or(0);
+            e.at(obu_index, bit_offset)
+        })
+    }
+
+    fn decode_payload<'a>(&mut self, obu: &OBU<'a>, r: &mut BitReader<Cursor<&'a [u8]>, BigEndian>) -> Result<DecodedObu<'a>, Error> {
+        match obu.obu_type() {
+            OBU_TYPE::OBU_TEMPORAL_DELIMITER => {
+                trailing_bits(obu.payload(), 0)?;
+                self.seen_frame_header = false;
+                self.metadata.clear();
+                Ok(DecodedObu::TemporalDelimiter)
+            }
+            OBU_TYPE::OBU_SEQUENCE_HEADER => {
+                let sequence_header = OBU_Sequence_Header::sequence_header_obu(r, &self.operating_point)?;
+                trailing_bits(obu.payload(), r.position_in_bits()?)?;
+                Ok(DecodedObu::SequenceHeader {
+                    new_coded_video_sequence: self.activate_sequence_header(sequence_header),
+                })
+            }
+            // 5.9.1 frame_header_copy( ) once SeenFrameHeader is 1
+            OBU_TYPE::OBU_FRAME_HEADER | OBU_TYPE::OBU_REDUNDANT_FRAME_HEADER if self.seen_frame_header => Ok(DecodedObu::FrameHeaderCopy),
+            OBU_TYPE::OBU_FRAME_HEADER | OBU_TYPE::OBU_REDUNDANT_FRAME_HEADER => {
+                let (frame_header, ref_frames) = self.frame_header_obu(obu, r)?;
+                trailing_bits(obu.payload(), r.position_in_bits()?)?;
+                self.activate_frame_header(frame_header, ref_frames);
+                Ok(DecodedObu::FrameHeader)
+            }
+            OBU_TYPE::OBU_FRAME if self.seen_frame_header => Err(Error::ConformanceViolation {
+                requirement: "all tile groups of a frame precede the next frame header",
+            }),
+            OBU_TYPE::OBU_FRAME => {
+                // 5.10 Frame OBU syntax
+                let (frame_header, ref_frames) = self.frame_header_obu(obu, r)?;
+                byte_alignment(r)?;
+                self.activate_frame_header(frame_header, ref_frames);
+                let start_position = (r.position_in_bits()? / 8) as usize;
+                Ok(DecodedObu::Frame(self.tile_group_obu(&obu.payload()[start_position..], true)?))
+            }
+            OBU_TYPE::OBU_TILE_GROUP => Ok(DecodedObu::TileGroup(self.tile_group_obu(obu.payload(), false)?)),
+            OBU_TYPE::OBU_METADATA => {
+                let metadata = OBU_Metadata::metadata_obu(obu.payload())?;
+                self.metadata.push(metadata.clone());
+                Ok(DecodedObu::Metadata(metadata))
+            }
+            // 5.12 Padding OBU syntax, the payload carries no information
+            OBU_TYPE::OBU_PADDING => Ok(DecodedObu::Padding),
+            obu_type => Ok(DecodedObu::Skipped(obu_type)),
+        }
+    }
+
+    // 5.9.1 General frame header OBU syntax
+    // Marking and ref_order_hint change a copy of the slots, kept only once the whole header parsed
+    fn frame_header_obu<R: bitstream_io::BitRead + ?Sized>(&self, obu: &OBU, r: &mut R) -> Result<(FrameHeader, RefFrameStore), Error> {
+        let sequence_header = self
+            .sequence_header
+            .as_ref()
+            .ok_or(Error::ConformanceViolation {
+                requirement: "a sequence header precedes the first frame header",
+            })?;
+
+        let mut ref_frames = self.ref_frames.clone();
+        let frame_header = FrameHeader::uncompressed_header(r, sequence_header, obu.obu_extension_header(), &mut ref_frames)?;
+        Ok((frame_header, ref_frames))
+    }
+
+    fn activate_frame_header(&mut self, frame_header: FrameHeader, ref_frames: RefFrameStore) {
+        // Nothing after the header changes what is saved, so the slots can be refreshed right away
+        self.ref_frames = ref_frames;
+        self.ref_frames.update(&frame_header);
+        self.seen_frame_header = frame_header.show_existing_frame() == 0;
+        self.tile_num = 0;
+        self.frame_header = Some(frame_header);
+    }
+
+    // 5.11.1 General tile group OBU syntax, the last tile of the frame ends the frame
+    // A frame OBU holds every tile of the frame in its single tile group
+    fn tile_group_obu<'a>(&mut self, data: &'a [u8], in_frame_obu: bool) -> Result<TileGroup<'a>, Error> {
+        let frame_header = self
+            .frame_header
+            .as_ref()
+            .filter(|_| self.seen_frame_header)
+            .ok_or(Error::ConformanceViolation {
+                requirement: "a frame header precedes each tile group",
+            })?;
+
+        let tile_group = TileGroup::tile_group_obu(data, frame_header.tile_info())?;
+        if in_frame_obu && tile_group.tile_start_and_end_present_flag() != 0 {
+            return Err(Error::ConformanceViolation {
+                requirement: "tile_start_and_end_present_flag is 0 in a frame OBU",
+            });
+        }
+        if tile_group.tg_start() != self.tile_num {
+            return Err(Error::ConformanceViolation {
+                requirement: "tg_start is equal to TileNum",
+            });
+        }
+
+        self.tile_num = tile_group.tg_end() + 1;
+        if tile_group.tg_end() == frame_header.tile_info().num_tiles() - 1 {
+            self.seen_frame_header = false;
+        }
+        Ok(tile_group)
+    }
+
+    // 7.1 Layer dropping against OperatingPointIdc of the active sequence header
+    fn drop_obu(&self, obu: &OBU) -> bool {
+        if obu.obu_type() == OBU_TYPE::OBU_SEQUENCE_HEADER || obu.obu_type() == OBU_TYPE::OBU_TEMPORAL_DELIMITER {
+            return false;
+        }
+
+        let operating_point_idc = self.sequence_header.as_ref().map_or(0u16, |s| s.c_operating_point_idc());
+        match obu.obu_extension_header() {
+            Some(extension_header) if operating_point_idc != 0 => !extension_header.in_operating_point(operating_point_idc),
+            _ => false,
+        }
+    }
+
+    // A sequence header that changes starts a new coded video sequence,
+    // a repeated one still takes the operating point chosen for it
+    fn activate_sequence_header(&mut self, sequence_header: OBU_Sequence_Header) -> bool {
+        let new_coded_video_sequence = !self
+            .sequence_header
+            .as_ref()
+            .is_some_and(|active| active.same_syntax(&sequence_header));
+        if new_coded_video_sequence {
+            log::debug!("decoder->activate_sequence_header(): new coded video sequence");
+        }
+        self.sequence_header = Some(sequence_header);
+        new_coded_video_sequence
+    }
+}
@@ -0,0 +1,62 @@
+mod impls;
+mod push;
+
+use std::{collections::VecDeque, ops::Range};
+
+use crate::{
+    frame_header::{FrameHeader, RefFrameStore},
+    metadata::OBU_Metadata,
+    obu::{OperatingPoint, OBU_Header, OBU_Sequence_Header},
+    tile_group::TileGroup,
+    leb_128,
+};
+
+// Persistent state carried from one OBU to the next
+#[derive(Debug, Default, Clone)]
+pub struct Decoder {
+    operating_point: OperatingPoint,              // choose_operating_point( )
+    sequence_header: Option<OBU_Sequence_Header>, // active sequence header
+    frame_header: Option<FrameHeader>,            // header of the current frame
+    seen_frame_header: bool,                      // SeenFrameHeader
+    tile_num: u32,                                // TileNum, the tile the next tile group starts with
+    ref_frames: RefFrameStore,
+    metadata: Vec<OBU_Metadata>,                  // metadata OBUs of the current temporal unit
+    obu_index: usize,                             // OBUs passed to decode_obu so far
+    buffer: Vec<u8>,                              // bytes given to send_data that do not form a whole OBU yet
+    partial_obu: Option<(OBU_Header, leb_128, usize)>, // header, obu_size and header size of the OBU starting the buffer
+    frame: Option<DecodedFrame>,                  // frame still waiting for tile groups
+    frames: VecDeque<DecodedFrame>,               // frames ready for get_frame
+}
+
+// What send_data left for the caller
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DecodeStatus {
+    NeedMoreData, // no frame is ready, the rest of the data is buffered
+    FrameReady,   // get_frame returns at least one frame
+}
+
+// A frame returned by get_frame, owns a copy of its tile data
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DecodedFrame {
+    frame_header: FrameHeader,
+    metadata: Vec<OBU_Metadata>,  // metadata of the temporal unit
+    tile_data: Vec<u8>,           // tiles back to back
+    tiles: Vec<(u32, Range<usize>)>, // tile number and its bytes in tile_data
+}
+
+// What decode_obu did with an OBU
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DecodedObu<'a> {
+    TemporalDelimiter,
+    SequenceHeader {
+        new_coded_video_sequence: bool, // header differs from the active one
+    },
+    FrameHeader,
+    FrameHeaderCopy, // redundant copy of the current frame header
+    TileGroup(TileGroup<'a>),
+    Frame(TileGroup<'a>), // frame header followed by its tile group
+    Metadata(OBU_Metadata),
+    Padding,
+    Dropped(crate::consts::OBU_TYPE), // not in the chosen operating point
+    Skipped(crate::consts::OBU_TYPE), // not parsed (yet)
+}
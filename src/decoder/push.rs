@@ -0,0 +1,158 @@
+use std::mem;
+
+use crate::{frame_header::{Film_Grain_Params, FrameHeader}, metadata::OBU_Metadata, tile_group::{Tile, TileGroup}, Error, OBU};
+
+use super::{DecodeStatus, DecodedFrame, DecodedObu, Decoder};
+
+impl Decoder {
+    // 5.2 Low overhead bitstream format, fed in chunks of any size
+    // Whole OBUs are decoded and dropped from the buffer, a partial OBU waits for the next call
+    pub fn send_data(&mut self, data: &[u8]) -> Result<DecodeStatus, Error> {
+        self.buffer.extend_from_slice(data);
+
+        let mut buffer = mem::take(&mut self.buffer);
+        let mut position = 0;
+        let result = self.decode_buffer(&buffer, &mut position);
+        buffer.drain(..position);
+        self.buffer = buffer;
+        result?;
+
+        Ok(if self.frames.is_empty() { DecodeStatus::NeedMoreData } else { DecodeStatus::FrameReady })
+    }
+
+    // Next frame in decoding order, frames with show_frame equal to 0 are returned as well
+    pub fn get_frame(&mut self) -> Option<DecodedFrame> {
+        self.frames.pop_front()
+    }
+
+    // Bytes of a partial OBU held back by send_data
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn decode_buffer(&mut self, buffer: &[u8], position: &mut usize) -> Result<(), Error> {
+        while *position < buffer.len() {
+            let data = &buffer[*position..];
+            // The header of a partial OBU is parsed once and kept until its payload is complete
+            let (obu_header, obu_size, header_size) = match self.partial_obu.take() {
+                Some(partial_obu) => partial_obu,
+                None => match OBU::open_header(data) {
+                    Ok(header) if header.0.obu_has_size_field() == 1 => header,
+                    // Without obu_size the end of the OBU is unknown, and so is the start of the next one
+                    Ok(_) => {
+                        *position = buffer.len();
+                        return Err(Error::ConformanceViolation {
+                            requirement: "obu_has_size_field is 1 in a low overhead bitstream",
+                        }
+                        .at(self.obu_index, 0));
+                    }
+                    Err(e) if matches!(e.kind(), Error::UnexpectedEof) => return Ok(()),
+                    Err(e) => {
+                        *position = buffer.len();
+                        return Err(e.with_obu_index(self.obu_index));
+                    }
+                },
+            };
+            if (data.len() as u64) < header_size as u64 + obu_size.value() {
+                self.partial_obu = Some((obu_header, obu_size, header_size));
+                return Ok(());
+            }
+            let obu = OBU::with_payload(obu_header, obu_size, header_size, data).map_err(|e| e.with_obu_index(self.obu_index))?;
+
+            // A complete OBU is consumed even when decoding fails, the next call starts after it
+            *position += obu.total_size();
+            let obu_index = self.obu_index;
+            match self.decode_obu(&obu)? {
+                DecodedObu::FrameHeader => self.start_frame(),
+                DecodedObu::Frame(tile_group) => {
+                    let started = self.start_frame();
+                    self.push_tile_group(&tile_group);
+                    started
+                }
+                DecodedObu::TileGroup(tile_group) => {
+                    self.push_tile_group(&tile_group);
+                    Ok(())
+                }
+                _ => Ok(()),
+            }
+            .map_err(|e| e.at(obu_index, 0))?;
+        }
+        Ok(())
+    }
+
+    // A frame still waiting for tile groups is dropped with an error, the new frame replaces it
+    fn start_frame(&mut self) -> Result<(), Error> {
+        let Some(frame_header) = self.frame_header.clone() else {
+            return Ok(());
+        };
+
+        let frame = DecodedFrame {
+            frame_header,
+            metadata: self.metadata.clone(),
+            tile_data: Vec::new(),
+            tiles: Vec::new(),
+        };
+        let incomplete = self.frame.take();
+        // show_existing_frame has no tile groups
+        if frame.frame_header.show_existing_frame() == 1 {
+            self.frames.push_back(frame);
+        } else {
+            self.frame = Some(frame);
+        }
+
+        match incomplete {
+            Some(_) => Err(Error::ConformanceViolation {
+                requirement: "all tile groups of a frame precede the next frame header",
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn push_tile_group(&mut self, tile_group: &TileGroup) {
+        let Some(frame) = self.frame.as_mut() else {
+            return;
+        };
+
+        for tile in tile_group.tiles() {
+            let start = frame.tile_data.len();
+            frame.tile_data.extend_from_slice(tile.data());
+            frame.tiles.push((tile.tile_num(), start..frame.tile_data.len()));
+        }
+
+        // The last tile of the frame cleared SeenFrameHeader
+        if !self.seen_frame_header {
+            self.frames.extend(self.frame.take());
+        }
+    }
+}
+
+impl DecodedFrame {
+    pub fn frame_header(&self) -> &FrameHeader {
+        &self.frame_header
+    }
+
+    // Metadata OBUs of the temporal unit up to the frame header
+    pub fn metadata(&self) -> &[OBU_Metadata] {
+        &self.metadata
+    }
+
+    pub fn is_shown(&self) -> bool {
+        self.frame_header.show_existing_frame() == 1 || self.frame_header.show_frame() == 1
+    }
+
+    // Grain to synthesize on output, loaded from the shown frame for show_existing_frame
+    pub fn film_grain_params(&self) -> &Film_Grain_Params {
+        self.frame_header.film_grain_params()
+    }
+
+    pub fn num_tiles(&self) -> u32 {
+        self.tiles.len() as u32
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = Tile<'_>> {
+        let tile_cols = self.frame_header.tile_info().tile_cols();
+        self.tiles
+            .iter()
+            .map(move |(tile_num, range)| Tile::new(*tile_num, tile_cols, &self.tile_data[range.clone()]))
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::VecDeque;
+
+use crate::{
+    consts::{BUFFER_POOL_MAX_SIZE, FRAME_TYPE, NUM_REF_FRAMES},
+    frame_header::FrameHeader,
+    level::Level,
+    obu::OBU_Sequence_Header,
+    temporal_unit::TemporalUnit,
+};
+
+use super::{DecoderModel, Decoder_Model_Violation};
+
+// Times are sums of f64 ticks, differences below this are rounding and not a violation
+const TIME_EPSILON: f64 = 1e-9;
+
+impl DecoderModel {
+    // None when the chosen operating point has no decoder model
+    pub fn new(sequence_header: &OBU_Sequence_Header) -> Option<Self> {
+        let operating_point = sequence_header.operating_point();
+        let decoder_model_present_for_this_op = sequence_header.decoder_model_present_for_this_op();
+        if decoder_model_present_for_this_op.get(operating_point) != Some(&1) {
+            return None;
+        }
+
+        let timing_info = sequence_header.timing_info()?;
+        let decoder_model_info = sequence_header.decoder_model_info()?;
+        let operating_parameters_info = sequence_header.operating_parameters_info()?;
+        let time_scale = timing_info.time_scale() as f64;
+        if time_scale == 0.0 {
+            return None;
+        }
+
+        // Operating_Parameters_Info only has entries for operating points with a decoder model
+        let idx = decoder_model_present_for_this_op[..operating_point].iter().filter(|present| **present == 1).count();
+        let level = Level::from_seq_level_idx(sequence_header.seq_level_idx()[operating_point]);
+        let bit_rate = level
+            .and_then(|level| level.max_bitrate(sequence_header.seq_tier()[operating_point], sequence_header.seq_profile()))
+            .map(|bit_rate| bit_rate as f64);
+
+        Some(Self {
+            dec_ct: decoder_model_info.num_units_in_decoding_tick() as f64 / time_scale,
+            disp_ct: timing_info.num_units_in_display_tick() as f64 / time_scale,
+            ticks_per_picture: timing_info.num_ticks_per_picture_minus_1().map(|ticks| ticks as f64 + 1.0),
+            presentation_delay_modulo: 1u64 << (decoder_model_info.frame_presentation_delay_length_minus_1() as u32 + 1),
+            decoder_buffer_delay: operating_parameters_info.decoder_buffer_delay()[idx] as f64 / 90000.0,
+            encoder_buffer_delay: operating_parameters_info.encoder_buffer_delay()[idx] as f64 / 90000.0,
+            low_delay_mode_flag: operating_parameters_info.low_delay_mode_flag()[idx],
+            bit_rate,
+            buffer_size: bit_rate,
+            max_decode_rate: level.map(|level| level.max_decode_rate() as f64),
+            operating_point,
+
+            frames: 0,
+            last_bit_arrival: 0.0,
+            removal_rap: 0.0,
+            last_decoded: 0.0,
+            presentation_base: None,
+            shown: 0,
+            smoothing_buffer: VecDeque::new(),
+            ref_slots: [None; NUM_REF_FRAMES],
+            decoded_at: Vec::new(),
+            presentations: Vec::new(),
+            violations: Vec::new(),
+        })
+    }
+
+    // Temporal units have to come from an assembler set to the same operating point
+    pub fn check_temporal_unit(&mut self, temporal_unit: &TemporalUnit) {
+        // OBUs in front of the first frame header belong to it
+        let frames_size: usize = temporal_unit.frames().iter().map(|frame| frame.size()).sum();
+        let mut leading = temporal_unit.obus().iter().map(|obu| obu.total_size()).sum::<usize>() - frames_size;
+        for frame in temporal_unit.frames() {
+            let frame_size = frame.size() + std::mem::take(&mut leading);
+            self.frame(frame.frame_header(), frame_size as f64 * 8.0);
+        }
+    }
+
+    pub fn operating_point(&self) -> usize {
+        self.operating_point
+    }
+
+    pub fn violations(&self) -> &[Decoder_Model_Violation] {
+        &self.violations
+    }
+
+    fn frame(&mut self, frame_header: &FrameHeader, bits: f64) {
+        let frame = self.frames;
+        self.frames += 1;
+
+        // E.3 Smoothing buffer, frames without buffer_removal_time are removed as soon as they arrived
+        let random_access_point = frame_header.frame_type() == FRAME_TYPE::KEY_FRAME && frame_header.show_frame() == 1;
+        let buffer_removal_time = frame_header.buffer_removal_time().get(self.operating_point).copied().flatten();
+        let scheduled_removal = match buffer_removal_time {
+            _ if frame == 0 => Some(self.decoder_buffer_delay),
+            Some(buffer_removal_time) => Some(self.removal_rap + buffer_removal_time as f64 * self.dec_ct),
+            None => None,
+        };
+
+        let first_bit_arrival = match scheduled_removal {
+            _ if frame == 0 => 0.0,
+            Some(removal) => self.last_bit_arrival.max(removal - self.decoder_buffer_delay - self.encoder_buffer_delay),
+            None => self.last_bit_arrival,
+        };
+        let last_bit_arrival = first_bit_arrival + self.bit_rate.map_or(0.0, |bit_rate| bits / bit_rate);
+        self.last_bit_arrival = last_bit_arrival;
+
+        let removal = match scheduled_removal {
+            Some(removal) if last_bit_arrival > removal + TIME_EPSILON && self.low_delay_mode_flag == 0 => {
+                self.violations.push(Decoder_Model_Violation::SmoothingBufferUnderflow {
+                    frame,
+                    removal_time: removal,
+                    last_bit_arrival,
+                });
+                last_bit_arrival
+            }
+            // Low delay mode waits for the whole frame
+            Some(removal) => removal.max(last_bit_arrival),
+            None => last_bit_arrival,
+        };
+        if random_access_point || frame == 0 {
+            self.removal_rap = removal;
+        }
+
+        while self.smoothing_buffer.front().is_some_and(|(removal_time, _)| *removal_time <= last_bit_arrival) {
+            self.smoothing_buffer.pop_front();
+        }
+        self.smoothing_buffer.push_back((removal, bits));
+        let buffered: f64 = self.smoothing_buffer.iter().map(|(_, bits)| bits).sum();
+        if let Some(buffer_size) = self.buffer_size.filter(|buffer_size| buffered > *buffer_size) {
+            self.violations.push(Decoder_Model_Violation::SmoothingBufferOverflow {
+                frame,
+                bits: buffered,
+                buffer_size,
+            });
+        }
+
+        if frame_header.show_existing_frame() == 1 {
+            let shown_frame = self.ref_slots[frame_header.frame_to_show_map_idx() as usize];
+            let decoded_time = shown_frame
+                .and_then(|shown_frame| self.decoded_at.iter().find(|(id, _)| *id == shown_frame))
+                .map_or(removal, |(_, decoded_time)| *decoded_time);
+            self.present(shown_frame.unwrap_or(frame), frame_header, decoded_time);
+            if frame_header.frame_type() == FRAME_TYPE::KEY_FRAME {
+                self.refresh(shown_frame.unwrap_or(frame), frame_header.refresh_frame_flags(), decoded_time);
+            }
+            return;
+        }
+
+        // E.4 Decoding process, one frame at a time at MaxDecodeRate
+        let frame_size = frame_header.frame_size();
+        let samples = frame_size.upscaled_width() as f64 * frame_size.frame_height() as f64;
+        let decoded_time = removal.max(self.last_decoded) + self.max_decode_rate.map_or(0.0, |max_decode_rate| samples / max_decode_rate);
+        self.last_decoded = decoded_time;
+
+        // E.5 Frame buffers, the new frame needs a buffer next to everything referenced or waiting to be shown
+        self.presentations.retain(|(_, presentation_time)| *presentation_time > decoded_time);
+        let mut buffers: Vec<u64> = self.ref_slots.iter().flatten().copied().collect();
+        buffers.extend(self.presentations.iter().map(|(id, _)| *id));
+        buffers.push(frame);
+        buffers.sort_unstable();
+        buffers.dedup();
+        if buffers.len() > BUFFER_POOL_MAX_SIZE {
+            self.violations.push(Decoder_Model_Violation::DecodedFrameBufferOverflow {
+                frame,
+                frames: buffers.len(),
+            });
+        }
+
+        if frame_header.show_frame() == 1 {
+            self.present(frame, frame_header, decoded_time);
+        }
+        self.refresh(frame, frame_header.refresh_frame_flags(), decoded_time);
+    }
+
+    // E.6 Output process, PresentationTime follows frame_presentation_time or the constant picture interval
+    fn present(&mut self, frame: u64, frame_header: &FrameHeader, decoded_time: f64) {
+        let frame_presentation_time = frame_header.frame_presentation_time().unwrap_or(0);
+        let Some((base_time, base_presentation_time)) = self.presentation_base else {
+            self.presentation_base = Some((decoded_time, frame_presentation_time));
+            self.presentations.push((frame, decoded_time));
+            return;
+        };
+
+        self.shown += 1;
+        let presentation_time = match (frame_header.frame_presentation_time(), self.ticks_per_picture) {
+            (Some(frame_presentation_time), _) => {
+                let delta = (frame_presentation_time as u64 + self.presentation_delay_modulo - base_presentation_time as u64) % self.presentation_delay_modulo;
+                base_time + delta as f64 * self.disp_ct
+            }
+            (None, Some(ticks_per_picture)) => base_time + self.shown as f64 * ticks_per_picture * self.disp_ct,
+            (None, None) => return,
+        };
+
+        if presentation_time + TIME_EPSILON < decoded_time {
+            self.violations.push(Decoder_Model_Violation::PresentationTooEarly {
+                frame,
+                presentation_time,
+                decoded_time,
+            });
+        }
+        self.presentations.push((frame, presentation_time));
+    }
+
+    fn refresh(&mut self, frame: u64, refresh_frame_flags: u8, decoded_time: f64) {
+        for (i, slot) in self.ref_slots.iter_mut().enumerate() {
+            if (refresh_frame_flags >> i) & 1 == 1 {
+                *slot = Some(frame);
+            }
+        }
+        let ref_slots = self.ref_slots;
+        self.decoded_at.retain(|(id, _)| ref_slots.contains(&Some(*id)));
+        if ref_slots.contains(&Some(frame)) && !self.decoded_at.iter().any(|(id, _)| *id == frame) {
+            self.decoded_at.push((frame, decoded_time));
+        }
+    }
+}
@@ -0,0 +1,61 @@
+mod impls;
+
+use std::collections::VecDeque;
+
+use crate::consts::NUM_REF_FRAMES;
+
+// Annex E Hypothetical decoder for the chosen operating point, times are in seconds
+#[derive(Debug, PartialEq, Clone)]
+pub struct DecoderModel {
+    dec_ct: f64,                      // DecCT
+    disp_ct: f64,                     // DispCT
+    ticks_per_picture: Option<f64>,   // num_ticks_per_picture_minus_1 + 1 with equal_picture_interval
+    presentation_delay_modulo: u64,   // frame_presentation_time wraps around at this value
+    decoder_buffer_delay: f64,        // decoder_buffer_delay / 90000
+    encoder_buffer_delay: f64,        // encoder_buffer_delay / 90000
+    low_delay_mode_flag: u8,
+    bit_rate: Option<f64>,            // BitRate, MaxBitrate of the level in bits per second
+    buffer_size: Option<f64>,         // BufferSize, one second of BitRate
+    max_decode_rate: Option<f64>,     // MaxDecodeRate of the level
+    operating_point: usize,
+
+    frames: u64,                      // frames seen so far in decoding order
+    last_bit_arrival: f64,            // LastBitArrival of the previous frame
+    removal_rap: f64,                 // ScheduledRemovalTiming of the last random access point
+    last_decoded: f64,                // when the previous frame finished decoding
+    presentation_base: Option<(f64, u32)>, // PresentationTime and frame_presentation_time of the first shown frame
+    shown: u64,                       // shown frames since the first one
+    smoothing_buffer: VecDeque<(f64, f64)>, // removal time and bits of frames still in the smoothing buffer
+    ref_slots: [Option<u64>; NUM_REF_FRAMES], // frame held by each reference slot
+    decoded_at: Vec<(u64, f64)>,      // decode time of the frames held by ref_slots
+    presentations: Vec<(u64, f64)>,   // frames waiting to be presented and their PresentationTime
+    violations: Vec<Decoder_Model_Violation>,
+}
+
+// One broken constraint of Annex E, frame counts decoded frames and show_existing_frame headers from 0
+#[derive(Debug, PartialEq, Clone)]
+pub enum Decoder_Model_Violation {
+    // The last bit of a frame arrives after its removal time
+    SmoothingBufferUnderflow {
+        frame: u64,
+        removal_time: f64,
+        last_bit_arrival: f64,
+    },
+    // The smoothing buffer holds more than BufferSize bits
+    SmoothingBufferOverflow {
+        frame: u64,
+        bits: f64,
+        buffer_size: f64,
+    },
+    // More than BUFFER_POOL_MAX_SIZE frames are referenced or waiting to be presented
+    DecodedFrameBufferOverflow {
+        frame: u64,
+        frames: usize,
+    },
+    // A frame is due for presentation before it finished decoding
+    PresentationTooEarly {
+        frame: u64,
+        presentation_time: f64,
+        decoded_time: f64,
+    },
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+
+// Errors returned while parsing a bitstream
+#[derive(Debug)]
+pub enum Error {
+    // The data ended in the middle of a syntax element
+    UnexpectedEof,
+    // A syntax element holds a value the specification does not allow
+    InvalidSyntax {
+        element: &'static str,
+        value: u64,
+    },
+    // Valid syntax this crate cannot handle yet
+    Unsupported {
+        feature: &'static str,
+    },
+    // The bitstream breaks a requirement of bitstream conformance
+    ConformanceViolation {
+        requirement: &'static str,
+    },
+    // trailing_bits( ) does not start where parsing of the payload stopped
+    PayloadSizeMismatch {
+        obu_size: u64,
+        payload_bits: u64,
+    },
+    // The configured operating point does not exist in the sequence header
+    InvalidOperatingPoint {
+        operating_point: usize,
+        operating_points_cnt: usize,
+    },
+    // Any other error from the underlying reader
+    Io(std::io::Error),
+    // Where parsing failed, bit_offset counts from the first bit of the OBU header
+    Obu {
+        obu_index: usize,
+        bit_offset: u64,
+        source: Box<Error>,
+    },
+}
+
+impl Error {
+    pub(crate) fn at(self, obu_index: usize, bit_offset: u64) -> Self {
+        match self {
+            Self::Obu { .. } => self,
+            source => Self::Obu {
+                obu_index,
+                bit_offset,
+                source: Box::new(source),
+            },
+        }
+    }
+
+    // Replaces the OBU index of an error located by a parser that only saw a single OBU
+    pub(crate) fn with_obu_index(self, obu_index: usize) -> Self {
+        match self {
+            Self::Obu { bit_offset, source, .. } => Self::Obu {
+                obu_index,
+                bit_offset,
+                source,
+            },
+            source => source.at(obu_index, 0),
+        }
+    }
+
+    // The error without its location
+    pub fn kind(&self) -> &Error {
+        match self {
+            Self::Obu { source, .. } => source.kind(),
+            error => error,
+        }
+    }
+
+    pub fn obu_index(&self) -> Option<usize> {
+        match self {
+            Self::Obu { obu_index, .. } => Some(*obu_index),
+            _ => None,
+        }
+    }
+
+    pub fn bit_offset(&self) -> Option<u64> {
+        match self {
+            Self::Obu { bit_offset, .. } => Some(*bit_offset),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEof => write!(f, "unexpected end of data"),
+            Self::InvalidSyntax { element, value } => write!(f, "invalid {element} value {value}"),
+            Self::Unsupported { feature } => write!(f, "{feature} is not supported"),
+            Self::ConformanceViolation { requirement } => write!(f, "bitstream conformance violation: {requirement}"),
+            Self::PayloadSizeMismatch { obu_size, payload_bits } => {
+                write!(f, "OBU payload of {obu_size} bytes does not end with trailing bits after {payload_bits} bits")
+            }
+            Self::InvalidOperatingPoint {
+                operating_point,
+                operating_points_cnt,
+            } => write!(f, "operating point {operating_point} out of range, the sequence has {operating_points_cnt}"),
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Obu {
+                obu_index,
+                bit_offset,
+                source,
+            } => write!(f, "OBU {obu_index} at bit {bit_offset}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Obu { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::UnexpectedEof => Self::UnexpectedEof,
+            _ => Self::Io(e),
+        }
+    }
+}
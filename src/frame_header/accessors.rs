@@ -0,0 +1,609 @@
+use crate::consts::{FRAME_RESTORATION_TYPE, FRAME_TYPE, GM_TYPE, INTERPOLATION_FILTER, MAX_SEGMENTS, REFS_PER_FRAME, SEG_LVL_MAX, TOTAL_REFS_PER_FRAME, TX_MODE};
+
+use super::{
+    Cdef_Params, Delta_Lf_Params, Delta_Q_Params, Film_Grain_Params, FrameHeader, Frame_Size, Global_Motion_Params, Loop_Filter_Params, Lr_Params, Quantization_Params, Ref_Frame, Segmentation_Params, TileLayout,
+    Tile_Info,
+};
+
+impl FrameHeader {
+    pub fn show_existing_frame(&self) -> u8 {
+        self.show_existing_frame
+    }
+
+    pub fn frame_to_show_map_idx(&self) -> u8 {
+        self.frame_to_show_map_idx
+    }
+
+    pub fn frame_presentation_time(&self) -> Option<u32> {
+        self.frame_presentation_time
+    }
+
+    pub fn display_frame_id(&self) -> Option<u32> {
+        self.display_frame_id
+    }
+
+    pub fn frame_type(&self) -> FRAME_TYPE {
+        self.frame_type
+    }
+
+    pub fn show_frame(&self) -> u8 {
+        self.show_frame
+    }
+
+    pub fn showable_frame(&self) -> u8 {
+        self.showable_frame
+    }
+
+    pub fn error_resilient_mode(&self) -> u8 {
+        self.error_resilient_mode
+    }
+
+    pub fn disable_cdf_update(&self) -> u8 {
+        self.disable_cdf_update
+    }
+
+    pub fn allow_screen_content_tools(&self) -> u8 {
+        self.allow_screen_content_tools
+    }
+
+    pub fn force_integer_mv(&self) -> u8 {
+        self.force_integer_mv
+    }
+
+    pub fn current_frame_id(&self) -> u32 {
+        self.current_frame_id
+    }
+
+    pub fn frame_size_override_flag(&self) -> u8 {
+        self.frame_size_override_flag
+    }
+
+    pub fn order_hint(&self) -> u8 {
+        self.order_hint
+    }
+
+    pub fn primary_ref_frame(&self) -> u8 {
+        self.primary_ref_frame
+    }
+
+    // Indexed by operating point, None when not coded for it
+    pub fn buffer_removal_time(&self) -> &[Option<u32>] {
+        &self.buffer_removal_time
+    }
+
+    pub fn refresh_frame_flags(&self) -> u8 {
+        self.refresh_frame_flags
+    }
+
+    pub fn ref_order_hint(&self) -> Option<&[u8]> {
+        self.ref_order_hint.as_deref()
+    }
+
+    pub fn frame_size(&self) -> &Frame_Size {
+        &self.frame_size
+    }
+
+    pub fn allow_intrabc(&self) -> u8 {
+        self.allow_intrabc
+    }
+
+    pub fn frame_refs_short_signaling(&self) -> u8 {
+        self.frame_refs_short_signaling
+    }
+
+    pub fn last_frame_idx(&self) -> u8 {
+        self.last_frame_idx
+    }
+
+    pub fn gold_frame_idx(&self) -> u8 {
+        self.gold_frame_idx
+    }
+
+    pub fn ref_frame_idx(&self) -> &[u8; REFS_PER_FRAME] {
+        &self.ref_frame_idx
+    }
+
+    pub fn delta_frame_id_minus_1(&self) -> Option<&[u32]> {
+        self.delta_frame_id_minus_1.as_deref()
+    }
+
+    pub fn allow_high_precision_mv(&self) -> u8 {
+        self.allow_high_precision_mv
+    }
+
+    pub fn interpolation_filter(&self) -> INTERPOLATION_FILTER {
+        self.interpolation_filter
+    }
+
+    pub fn is_motion_mode_switchable(&self) -> u8 {
+        self.is_motion_mode_switchable
+    }
+
+    pub fn use_ref_frame_mvs(&self) -> u8 {
+        self.use_ref_frame_mvs
+    }
+
+    pub fn order_hints(&self) -> &[u8; TOTAL_REFS_PER_FRAME] {
+        &self.order_hints
+    }
+
+    pub fn ref_frame_sign_bias(&self) -> &[u8; TOTAL_REFS_PER_FRAME] {
+        &self.ref_frame_sign_bias
+    }
+
+    pub fn disable_frame_end_update_cdf(&self) -> u8 {
+        self.disable_frame_end_update_cdf
+    }
+
+    pub fn tile_info(&self) -> &Tile_Info {
+        &self.tile_info
+    }
+
+    pub fn tile_layout(&self) -> TileLayout {
+        self.tile_info.layout(&self.frame_size)
+    }
+
+    pub fn quantization_params(&self) -> &Quantization_Params {
+        &self.quantization_params
+    }
+
+    pub fn segmentation_params(&self) -> &Segmentation_Params {
+        &self.segmentation_params
+    }
+
+    pub fn delta_q_params(&self) -> &Delta_Q_Params {
+        &self.delta_q_params
+    }
+
+    pub fn delta_lf_params(&self) -> &Delta_Lf_Params {
+        &self.delta_lf_params
+    }
+
+    pub fn loop_filter_params(&self) -> &Loop_Filter_Params {
+        &self.loop_filter_params
+    }
+
+    pub fn cdef_params(&self) -> &Cdef_Params {
+        &self.cdef_params
+    }
+
+    pub fn lr_params(&self) -> &Lr_Params {
+        &self.lr_params
+    }
+
+    pub fn tx_mode(&self) -> TX_MODE {
+        self.tx_mode
+    }
+
+    pub fn reference_select(&self) -> u8 {
+        self.reference_select
+    }
+
+    pub fn skip_mode_present(&self) -> u8 {
+        self.skip_mode_present
+    }
+
+    pub fn skip_mode_frame(&self) -> &[usize; 2] {
+        &self.skip_mode_frame
+    }
+
+    pub fn allow_warped_motion(&self) -> u8 {
+        self.allow_warped_motion
+    }
+
+    pub fn reduced_tx_set(&self) -> u8 {
+        self.reduced_tx_set
+    }
+
+    pub fn global_motion_params(&self) -> &Global_Motion_Params {
+        &self.global_motion_params
+    }
+
+    pub fn film_grain_params(&self) -> &Film_Grain_Params {
+        &self.film_grain_params
+    }
+}
+
+impl Quantization_Params {
+    pub fn base_q_idx(&self) -> u8 {
+        self.base_q_idx
+    }
+
+    pub fn delta_q_y_dc(&self) -> i8 {
+        self.delta_q_y_dc
+    }
+
+    pub fn diff_uv_delta(&self) -> u8 {
+        self.diff_uv_delta
+    }
+
+    pub fn delta_q_u_dc(&self) -> i8 {
+        self.delta_q_u_dc
+    }
+
+    pub fn delta_q_u_ac(&self) -> i8 {
+        self.delta_q_u_ac
+    }
+
+    pub fn delta_q_v_dc(&self) -> i8 {
+        self.delta_q_v_dc
+    }
+
+    pub fn delta_q_v_ac(&self) -> i8 {
+        self.delta_q_v_ac
+    }
+
+    pub fn using_qmatrix(&self) -> u8 {
+        self.using_qmatrix
+    }
+
+    pub fn qm_y(&self) -> u8 {
+        self.qm_y
+    }
+
+    pub fn qm_u(&self) -> u8 {
+        self.qm_u
+    }
+
+    pub fn qm_v(&self) -> u8 {
+        self.qm_v
+    }
+}
+
+impl Segmentation_Params {
+    pub fn segmentation_enabled(&self) -> u8 {
+        self.segmentation_enabled
+    }
+
+    pub fn segmentation_update_map(&self) -> u8 {
+        self.segmentation_update_map
+    }
+
+    pub fn segmentation_temporal_update(&self) -> u8 {
+        self.segmentation_temporal_update
+    }
+
+    pub fn segmentation_update_data(&self) -> u8 {
+        self.segmentation_update_data
+    }
+
+    // FeatureEnabled
+    pub fn feature_enabled(&self) -> &[[u8; SEG_LVL_MAX]; MAX_SEGMENTS] {
+        &self.feature_enabled
+    }
+
+    // FeatureData[ segmentId ][ feature ]
+    pub fn feature_data(&self, segment_id: usize, feature: usize) -> i16 {
+        self.feature_data[segment_id][feature]
+    }
+
+    pub fn seg_id_pre_skip(&self) -> u8 {
+        self.seg_id_pre_skip
+    }
+
+    pub fn last_active_seg_id(&self) -> u8 {
+        self.last_active_seg_id
+    }
+}
+
+impl Delta_Q_Params {
+    pub fn delta_q_present(&self) -> u8 {
+        self.delta_q_present
+    }
+
+    pub fn delta_q_res(&self) -> u8 {
+        self.delta_q_res
+    }
+}
+
+impl Delta_Lf_Params {
+    pub fn delta_lf_present(&self) -> u8 {
+        self.delta_lf_present
+    }
+
+    pub fn delta_lf_res(&self) -> u8 {
+        self.delta_lf_res
+    }
+
+    pub fn delta_lf_multi(&self) -> u8 {
+        self.delta_lf_multi
+    }
+}
+
+impl Frame_Size {
+    pub fn frame_width(&self) -> u32 {
+        self.frame_width
+    }
+
+    pub fn frame_height(&self) -> u32 {
+        self.frame_height
+    }
+
+    pub fn upscaled_width(&self) -> u32 {
+        self.upscaled_width
+    }
+
+    pub fn render_width(&self) -> u32 {
+        self.render_width
+    }
+
+    pub fn render_height(&self) -> u32 {
+        self.render_height
+    }
+
+    pub fn use_superres(&self) -> u8 {
+        self.use_superres
+    }
+
+    pub fn superres_denom(&self) -> u32 {
+        self.superres_denom
+    }
+
+    pub fn mi_cols(&self) -> u32 {
+        self.mi_cols
+    }
+
+    pub fn mi_rows(&self) -> u32 {
+        self.mi_rows
+    }
+}
+
+impl Loop_Filter_Params {
+    pub fn loop_filter_level(&self) -> &[u8; 4] {
+        &self.loop_filter_level
+    }
+
+    pub fn loop_filter_sharpness(&self) -> u8 {
+        self.loop_filter_sharpness
+    }
+
+    pub fn loop_filter_delta_enabled(&self) -> u8 {
+        self.loop_filter_delta_enabled
+    }
+
+    pub fn loop_filter_delta_update(&self) -> u8 {
+        self.loop_filter_delta_update
+    }
+
+    // Indexed by INTRA_FRAME .. ALTREF_FRAME
+    pub fn loop_filter_ref_deltas(&self) -> &[i8; TOTAL_REFS_PER_FRAME] {
+        &self.loop_filter_ref_deltas
+    }
+
+    pub fn loop_filter_mode_deltas(&self) -> &[i8; 2] {
+        &self.loop_filter_mode_deltas
+    }
+}
+
+impl Cdef_Params {
+    pub fn cdef_damping(&self) -> u8 {
+        self.cdef_damping
+    }
+
+    pub fn cdef_bits(&self) -> u8 {
+        self.cdef_bits
+    }
+
+    pub fn cdef_y_pri_strength(&self) -> &[u8] {
+        &self.cdef_y_pri_strength
+    }
+
+    pub fn cdef_y_sec_strength(&self) -> &[u8] {
+        &self.cdef_y_sec_strength
+    }
+
+    pub fn cdef_uv_pri_strength(&self) -> &[u8] {
+        &self.cdef_uv_pri_strength
+    }
+
+    pub fn cdef_uv_sec_strength(&self) -> &[u8] {
+        &self.cdef_uv_sec_strength
+    }
+}
+
+impl Lr_Params {
+    pub fn frame_restoration_type(&self) -> &[FRAME_RESTORATION_TYPE; 3] {
+        &self.frame_restoration_type
+    }
+
+    pub fn uses_lr(&self) -> u8 {
+        self.uses_lr
+    }
+
+    pub fn uses_chroma_lr(&self) -> u8 {
+        self.uses_chroma_lr
+    }
+
+    pub fn lr_unit_shift(&self) -> u8 {
+        self.lr_unit_shift
+    }
+
+    pub fn lr_uv_shift(&self) -> u8 {
+        self.lr_uv_shift
+    }
+
+    pub fn loop_restoration_size(&self) -> &[u32; 3] {
+        &self.loop_restoration_size
+    }
+}
+
+impl Global_Motion_Params {
+    // GmType[ ref ]
+    pub fn gm_type(&self) -> &[GM_TYPE; TOTAL_REFS_PER_FRAME] {
+        &self.gm_type
+    }
+
+    // gm_params[ ref ][ i ]
+    pub fn gm_params(&self) -> &[[i32; 6]; TOTAL_REFS_PER_FRAME] {
+        &self.gm_params
+    }
+
+    // PrevGmParams, from load_previous( ) or setup_past_independence( )
+    pub fn prev_gm_params(&self) -> &[[i32; 6]; TOTAL_REFS_PER_FRAME] {
+        &self.prev_gm_params
+    }
+}
+
+impl Film_Grain_Params {
+    pub fn apply_grain(&self) -> u8 {
+        self.apply_grain
+    }
+
+    pub fn grain_seed(&self) -> u16 {
+        self.grain_seed
+    }
+
+    pub fn update_grain(&self) -> u8 {
+        self.update_grain
+    }
+
+    pub fn film_grain_params_ref_idx(&self) -> u8 {
+        self.film_grain_params_ref_idx
+    }
+
+    pub fn num_y_points(&self) -> u8 {
+        self.num_y_points
+    }
+
+    pub fn point_y_value(&self) -> &[u8] {
+        &self.point_y_value
+    }
+
+    pub fn point_y_scaling(&self) -> &[u8] {
+        &self.point_y_scaling
+    }
+
+    pub fn chroma_scaling_from_luma(&self) -> u8 {
+        self.chroma_scaling_from_luma
+    }
+
+    pub fn num_cb_points(&self) -> u8 {
+        self.num_cb_points
+    }
+
+    pub fn point_cb_value(&self) -> &[u8] {
+        &self.point_cb_value
+    }
+
+    pub fn point_cb_scaling(&self) -> &[u8] {
+        &self.point_cb_scaling
+    }
+
+    pub fn num_cr_points(&self) -> u8 {
+        self.num_cr_points
+    }
+
+    pub fn point_cr_value(&self) -> &[u8] {
+        &self.point_cr_value
+    }
+
+    pub fn point_cr_scaling(&self) -> &[u8] {
+        &self.point_cr_scaling
+    }
+
+    pub fn grain_scaling_minus_8(&self) -> u8 {
+        self.grain_scaling_minus_8
+    }
+
+    pub fn ar_coeff_lag(&self) -> u8 {
+        self.ar_coeff_lag
+    }
+
+    pub fn ar_coeffs_y_plus_128(&self) -> &[u8] {
+        &self.ar_coeffs_y_plus_128
+    }
+
+    pub fn ar_coeffs_cb_plus_128(&self) -> &[u8] {
+        &self.ar_coeffs_cb_plus_128
+    }
+
+    pub fn ar_coeffs_cr_plus_128(&self) -> &[u8] {
+        &self.ar_coeffs_cr_plus_128
+    }
+
+    pub fn ar_coeff_shift_minus_6(&self) -> u8 {
+        self.ar_coeff_shift_minus_6
+    }
+
+    pub fn grain_scale_shift(&self) -> u8 {
+        self.grain_scale_shift
+    }
+
+    pub fn cb_mult(&self) -> u8 {
+        self.cb_mult
+    }
+
+    pub fn cb_luma_mult(&self) -> u8 {
+        self.cb_luma_mult
+    }
+
+    pub fn cb_offset(&self) -> u16 {
+        self.cb_offset
+    }
+
+    pub fn cr_mult(&self) -> u8 {
+        self.cr_mult
+    }
+
+    pub fn cr_luma_mult(&self) -> u8 {
+        self.cr_luma_mult
+    }
+
+    pub fn cr_offset(&self) -> u16 {
+        self.cr_offset
+    }
+
+    pub fn overlap_flag(&self) -> u8 {
+        self.overlap_flag
+    }
+
+    pub fn clip_to_restricted_range(&self) -> u8 {
+        self.clip_to_restricted_range
+    }
+}
+
+impl Ref_Frame {
+    pub fn ref_valid(&self) -> u8 {
+        self.ref_valid
+    }
+
+    pub fn ref_frame_id(&self) -> u32 {
+        self.ref_frame_id
+    }
+
+    pub fn ref_frame_type(&self) -> FRAME_TYPE {
+        self.ref_frame_type
+    }
+
+    pub fn ref_order_hint(&self) -> u8 {
+        self.ref_order_hint
+    }
+
+    pub fn ref_frame_size(&self) -> &Frame_Size {
+        &self.ref_frame_size
+    }
+
+    pub fn saved_order_hints(&self) -> &[u8; TOTAL_REFS_PER_FRAME] {
+        &self.saved_order_hints
+    }
+
+    pub fn saved_segmentation_params(&self) -> &Segmentation_Params {
+        &self.saved_segmentation_params
+    }
+
+    pub fn saved_loop_filter_ref_deltas(&self) -> &[i8; TOTAL_REFS_PER_FRAME] {
+        &self.saved_loop_filter_ref_deltas
+    }
+
+    pub fn saved_loop_filter_mode_deltas(&self) -> &[i8; 2] {
+        &self.saved_loop_filter_mode_deltas
+    }
+
+    pub fn saved_gm_params(&self) -> &[[i32; 6]; TOTAL_REFS_PER_FRAME] {
+        &self.saved_gm_params
+    }
+
+    pub fn saved_film_grain_params(&self) -> &Film_Grain_Params {
+        &self.saved_film_grain_params
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{consts::FRAME_TYPE, obu::OBU_Sequence_Header, Error};
+
+use super::{Film_Grain_Params, FrameHeader, RefFrameStore};
+
+impl Film_Grain_Params {
+
+    // 5.9.30 Film grain params syntax
+    pub(crate) fn from_reader<R: bitstream_io::BitRead + ?Sized>(
+        r: &mut R,
+        sequence_header: &OBU_Sequence_Header,
+        header: &FrameHeader,
+        ref_frames: &RefFrameStore,
+    ) -> Result<Self, Error> {
+        let mut params = Film_Grain_Params::default();
+        if sequence_header.film_grain_params_present() == 0 || (header.show_frame == 0 && header.showable_frame == 0) {
+            return Ok(params);
+        }
+
+        params.apply_grain = r.read::<1, u8>()?;
+        if params.apply_grain == 0 {
+            return Ok(params);
+        }
+
+        params.grain_seed = r.read::<16, u16>()?;
+        params.update_grain = if header.frame_type == FRAME_TYPE::INTER_FRAME {
+            r.read::<1, u8>()?
+        } else {
+            1u8
+        };
+
+        if params.update_grain == 0 {
+            params.film_grain_params_ref_idx = r.read::<3, u8>()?;
+            if !header.ref_frame_idx.contains(&params.film_grain_params_ref_idx) {
+                return Err(Error::ConformanceViolation {
+                    requirement: "film_grain_params_ref_idx is one of ref_frame_idx",
+                });
+            }
+
+            // load_grain_params( film_grain_params_ref_idx ), grain_seed, update_grain and the index stay as coded
+            let saved = &ref_frames.ref_frame(params.film_grain_params_ref_idx as usize).saved_film_grain_params;
+            return Ok(Film_Grain_Params {
+                grain_seed: params.grain_seed,
+                update_grain: params.update_grain,
+                film_grain_params_ref_idx: params.film_grain_params_ref_idx,
+                ..saved.clone()
+            });
+        }
+
+        params.num_y_points = r.read::<4, u8>()?;
+        if params.num_y_points > 14 {
+            return Err(Error::ConformanceViolation {
+                requirement: "num_y_points is at most 14",
+            });
+        }
+        (params.point_y_value, params.point_y_scaling) = read_scaling_points(r, params.num_y_points, "point_y_value is increasing")?;
+
+        let color_config = sequence_header.color_config();
+        params.chroma_scaling_from_luma = if color_config.mono_chrome() != 0 {
+            0u8
+        } else {
+            r.read::<1, u8>()?
+        };
+
+        if color_config.mono_chrome() == 0
+            && params.chroma_scaling_from_luma == 0
+            && !(color_config.subsampling_x() == 1 && color_config.subsampling_y() == 1 && params.num_y_points == 0)
+        {
+            params.num_cb_points = r.read::<4, u8>()?;
+            (params.point_cb_value, params.point_cb_scaling) = read_scaling_points(r, params.num_cb_points, "point_cb_value is increasing")?;
+            params.num_cr_points = r.read::<4, u8>()?;
+            (params.point_cr_value, params.point_cr_scaling) = read_scaling_points(r, params.num_cr_points, "point_cr_value is increasing")?;
+            if params.num_cb_points > 10 || params.num_cr_points > 10 {
+                return Err(Error::ConformanceViolation {
+                    requirement: "num_cb_points and num_cr_points are at most 10",
+                });
+            }
+        }
+
+        params.grain_scaling_minus_8 = r.read::<2, u8>()?;
+        params.ar_coeff_lag = r.read::<2, u8>()?;
+        let num_pos_luma = 2 * params.ar_coeff_lag as usize * (params.ar_coeff_lag as usize + 1);
+        let num_pos_chroma = if params.num_y_points != 0 {
+            params.ar_coeffs_y_plus_128 = read_bytes(r, num_pos_luma)?;
+            num_pos_luma + 1
+        } else {
+            num_pos_luma
+        };
+        if params.chroma_scaling_from_luma != 0 || params.num_cb_points != 0 {
+            params.ar_coeffs_cb_plus_128 = read_bytes(r, num_pos_chroma)?;
+        }
+        if params.chroma_scaling_from_luma != 0 || params.num_cr_points != 0 {
+            params.ar_coeffs_cr_plus_128 = read_bytes(r, num_pos_chroma)?;
+        }
+        params.ar_coeff_shift_minus_6 = r.read::<2, u8>()?;
+        params.grain_scale_shift = r.read::<2, u8>()?;
+        if params.num_cb_points != 0 {
+            params.cb_mult = r.read::<8, u8>()?;
+            params.cb_luma_mult = r.read::<8, u8>()?;
+            params.cb_offset = r.read::<9, u16>()?;
+        }
+        if params.num_cr_points != 0 {
+            params.cr_mult = r.read::<8, u8>()?;
+            params.cr_luma_mult = r.read::<8, u8>()?;
+            params.cr_offset = r.read::<9, u16>()?;
+        }
+        params.overlap_flag = r.read::<1, u8>()?;
+        params.clip_to_restricted_range = r.read::<1, u8>()?;
+        Ok(params)
+    }
+}
+
+// point_*_value and point_*_scaling pairs, the values have to increase
+fn read_scaling_points<R: bitstream_io::BitRead + ?Sized>(r: &mut R, num_points: u8, requirement: &'static str) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    let mut values = Vec::with_capacity(num_points as usize);
+    let mut scalings = Vec::with_capacity(num_points as usize);
+    for _ in 0..num_points {
+        let value = r.read::<8, u8>()?;
+        if values.last().is_some_and(|last| value <= *last) {
+            return Err(Error::ConformanceViolation { requirement });
+        }
+        values.push(value);
+        scalings.push(r.read::<8, u8>()?);
+    }
+    Ok((values, scalings))
+}
+
+fn read_bytes<R: bitstream_io::BitRead + ?Sized>(r: &mut R, n: usize) -> Result<Vec<u8>, Error> {
+    (0..n).map(|_| Ok(r.read::<8, u8>()?)).collect()
+}
@@ -0,0 +1,84 @@
+use bitstream_io::FromBitStream;
+
+use crate::{
+    consts::{
+        ALTREF_FRAME, GM_ABS_ALPHA_BITS, GM_ABS_TRANS_BITS, GM_ABS_TRANS_ONLY_BITS, GM_ALPHA_PREC_BITS, GM_TRANS_ONLY_PREC_BITS, GM_TRANS_PREC_BITS, GM_TYPE,
+        LAST_FRAME, TOTAL_REFS_PER_FRAME, WARPEDMODEL_PREC_BITS,
+    },
+    generics::decode_signed_subexp_with_ref,
+    Error,
+};
+
+use super::{Global_Motion_Params, Ref_Frame};
+
+// gm_params of the IDENTITY model, also PrevGmParams after setup_past_independence( )
+pub(super) const DEFAULT_GM_PARAMS: [[i32; 6]; TOTAL_REFS_PER_FRAME] =
+    [[0, 0, 1 << WARPEDMODEL_PREC_BITS, 0, 0, 1 << WARPEDMODEL_PREC_BITS]; TOTAL_REFS_PER_FRAME];
+
+impl Global_Motion_Params {
+
+    // 5.9.24 Global motion params syntax, previous is the reference frame of load_previous( ) when primary_ref_frame is set
+    pub(crate) fn from_reader<R: bitstream_io::BitRead + ?Sized>(
+        r: &mut R,
+        frame_is_intra: bool,
+        allow_high_precision_mv: u8,
+        previous: Option<&Ref_Frame>,
+    ) -> Result<Self, Error> {
+        let mut params = Global_Motion_Params {
+            gm_type: [GM_TYPE::IDENTITY; TOTAL_REFS_PER_FRAME],
+            gm_params: DEFAULT_GM_PARAMS,
+            prev_gm_params: previous.map_or(DEFAULT_GM_PARAMS, |previous| previous.saved_gm_params),
+        };
+        if frame_is_intra {
+            return Ok(params);
+        }
+
+        for reference in LAST_FRAME..=ALTREF_FRAME {
+            let gm_type = GM_TYPE::from_reader(r)?;
+            params.gm_type[reference] = gm_type;
+            if gm_type >= GM_TYPE::ROTZOOM {
+                params.read_global_param(r, gm_type, reference, 2, allow_high_precision_mv)?;
+                params.read_global_param(r, gm_type, reference, 3, allow_high_precision_mv)?;
+                if gm_type == GM_TYPE::AFFINE {
+                    params.read_global_param(r, gm_type, reference, 4, allow_high_precision_mv)?;
+                    params.read_global_param(r, gm_type, reference, 5, allow_high_precision_mv)?;
+                } else {
+                    params.gm_params[reference][4] = -params.gm_params[reference][3];
+                    params.gm_params[reference][5] = params.gm_params[reference][2];
+                }
+            }
+            if gm_type >= GM_TYPE::TRANSLATION {
+                params.read_global_param(r, gm_type, reference, 0, allow_high_precision_mv)?;
+                params.read_global_param(r, gm_type, reference, 1, allow_high_precision_mv)?;
+            }
+        }
+        Ok(params)
+    }
+
+    // 5.9.25 Global param syntax
+    fn read_global_param<R: bitstream_io::BitRead + ?Sized>(
+        &mut self,
+        r: &mut R,
+        gm_type: GM_TYPE,
+        reference: usize,
+        idx: usize,
+        allow_high_precision_mv: u8,
+    ) -> Result<(), Error> {
+        let (abs_bits, prec_bits) = match gm_type {
+            _ if idx >= 2 => (GM_ABS_ALPHA_BITS, GM_ALPHA_PREC_BITS),
+            GM_TYPE::TRANSLATION => {
+                let low_precision = (allow_high_precision_mv == 0) as u32;
+                (GM_ABS_TRANS_ONLY_BITS - low_precision, GM_TRANS_ONLY_PREC_BITS - low_precision)
+            }
+            _ => (GM_ABS_TRANS_BITS, GM_TRANS_PREC_BITS),
+        };
+        let prec_diff = WARPEDMODEL_PREC_BITS - prec_bits;
+        let round = if idx % 3 == 2 { 1i32 << WARPEDMODEL_PREC_BITS } else { 0 };
+        let sub = if idx % 3 == 2 { 1i32 << prec_bits } else { 0 };
+        let mx = 1i32 << abs_bits;
+        let reference_value = (self.prev_gm_params[reference][idx] >> prec_diff) - sub;
+        let value = decode_signed_subexp_with_ref(r, -mx, mx + 1, reference_value)?;
+        self.gm_params[reference][idx] = (value << prec_diff) + round;
+        Ok(())
+    }
+}
@@ -0,0 +1,436 @@
+use bitstream_io::FromBitStream;
+
+use crate::{
+    consts::{self, FRAME_TYPE, INTERPOLATION_FILTER, LAST_FRAME, NUM_REF_FRAMES, PRIMARY_REF_NONE, REFS_PER_FRAME, TX_MODE},
+    obu::{OBU_Extension_Header, OBU_Sequence_Header},
+    Error,
+};
+
+use super::{Cdef_Params, Delta_Lf_Params, Delta_Q_Params, Film_Grain_Params, FrameHeader, Frame_Size, Global_Motion_Params, Loop_Filter_Params, Lr_Params, Quantization_Params, RefFrameStore, Segmentation_Params, Tile_Info};
+
+impl FrameHeader {
+
+    // 5.9.2 Uncompressed header syntax
+    pub fn uncompressed_header<R: bitstream_io::BitRead + ?Sized>(
+        r: &mut R,
+        sequence_header: &OBU_Sequence_Header,
+        extension_header: Option<&OBU_Extension_Header>,
+        ref_frames: &mut RefFrameStore,
+    ) -> Result<Self, Error> {
+        let mut header = FrameHeader::default();
+
+        let id_len: u32 = match (sequence_header.additional_frame_id_length_minus_1(), sequence_header.delta_frame_id_length_minus_2()) {
+            (Some(additional), Some(delta)) => additional as u32 + delta as u32 + 3,
+            _ => 0,
+        };
+        let all_frames: u8 = 0xff; // (1 << NUM_REF_FRAMES) - 1
+
+        let decoder_model_info_present_flag = sequence_header.decoder_model_info().is_some();
+        let equal_picture_interval = sequence_header.timing_info().is_some_and(|t| t.equal_picture_interval() == 1);
+
+        if sequence_header.reduced_still_picture_header() != 0 {
+            header.frame_type = FRAME_TYPE::KEY_FRAME;
+            header.show_frame = 1;
+        } else {
+            header.show_existing_frame = r.read::<1, u8>()?;
+            if header.show_existing_frame == 1 {
+                header.frame_to_show_map_idx = r.read::<3, u8>()?;
+                if decoder_model_info_present_flag && !equal_picture_interval {
+                    header.frame_presentation_time = Some(Self::temporal_point_info(r, sequence_header)?);
+                }
+                if id_len != 0 {
+                    header.display_frame_id = Some(r.read_var(id_len)?);
+                }
+
+                let ref_frame = ref_frames.ref_frame(header.frame_to_show_map_idx as usize);
+                if ref_frame.ref_valid == 0 {
+                    return Err(Error::ConformanceViolation {
+                        requirement: "show_existing_frame references a valid reference frame",
+                    });
+                }
+                header.frame_type = ref_frame.ref_frame_type;
+                header.frame_size = ref_frame.ref_frame_size.clone();
+                header.order_hint = ref_frame.ref_order_hint;
+                header.current_frame_id = ref_frame.ref_frame_id;
+                header.show_frame = 1;
+                header.refresh_frame_flags = if header.frame_type == FRAME_TYPE::KEY_FRAME {
+                    all_frames
+                } else {
+                    0u8
+                };
+                if sequence_header.film_grain_params_present() != 0 {
+                    // load_grain_params( frame_to_show_map_idx )
+                    header.film_grain_params = ref_frame.saved_film_grain_params.clone();
+                }
+                return Ok(header);
+            }
+
+            header.frame_type = FRAME_TYPE::from_reader(r)?;
+            header.show_frame = r.read::<1, u8>()?;
+            if header.show_frame == 1 && decoder_model_info_present_flag && !equal_picture_interval {
+                header.frame_presentation_time = Some(Self::temporal_point_info(r, sequence_header)?);
+            }
+            header.showable_frame = if header.show_frame == 1 {
+                (header.frame_type != FRAME_TYPE::KEY_FRAME) as u8
+            } else {
+                r.read::<1, u8>()?
+            };
+            header.error_resilient_mode = if header.frame_type == FRAME_TYPE::SWITCH_FRAME
+                || (header.frame_type == FRAME_TYPE::KEY_FRAME && header.show_frame == 1)
+            {
+                1u8
+            } else {
+                r.read::<1, u8>()?
+            };
+        }
+
+        if header.frame_type == FRAME_TYPE::KEY_FRAME && header.show_frame == 1 {
+            ref_frames.reset();
+        }
+
+        header.disable_cdf_update = r.read::<1, u8>()?;
+        header.allow_screen_content_tools = if sequence_header.seq_force_screen_content_tools() == consts::SELECT_SCREEN_CONTENT_TOOLS {
+            r.read::<1, u8>()?
+        } else {
+            sequence_header.seq_force_screen_content_tools()
+        };
+        header.force_integer_mv = if header.allow_screen_content_tools != 0 {
+            if sequence_header.seq_force_integer_mv() == consts::SELECT_INTEGER_MV {
+                r.read::<1, u8>()?
+            } else {
+                sequence_header.seq_force_integer_mv()
+            }
+        } else {
+            0u8
+        };
+        if header.frame_is_intra() {
+            header.force_integer_mv = 1;
+        }
+
+        if id_len != 0 {
+            header.current_frame_id = r.read_var(id_len)?;
+            let diff_len = sequence_header.delta_frame_id_length_minus_2().unwrap_or(0) as u32 + 2;
+            ref_frames.mark_ref_frames(header.current_frame_id, id_len, diff_len);
+        }
+
+        header.frame_size_override_flag = if header.frame_type == FRAME_TYPE::SWITCH_FRAME {
+            1u8
+        } else if sequence_header.reduced_still_picture_header() != 0 {
+            0u8
+        } else {
+            r.read::<1, u8>()?
+        };
+
+        header.order_hint = r.read_var(sequence_header.order_hint_bits() as u32)?;
+        header.primary_ref_frame = if header.frame_is_intra() || header.error_resilient_mode != 0 {
+            PRIMARY_REF_NONE
+        } else {
+            r.read::<3, u8>()?
+        };
+
+        if let Some(decoder_model_info) = sequence_header.decoder_model_info() {
+            // buffer_removal_time_present_flag
+            if r.read::<1, u8>()? == 1 {
+                let n = decoder_model_info.buffer_removal_delay_length_minus_1() as u32 + 1;
+                let (temporal_id, spatial_id) = extension_header.map_or((0u8, 0u8), |e| (e.temporal_id(), e.spatial_id()));
+                for (op_num, op_pt_idc) in sequence_header.operating_point_idc().iter().enumerate() {
+                    let mut buffer_removal_time = None;
+                    if sequence_header.decoder_model_present_for_this_op()[op_num] != 0 {
+                        let in_temporal_layer = (op_pt_idc >> temporal_id) & 1;
+                        let in_spatial_layer = (op_pt_idc >> (spatial_id + 8)) & 1;
+                        if *op_pt_idc == 0 || (in_temporal_layer != 0 && in_spatial_layer != 0) {
+                            buffer_removal_time = Some(r.read_var(n)?);
+                        }
+                    }
+                    header.buffer_removal_time.push(buffer_removal_time);
+                }
+            }
+        }
+
+        header.refresh_frame_flags = if header.frame_type == FRAME_TYPE::SWITCH_FRAME
+            || (header.frame_type == FRAME_TYPE::KEY_FRAME && header.show_frame == 1)
+        {
+            all_frames
+        } else {
+            r.read::<8, u8>()?
+        };
+
+        if (!header.frame_is_intra() || header.refresh_frame_flags != all_frames)
+            && header.error_resilient_mode != 0
+            && sequence_header.enable_order_hint() != 0
+        {
+            let mut ref_order_hint = Vec::with_capacity(NUM_REF_FRAMES);
+            for i in 0..NUM_REF_FRAMES {
+                let hint: u8 = r.read_var(sequence_header.order_hint_bits() as u32)?;
+                ref_frames.check_ref_order_hint(i, hint);
+                ref_order_hint.push(hint);
+            }
+            header.ref_order_hint = Some(ref_order_hint);
+        }
+
+        if header.frame_is_intra() {
+            header.frame_size = Frame_Size::from_reader(r, sequence_header, header.frame_size_override_flag)?;
+            header.frame_size.render_size(r)?;
+            if header.allow_screen_content_tools != 0 && header.frame_size.upscaled_width == header.frame_size.frame_width {
+                header.allow_intrabc = r.read::<1, u8>()?;
+            }
+        } else {
+            if sequence_header.enable_order_hint() != 0 {
+                header.frame_refs_short_signaling = r.read::<1, u8>()?;
+                if header.frame_refs_short_signaling != 0 {
+                    header.last_frame_idx = r.read::<3, u8>()?;
+                    header.gold_frame_idx = r.read::<3, u8>()?;
+                    header.ref_frame_idx = ref_frames.set_frame_refs(sequence_header, header.order_hint, header.last_frame_idx, header.gold_frame_idx)?;
+                }
+            }
+
+            let mut delta_frame_id_minus_1 = Vec::new();
+            for i in 0..REFS_PER_FRAME {
+                if header.frame_refs_short_signaling == 0 {
+                    header.ref_frame_idx[i] = r.read::<3, u8>()?;
+                }
+                if ref_frames.ref_frame(header.ref_frame_idx[i] as usize).ref_valid == 0 {
+                    return Err(Error::ConformanceViolation {
+                        requirement: "ref_frame_idx references a valid reference frame",
+                    });
+                }
+                if let Some(delta_frame_id_length_minus_2) = sequence_header.delta_frame_id_length_minus_2() {
+                    delta_frame_id_minus_1.push(r.read_var(delta_frame_id_length_minus_2 as u32 + 2)?);
+                }
+            }
+            if id_len != 0 {
+                header.delta_frame_id_minus_1 = Some(delta_frame_id_minus_1);
+            }
+
+            header.frame_size = if header.frame_size_override_flag != 0 && header.error_resilient_mode == 0 {
+                Frame_Size::frame_size_with_refs(r, sequence_header, &header.ref_frame_idx, ref_frames)?
+            } else {
+                let mut frame_size = Frame_Size::from_reader(r, sequence_header, header.frame_size_override_flag)?;
+                frame_size.render_size(r)?;
+                frame_size
+            };
+
+            header.allow_high_precision_mv = if header.force_integer_mv != 0 {
+                0u8
+            } else {
+                r.read::<1, u8>()?
+            };
+            header.interpolation_filter = INTERPOLATION_FILTER::from_reader(r)?;
+            header.is_motion_mode_switchable = r.read::<1, u8>()?;
+            header.use_ref_frame_mvs = if header.error_resilient_mode != 0 || sequence_header.enable_ref_frame_mvs() == 0 {
+                0u8
+            } else {
+                r.read::<1, u8>()?
+            };
+
+            for i in 0..REFS_PER_FRAME {
+                let hint = ref_frames.ref_frame(header.ref_frame_idx[i] as usize).ref_order_hint;
+                header.order_hints[LAST_FRAME + i] = hint;
+                header.ref_frame_sign_bias[LAST_FRAME + i] = (sequence_header.get_relative_dist(hint, header.order_hint) > 0) as u8;
+            }
+        }
+
+        header.disable_frame_end_update_cdf = if sequence_header.reduced_still_picture_header() != 0 || header.disable_cdf_update != 0 {
+            1u8
+        } else {
+            r.read::<1, u8>()?
+        };
+
+        header.tile_info = Tile_Info::from_reader(r, &header.frame_size, sequence_header.use_128x128_superblock())?;
+        header.quantization_params = Quantization_Params::from_reader(r, sequence_header.color_config())?;
+
+        // load_previous( ) takes the saved values of ref_frame_idx[ primary_ref_frame ]
+        let previous = (header.primary_ref_frame != PRIMARY_REF_NONE)
+            .then(|| ref_frames.ref_frame(header.ref_frame_idx[header.primary_ref_frame as usize] as usize));
+        header.segmentation_params = Segmentation_Params::from_reader(
+            r,
+            header.primary_ref_frame,
+            previous.map(|ref_frame| &ref_frame.saved_segmentation_params),
+        )?;
+        header.delta_q_params = Delta_Q_Params::from_reader(r, header.quantization_params.base_q_idx())?;
+        header.delta_lf_params = Delta_Lf_Params::from_reader(r, header.delta_q_params.delta_q_present(), header.allow_intrabc)?;
+
+        let num_planes = sequence_header.color_config().num_planes();
+        let coded_lossless = header.coded_lossless();
+        let allow_intrabc = header.allow_intrabc != 0;
+        header.loop_filter_params = Loop_Filter_Params::from_reader(r, num_planes, coded_lossless || allow_intrabc, previous)?;
+        header.cdef_params = Cdef_Params::from_reader(r, num_planes, coded_lossless || allow_intrabc || sequence_header.enable_cdef() == 0)?;
+        header.lr_params = Lr_Params::from_reader(r, sequence_header, header.all_lossless() || allow_intrabc || sequence_header.enable_restoration() == 0)?;
+
+        // 5.9.21 TX mode syntax
+        header.tx_mode = if coded_lossless {
+            TX_MODE::ONLY_4X4
+        } else if r.read::<1, u8>()? == 1 {
+            TX_MODE::TX_MODE_SELECT
+        } else {
+            TX_MODE::TX_MODE_LARGEST
+        };
+
+        // 5.9.23 Frame reference mode syntax
+        header.reference_select = if header.frame_is_intra() { 0u8 } else { r.read::<1, u8>()? };
+
+        header.skip_mode_present = if header.skip_mode_allowed(sequence_header) {
+            r.read::<1, u8>()?
+        } else {
+            0u8
+        };
+
+        header.allow_warped_motion = if header.frame_is_intra() || header.error_resilient_mode != 0 || sequence_header.enable_warped_motion() == 0 {
+            0u8
+        } else {
+            r.read::<1, u8>()?
+        };
+        header.reduced_tx_set = r.read::<1, u8>()?;
+        header.global_motion_params = Global_Motion_Params::from_reader(r, header.frame_is_intra(), header.allow_high_precision_mv, previous)?;
+        header.film_grain_params = Film_Grain_Params::from_reader(r, sequence_header, &header, ref_frames)?;
+
+        Ok(header)
+    }
+
+    // 5.9.22 Skip mode params syntax, sets SkipModeFrame and returns skipModeAllowed
+    fn skip_mode_allowed(&mut self, sequence_header: &OBU_Sequence_Header) -> bool {
+        if self.frame_is_intra() || self.reference_select == 0 || sequence_header.enable_order_hint() == 0 {
+            return false;
+        }
+
+        let mut forward: Option<(usize, u8)> = None;
+        let mut backward: Option<(usize, u8)> = None;
+        for i in 0..REFS_PER_FRAME {
+            let ref_hint = self.order_hints[LAST_FRAME + i];
+            let dist = sequence_header.get_relative_dist(ref_hint, self.order_hint);
+            if dist < 0 {
+                if forward.is_none_or(|(_, forward_hint)| sequence_header.get_relative_dist(ref_hint, forward_hint) > 0) {
+                    forward = Some((i, ref_hint));
+                }
+            } else if dist > 0 && backward.is_none_or(|(_, backward_hint)| sequence_header.get_relative_dist(ref_hint, backward_hint) < 0) {
+                backward = Some((i, ref_hint));
+            }
+        }
+
+        let Some((forward_idx, forward_hint)) = forward else {
+            return false;
+        };
+        let other_idx = match backward {
+            Some((backward_idx, _)) => backward_idx,
+            None => {
+                let mut second_forward: Option<(usize, u8)> = None;
+                for i in 0..REFS_PER_FRAME {
+                    let ref_hint = self.order_hints[LAST_FRAME + i];
+                    if sequence_header.get_relative_dist(ref_hint, forward_hint) < 0
+                        && second_forward.is_none_or(|(_, second_hint)| sequence_header.get_relative_dist(ref_hint, second_hint) > 0)
+                    {
+                        second_forward = Some((i, ref_hint));
+                    }
+                }
+                match second_forward {
+                    Some((second_forward_idx, _)) => second_forward_idx,
+                    None => return false,
+                }
+            }
+        };
+        self.skip_mode_frame = [LAST_FRAME + forward_idx.min(other_idx), LAST_FRAME + forward_idx.max(other_idx)];
+        true
+    }
+
+    // 5.9.31 Temporal point info syntax
+    fn temporal_point_info<R: bitstream_io::BitRead + ?Sized>(r: &mut R, sequence_header: &OBU_Sequence_Header) -> Result<u32, Error> {
+        let decoder_model_info = sequence_header
+            .decoder_model_info()
+            .ok_or(Error::ConformanceViolation {
+                requirement: "temporal_point_info requires decoder_model_info",
+            })?;
+        Ok(r.read_var(decoder_model_info.frame_presentation_delay_length_minus_1() as u32 + 1)?)
+    }
+
+    // FrameIsIntra
+    pub fn frame_is_intra(&self) -> bool {
+        self.frame_type == FRAME_TYPE::INTRA_ONLY_FRAME || self.frame_type == FRAME_TYPE::KEY_FRAME
+    }
+}
+
+impl Frame_Size {
+
+    // 5.9.5 Frame size syntax
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R, sequence_header: &OBU_Sequence_Header, frame_size_override_flag: u8) -> Result<Self, Error> {
+        let mut frame_size = Frame_Size::default();
+        if frame_size_override_flag != 0 {
+            let frame_width_minus_1: u32 = r.read_var(sequence_header.frame_width_bits_minus_1() as u32 + 1)?;
+            let frame_height_minus_1: u32 = r.read_var(sequence_header.frame_height_bits_minus_1() as u32 + 1)?;
+            frame_size.frame_width = frame_width_minus_1 + 1;
+            frame_size.frame_height = frame_height_minus_1 + 1;
+        } else {
+            frame_size.frame_width = sequence_header.max_frame_width();
+            frame_size.frame_height = sequence_header.max_frame_height();
+        }
+        frame_size.superres_params(r, sequence_header)?;
+        frame_size.compute_image_size();
+        Ok(frame_size)
+    }
+
+    // 5.9.6 Render size syntax
+    fn render_size<R: bitstream_io::BitRead + ?Sized>(&mut self, r: &mut R) -> Result<(), Error> {
+        // render_and_frame_size_different
+        if r.read::<1, u8>()? == 1 {
+            self.render_width = r.read::<16, u32>()? + 1;
+            self.render_height = r.read::<16, u32>()? + 1;
+        } else {
+            self.render_width = self.upscaled_width;
+            self.render_height = self.frame_height;
+        }
+        Ok(())
+    }
+
+    // 5.9.7 Frame size with refs syntax
+    fn frame_size_with_refs<R: bitstream_io::BitRead + ?Sized>(
+        r: &mut R,
+        sequence_header: &OBU_Sequence_Header,
+        ref_frame_idx: &[u8; REFS_PER_FRAME],
+        ref_frames: &RefFrameStore,
+    ) -> Result<Self, Error> {
+        for idx in ref_frame_idx {
+            // found_ref
+            if r.read::<1, u8>()? == 1 {
+                let ref_frame_size = &ref_frames.ref_frame(*idx as usize).ref_frame_size;
+                let mut frame_size = Frame_Size {
+                    frame_width: ref_frame_size.upscaled_width,
+                    frame_height: ref_frame_size.frame_height,
+                    upscaled_width: ref_frame_size.upscaled_width,
+                    render_width: ref_frame_size.render_width,
+                    render_height: ref_frame_size.render_height,
+                    ..Default::default()
+                };
+                frame_size.superres_params(r, sequence_header)?;
+                frame_size.compute_image_size();
+                return Ok(frame_size);
+            }
+        }
+
+        let mut frame_size = Frame_Size::from_reader(r, sequence_header, 1u8)?;
+        frame_size.render_size(r)?;
+        Ok(frame_size)
+    }
+
+    // 5.9.8 Superres params syntax
+    fn superres_params<R: bitstream_io::BitRead + ?Sized>(&mut self, r: &mut R, sequence_header: &OBU_Sequence_Header) -> Result<(), Error> {
+        self.use_superres = if sequence_header.enable_superres() != 0 {
+            r.read::<1, u8>()?
+        } else {
+            0u8
+        };
+        self.superres_denom = if self.use_superres != 0 {
+            let coded_denom: u32 = r.read_var(consts::SUPERRES_DENOM_BITS)?;
+            coded_denom + consts::SUPERRES_DENOM_MIN
+        } else {
+            consts::SUPERRES_NUM
+        };
+        self.upscaled_width = self.frame_width;
+        self.frame_width = (self.upscaled_width * consts::SUPERRES_NUM + (self.superres_denom / 2)) / self.superres_denom;
+        Ok(())
+    }
+
+    // 5.9.9 Compute image size semantics
+    fn compute_image_size(&mut self) {
+        self.mi_cols = 2 * ((self.frame_width + 7) >> 3);
+        self.mi_rows = 2 * ((self.frame_height + 7) >> 3);
+    }
+}
@@ -0,0 +1,162 @@
+use bitstream_io::{FromBitStream, FromBitStreamWith};
+
+use crate::{
+    consts::{
+        ALTREF2_FRAME, ALTREF_FRAME, BWDREF_FRAME, FRAME_RESTORATION_TYPE, GOLDEN_FRAME, INTRA_FRAME, LAST2_FRAME, LAST3_FRAME, LAST_FRAME,
+        RESTORATION_TILESIZE_MAX, TOTAL_REFS_PER_FRAME,
+    },
+    generics::su,
+    obu::OBU_Sequence_Header,
+    Error,
+};
+
+use super::{Cdef_Params, Loop_Filter_Params, Lr_Params, Ref_Frame};
+
+// loop_filter_ref_deltas set by setup_past_independence( ) and by lossless frames
+const DEFAULT_LOOP_FILTER_REF_DELTAS: [i8; TOTAL_REFS_PER_FRAME] = {
+    let mut deltas = [0i8; TOTAL_REFS_PER_FRAME];
+    deltas[INTRA_FRAME] = 1;
+    deltas[LAST_FRAME] = 0;
+    deltas[LAST2_FRAME] = 0;
+    deltas[LAST3_FRAME] = 0;
+    deltas[BWDREF_FRAME] = 0;
+    deltas[GOLDEN_FRAME] = -1;
+    deltas[ALTREF_FRAME] = -1;
+    deltas[ALTREF2_FRAME] = -1;
+    deltas
+};
+
+impl Loop_Filter_Params {
+
+    // 5.9.11 Loop filter params syntax, skipped is CodedLossless || allow_intrabc,
+    // previous is the reference frame of load_previous( ) when primary_ref_frame is set
+    pub(crate) fn from_reader<R: bitstream_io::BitRead + ?Sized>(
+        r: &mut R,
+        num_planes: u8,
+        skipped: bool,
+        previous: Option<&Ref_Frame>,
+    ) -> Result<Self, Error> {
+        let mut params = Loop_Filter_Params {
+            loop_filter_ref_deltas: DEFAULT_LOOP_FILTER_REF_DELTAS,
+            ..Default::default()
+        };
+        if skipped {
+            return Ok(params);
+        }
+
+        // load_loop_filter_params( prevFrame )
+        if let Some(previous) = previous {
+            params.loop_filter_ref_deltas = previous.saved_loop_filter_ref_deltas;
+            params.loop_filter_mode_deltas = previous.saved_loop_filter_mode_deltas;
+        }
+
+        params.loop_filter_level[0] = r.read::<6, u8>()?;
+        params.loop_filter_level[1] = r.read::<6, u8>()?;
+        if num_planes > 1 && (params.loop_filter_level[0] != 0 || params.loop_filter_level[1] != 0) {
+            params.loop_filter_level[2] = r.read::<6, u8>()?;
+            params.loop_filter_level[3] = r.read::<6, u8>()?;
+        }
+        params.loop_filter_sharpness = r.read::<3, u8>()?;
+        params.loop_filter_delta_enabled = r.read::<1, u8>()?;
+        if params.loop_filter_delta_enabled == 1 {
+            params.loop_filter_delta_update = r.read::<1, u8>()?;
+            if params.loop_filter_delta_update == 1 {
+                for delta in params.loop_filter_ref_deltas.iter_mut() {
+                    // update_ref_delta
+                    if r.read::<1, u8>()? == 1 {
+                        *delta = su::from_reader(r, &7)?.value() as i8;
+                    }
+                }
+                for delta in params.loop_filter_mode_deltas.iter_mut() {
+                    // update_mode_delta
+                    if r.read::<1, u8>()? == 1 {
+                        *delta = su::from_reader(r, &7)?.value() as i8;
+                    }
+                }
+            }
+        }
+        Ok(params)
+    }
+}
+
+impl Cdef_Params {
+
+    // 5.9.19 CDEF params syntax, skipped is CodedLossless || allow_intrabc || !enable_cdef
+    pub(crate) fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R, num_planes: u8, skipped: bool) -> Result<Self, Error> {
+        if skipped {
+            return Ok(Cdef_Params {
+                cdef_damping: 3,
+                cdef_bits: 0,
+                cdef_y_pri_strength: vec![0],
+                cdef_y_sec_strength: vec![0],
+                cdef_uv_pri_strength: vec![0],
+                cdef_uv_sec_strength: vec![0],
+            });
+        }
+
+        let mut params = Cdef_Params {
+            cdef_damping: r.read::<2, u8>()? + 3,
+            cdef_bits: r.read::<2, u8>()?,
+            ..Default::default()
+        };
+        for _ in 0..(1 << params.cdef_bits) {
+            params.cdef_y_pri_strength.push(r.read::<4, u8>()?);
+            params.cdef_y_sec_strength.push(sec_strength(r)?);
+            if num_planes > 1 {
+                params.cdef_uv_pri_strength.push(r.read::<4, u8>()?);
+                params.cdef_uv_sec_strength.push(sec_strength(r)?);
+            } else {
+                params.cdef_uv_pri_strength.push(0);
+                params.cdef_uv_sec_strength.push(0);
+            }
+        }
+        Ok(params)
+    }
+}
+
+// cdef_y_sec_strength and cdef_uv_sec_strength code 4 as 3
+fn sec_strength<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<u8, Error> {
+    let strength = r.read::<2, u8>()?;
+    Ok(if strength == 3 { 4 } else { strength })
+}
+
+impl Lr_Params {
+
+    // 5.9.20 Loop restoration params syntax, skipped is AllLossless || allow_intrabc || !enable_restoration
+    pub(crate) fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R, sequence_header: &OBU_Sequence_Header, skipped: bool) -> Result<Self, Error> {
+        let mut params = Lr_Params::default();
+        if skipped {
+            return Ok(params);
+        }
+
+        let color_config = sequence_header.color_config();
+        for i in 0..color_config.num_planes() as usize {
+            params.frame_restoration_type[i] = FRAME_RESTORATION_TYPE::from_reader(r)?;
+            if params.frame_restoration_type[i] != FRAME_RESTORATION_TYPE::RESTORE_NONE {
+                params.uses_lr = 1;
+                if i > 0 {
+                    params.uses_chroma_lr = 1;
+                }
+            }
+        }
+
+        if params.uses_lr == 1 {
+            if sequence_header.use_128x128_superblock() != 0 {
+                params.lr_unit_shift = r.read::<1, u8>()? + 1;
+            } else {
+                params.lr_unit_shift = r.read::<1, u8>()?;
+                if params.lr_unit_shift != 0 {
+                    // lr_unit_extra_shift
+                    params.lr_unit_shift += r.read::<1, u8>()?;
+                }
+            }
+            params.loop_restoration_size[0] = RESTORATION_TILESIZE_MAX >> (2 - params.lr_unit_shift);
+            if color_config.subsampling_x() != 0 && color_config.subsampling_y() != 0 && params.uses_chroma_lr == 1 {
+                params.lr_uv_shift = r.read::<1, u8>()?;
+            }
+            params.loop_restoration_size[1] = params.loop_restoration_size[0] >> params.lr_uv_shift;
+            params.loop_restoration_size[2] = params.loop_restoration_size[0] >> params.lr_uv_shift;
+        }
+        Ok(params)
+    }
+}
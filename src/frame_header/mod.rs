@@ -0,0 +1,259 @@
+mod impls;
+mod accessors;
+mod ref_frames;
+mod tile_info;
+mod quantization;
+mod segmentation;
+mod loop_filter;
+mod global_motion;
+mod film_grain;
+
+use crate::consts::{FRAME_RESTORATION_TYPE, FRAME_TYPE, GM_TYPE, INTERPOLATION_FILTER, MAX_SEGMENTS, NUM_REF_FRAMES, REFS_PER_FRAME, SEG_LVL_MAX, TOTAL_REFS_PER_FRAME, TX_MODE};
+
+// 5.9.2 Uncompressed header syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct FrameHeader {
+    show_existing_frame: u8,                    // 1 bit
+    frame_to_show_map_idx: u8,                  // 3 bits
+    frame_presentation_time: Option<u32>,       // frame_presentation_delay_length_minus_1 + 1 bits
+    display_frame_id: Option<u32>,              // idLen bits
+    frame_type: FRAME_TYPE,                     // 2 bits
+    show_frame: u8,                             // 1 bit
+    showable_frame: u8,                         // 1 bit
+    error_resilient_mode: u8,                   // 1 bit
+    disable_cdf_update: u8,                     // 1 bit
+    allow_screen_content_tools: u8,             // 1 bit
+    force_integer_mv: u8,                       // 1 bit
+    current_frame_id: u32,                      // idLen bits
+    frame_size_override_flag: u8,               // 1 bit
+    order_hint: u8,                             // OrderHintBits
+    primary_ref_frame: u8,                      // 3 bits
+    buffer_removal_time: Vec<Option<u32>>,      // buffer_removal_delay_length_minus_1 + 1 bits per operating point
+    refresh_frame_flags: u8,                    // 8 bits
+    ref_order_hint: Option<Vec<u8>>,            // OrderHintBits per reference slot
+    frame_size: Frame_Size,
+    allow_intrabc: u8,                          // 1 bit
+    frame_refs_short_signaling: u8,             // 1 bit
+    last_frame_idx: u8,                         // 3 bits
+    gold_frame_idx: u8,                         // 3 bits
+    ref_frame_idx: [u8; REFS_PER_FRAME],        // 3 bits each, or set_frame_refs( )
+    delta_frame_id_minus_1: Option<Vec<u32>>,   // delta_frame_id_length_minus_2 + 2 bits each
+    allow_high_precision_mv: u8,                // 1 bit
+    interpolation_filter: INTERPOLATION_FILTER, // 1 or 3 bits
+    is_motion_mode_switchable: u8,              // 1 bit
+    use_ref_frame_mvs: u8,                      // 1 bit
+    order_hints: [u8; TOTAL_REFS_PER_FRAME],    // OrderHints
+    ref_frame_sign_bias: [u8; TOTAL_REFS_PER_FRAME], // RefFrameSignBias
+    disable_frame_end_update_cdf: u8,           // 1 bit
+    tile_info: Tile_Info,
+    quantization_params: Quantization_Params,
+    segmentation_params: Segmentation_Params,
+    delta_q_params: Delta_Q_Params,
+    delta_lf_params: Delta_Lf_Params,
+    loop_filter_params: Loop_Filter_Params,
+    cdef_params: Cdef_Params,
+    lr_params: Lr_Params,
+    tx_mode: TX_MODE,                           // TxMode, tx_mode_select 1 bit
+    reference_select: u8,                       // 1 bit
+    skip_mode_present: u8,                      // 1 bit
+    skip_mode_frame: [usize; 2],                // SkipModeFrame
+    allow_warped_motion: u8,                    // 1 bit
+    reduced_tx_set: u8,                         // 1 bit
+    global_motion_params: Global_Motion_Params,
+    film_grain_params: Film_Grain_Params,
+}
+
+// 5.9.5 - 5.9.8 frame_size( ), superres_params( ), compute_image_size( ), render_size( )
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Frame_Size {
+    frame_width: u32,                   // FrameWidth after superres downscaling
+    frame_height: u32,                  // FrameHeight
+    upscaled_width: u32,                // UpscaledWidth
+    render_width: u32,                  // RenderWidth
+    render_height: u32,                 // RenderHeight
+    use_superres: u8,                   // 1 bit
+    superres_denom: u32,                // SuperresDenom
+    mi_cols: u32,                       // MiCols
+    mi_rows: u32,                       // MiRows
+}
+
+// 5.9.15 Tile info syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Tile_Info {
+    sb_shift: u32,                      // 5 for 128x128 superblocks, 4 otherwise
+    uniform_tile_spacing_flag: u8,      // 1 bit
+    tile_cols_log2: u32,                // TileColsLog2
+    tile_rows_log2: u32,                // TileRowsLog2
+    mi_col_starts: Vec<u32>,            // MiColStarts, TileCols + 1 entries
+    mi_row_starts: Vec<u32>,            // MiRowStarts, TileRows + 1 entries
+    context_update_tile_id: u32,        // TileRowsLog2 + TileColsLog2 bits
+    tile_size_bytes: u8,                // tile_size_bytes_minus_1 + 1
+}
+
+// 5.9.12 Quantization params syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Quantization_Params {
+    base_q_idx: u8,                     // 8 bits
+    delta_q_y_dc: i8,                   // DeltaQYDc, su(1+6)
+    diff_uv_delta: u8,                  // 1 bit
+    delta_q_u_dc: i8,                   // DeltaQUDc
+    delta_q_u_ac: i8,                   // DeltaQUAc
+    delta_q_v_dc: i8,                   // DeltaQVDc
+    delta_q_v_ac: i8,                   // DeltaQVAc
+    using_qmatrix: u8,                  // 1 bit
+    qm_y: u8,                           // 4 bits
+    qm_u: u8,                           // 4 bits
+    qm_v: u8,                           // 4 bits
+}
+
+// 5.9.14 Segmentation params syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Segmentation_Params {
+    segmentation_enabled: u8,           // 1 bit
+    segmentation_update_map: u8,        // 1 bit
+    segmentation_temporal_update: u8,   // 1 bit
+    segmentation_update_data: u8,       // 1 bit
+    feature_enabled: [[u8; SEG_LVL_MAX]; MAX_SEGMENTS], // FeatureEnabled
+    feature_data: [[i16; SEG_LVL_MAX]; MAX_SEGMENTS],   // FeatureData, clipped to Segmentation_Feature_Max
+    seg_id_pre_skip: u8,                // SegIdPreSkip
+    last_active_seg_id: u8,             // LastActiveSegId
+}
+
+// 5.9.17 Quantizer index delta parameters syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Delta_Q_Params {
+    delta_q_present: u8,                // 1 bit
+    delta_q_res: u8,                    // 2 bits
+}
+
+// 5.9.18 Loop filter delta parameters syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Delta_Lf_Params {
+    delta_lf_present: u8,               // 1 bit
+    delta_lf_res: u8,                   // 2 bits
+    delta_lf_multi: u8,                 // 1 bit
+}
+
+// 5.9.11 Loop filter params syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Loop_Filter_Params {
+    loop_filter_level: [u8; 4],         // 6 bits each, [ 2 ] and [ 3 ] only for chroma
+    loop_filter_sharpness: u8,          // 3 bits
+    loop_filter_delta_enabled: u8,      // 1 bit
+    loop_filter_delta_update: u8,       // 1 bit
+    loop_filter_ref_deltas: [i8; TOTAL_REFS_PER_FRAME], // su(1+6) each when updated
+    loop_filter_mode_deltas: [i8; 2],   // su(1+6) each when updated
+}
+
+// 5.9.19 CDEF params syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Cdef_Params {
+    cdef_damping: u8,                   // CdefDamping, cdef_damping_minus_3 + 3
+    cdef_bits: u8,                      // 2 bits
+    cdef_y_pri_strength: Vec<u8>,       // 4 bits each, 1 << cdef_bits entries
+    cdef_y_sec_strength: Vec<u8>,       // 2 bits each, 3 means 4
+    cdef_uv_pri_strength: Vec<u8>,      // 4 bits each
+    cdef_uv_sec_strength: Vec<u8>,      // 2 bits each, 3 means 4
+}
+
+// 5.9.20 Loop restoration params syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Lr_Params {
+    frame_restoration_type: [FRAME_RESTORATION_TYPE; 3], // FrameRestorationType, lr_type remapped
+    uses_lr: u8,                        // UsesLr
+    uses_chroma_lr: u8,                 // usesChromaLr
+    lr_unit_shift: u8,                  // 1 or 2 bits
+    lr_uv_shift: u8,                    // 1 bit
+    loop_restoration_size: [u32; 3],    // LoopRestorationSize, 0 without UsesLr
+}
+
+// 5.9.24 Global motion params syntax, indexed by reference frame from LAST_FRAME to ALTREF_FRAME
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Global_Motion_Params {
+    gm_type: [GM_TYPE; TOTAL_REFS_PER_FRAME],               // GmType
+    gm_params: [[i32; 6]; TOTAL_REFS_PER_FRAME],            // gm_params
+    prev_gm_params: [[i32; 6]; TOTAL_REFS_PER_FRAME],       // PrevGmParams the parameters were coded against
+}
+
+// 5.9.30 Film grain params syntax, all zero after reset_grain_params( )
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Film_Grain_Params {
+    apply_grain: u8,                    // 1 bit
+    grain_seed: u16,                    // 16 bits
+    update_grain: u8,                   // 1 bit
+    film_grain_params_ref_idx: u8,      // 3 bits
+    num_y_points: u8,                   // 4 bits
+    point_y_value: Vec<u8>,             // 8 bits each
+    point_y_scaling: Vec<u8>,           // 8 bits each
+    chroma_scaling_from_luma: u8,       // 1 bit
+    num_cb_points: u8,                  // 4 bits
+    point_cb_value: Vec<u8>,            // 8 bits each
+    point_cb_scaling: Vec<u8>,          // 8 bits each
+    num_cr_points: u8,                  // 4 bits
+    point_cr_value: Vec<u8>,            // 8 bits each
+    point_cr_scaling: Vec<u8>,          // 8 bits each
+    grain_scaling_minus_8: u8,          // 2 bits
+    ar_coeff_lag: u8,                   // 2 bits
+    ar_coeffs_y_plus_128: Vec<u8>,      // 8 bits each, numPosLuma entries
+    ar_coeffs_cb_plus_128: Vec<u8>,     // 8 bits each, numPosChroma entries
+    ar_coeffs_cr_plus_128: Vec<u8>,     // 8 bits each, numPosChroma entries
+    ar_coeff_shift_minus_6: u8,         // 2 bits
+    grain_scale_shift: u8,              // 2 bits
+    cb_mult: u8,                        // 8 bits
+    cb_luma_mult: u8,                   // 8 bits
+    cb_offset: u16,                     // 9 bits
+    cr_mult: u8,                        // 8 bits
+    cr_luma_mult: u8,                   // 8 bits
+    cr_offset: u16,                     // 9 bits
+    overlap_flag: u8,                   // 1 bit
+    clip_to_restricted_range: u8,       // 1 bit
+}
+
+// Tile geometry of a frame in raster order
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TileLayout {
+    tile_cols: u32,
+    tile_rows: u32,
+    superblock_size: u32,               // 64 or 128 luma samples
+    tiles: Vec<TileRect>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TileRect {
+    tile_num: u32,
+    tile_row: u32,
+    tile_col: u32,
+    mi_row_start: u32,
+    mi_row_end: u32,
+    mi_col_start: u32,
+    mi_col_end: u32,
+    sb_row_start: u32,
+    sb_row_end: u32,
+    sb_col_start: u32,
+    sb_col_end: u32,
+    x: u32,                             // luma samples
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+// 7.20 Reference frame update process, values saved per reference slot
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Ref_Frame {
+    ref_valid: u8,
+    ref_frame_id: u32,
+    ref_frame_type: FRAME_TYPE,
+    ref_order_hint: u8,
+    ref_frame_size: Frame_Size,                     // RefUpscaledWidth, RefFrameWidth, RefMiCols, ...
+    saved_order_hints: [u8; TOTAL_REFS_PER_FRAME],
+    saved_segmentation_params: Segmentation_Params, // SavedFeatureEnabled and SavedFeatureData
+    saved_loop_filter_ref_deltas: [i8; TOTAL_REFS_PER_FRAME],
+    saved_loop_filter_mode_deltas: [i8; 2],
+    saved_gm_params: [[i32; 6]; TOTAL_REFS_PER_FRAME],
+    saved_film_grain_params: Film_Grain_Params,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RefFrameStore {
+    ref_frames: [Ref_Frame; NUM_REF_FRAMES],
+}
@@ -0,0 +1,146 @@
+use bitstream_io::FromBitStreamWith;
+
+use crate::{
+    consts::{MAX_SEGMENTS, NUM_QM_LEVELS, SEG_LVL_ALT_Q},
+    generics::su,
+    obu::Color_Config,
+    Error,
+};
+
+use super::{Delta_Lf_Params, Delta_Q_Params, FrameHeader, Quantization_Params};
+
+impl Quantization_Params {
+
+    // 5.9.12 Quantization params syntax
+    pub(crate) fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R, color_config: &Color_Config) -> Result<Self, Error> {
+        let mut params = Quantization_Params {
+            base_q_idx: r.read::<8, u8>()?,
+            delta_q_y_dc: read_delta_q(r)?,
+            ..Default::default()
+        };
+
+        if color_config.num_planes() > 1 {
+            params.diff_uv_delta = if color_config.separate_uv_delta_q() != 0 {
+                r.read::<1, u8>()?
+            } else {
+                0u8
+            };
+            params.delta_q_u_dc = read_delta_q(r)?;
+            params.delta_q_u_ac = read_delta_q(r)?;
+            if params.diff_uv_delta != 0 {
+                params.delta_q_v_dc = read_delta_q(r)?;
+                params.delta_q_v_ac = read_delta_q(r)?;
+            } else {
+                params.delta_q_v_dc = params.delta_q_u_dc;
+                params.delta_q_v_ac = params.delta_q_u_ac;
+            }
+        }
+
+        params.using_qmatrix = r.read::<1, u8>()?;
+        if params.using_qmatrix != 0 {
+            params.qm_y = r.read::<4, u8>()?;
+            params.qm_u = r.read::<4, u8>()?;
+            params.qm_v = if color_config.separate_uv_delta_q() == 0 {
+                params.qm_u
+            } else {
+                r.read::<4, u8>()?
+            };
+        }
+        Ok(params)
+    }
+}
+
+// 5.9.13 Delta quantizer syntax
+fn read_delta_q<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<i8, Error> {
+    // delta_coded
+    if r.read::<1, u8>()? == 1 {
+        Ok(su::from_reader(r, &7)?.value() as i8)
+    } else {
+        Ok(0i8)
+    }
+}
+
+impl Delta_Q_Params {
+
+    // 5.9.17 Quantizer index delta parameters syntax
+    pub(crate) fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R, base_q_idx: u8) -> Result<Self, Error> {
+        let mut params = Delta_Q_Params::default();
+        if base_q_idx > 0 {
+            params.delta_q_present = r.read::<1, u8>()?;
+        }
+        if params.delta_q_present != 0 {
+            params.delta_q_res = r.read::<2, u8>()?;
+        }
+        Ok(params)
+    }
+}
+
+impl Delta_Lf_Params {
+
+    // 5.9.18 Loop filter delta parameters syntax
+    pub(crate) fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R, delta_q_present: u8, allow_intrabc: u8) -> Result<Self, Error> {
+        let mut params = Delta_Lf_Params::default();
+        if delta_q_present != 0 {
+            if allow_intrabc == 0 {
+                params.delta_lf_present = r.read::<1, u8>()?;
+            }
+            if params.delta_lf_present != 0 {
+                params.delta_lf_res = r.read::<2, u8>()?;
+                params.delta_lf_multi = r.read::<1, u8>()?;
+            }
+        }
+        Ok(params)
+    }
+}
+
+impl FrameHeader {
+
+    // 7.12.2 get_qindex( ignoreDeltaQ, segmentId ), current_q_index is CurrentQIndex of the block
+    pub fn get_qindex(&self, ignore_delta_q: bool, segment_id: usize, current_q_index: u8) -> u8 {
+        let base_q_idx = self.quantization_params.base_q_idx as i32;
+        let use_delta_q = !ignore_delta_q && self.delta_q_params.delta_q_present == 1;
+        if self.segmentation_params.feature_active(segment_id, SEG_LVL_ALT_Q) {
+            let data = self.segmentation_params.feature_data(segment_id, SEG_LVL_ALT_Q) as i32;
+            let qindex = if use_delta_q { current_q_index as i32 + data } else { base_q_idx + data };
+            qindex.clamp(0, 255) as u8
+        } else if use_delta_q {
+            current_q_index
+        } else {
+            base_q_idx as u8
+        }
+    }
+
+    // LosslessArray[ segmentId ]
+    pub fn lossless(&self, segment_id: usize) -> bool {
+        let q = &self.quantization_params;
+        self.get_qindex(true, segment_id, 0) == 0
+            && q.delta_q_y_dc == 0
+            && q.delta_q_u_ac == 0
+            && q.delta_q_u_dc == 0
+            && q.delta_q_v_ac == 0
+            && q.delta_q_v_dc == 0
+    }
+
+    // CodedLossless
+    pub fn coded_lossless(&self) -> bool {
+        (0..MAX_SEGMENTS).all(|segment_id| self.lossless(segment_id))
+    }
+
+    // AllLossless
+    pub fn all_lossless(&self) -> bool {
+        self.coded_lossless() && self.frame_size.frame_width == self.frame_size.upscaled_width
+    }
+
+    // Quantizer matrix level of a plane used by 7.12.3, SegQMLevel when using_qmatrix is 1
+    pub fn qm_level(&self, plane: usize, segment_id: usize) -> u8 {
+        let q = &self.quantization_params;
+        if q.using_qmatrix == 0 || self.lossless(segment_id) {
+            return NUM_QM_LEVELS - 1;
+        }
+        match plane {
+            0 => q.qm_y,
+            1 => q.qm_u,
+            _ => q.qm_v,
+        }
+    }
+}
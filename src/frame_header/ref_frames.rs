@@ -0,0 +1,169 @@
+use crate::{
+    consts::{ALTREF2_FRAME, ALTREF_FRAME, BWDREF_FRAME, GOLDEN_FRAME, LAST2_FRAME, LAST3_FRAME, LAST_FRAME, NUM_REF_FRAMES, REFS_PER_FRAME},
+    obu::OBU_Sequence_Header,
+    Error,
+};
+
+use super::{global_motion::DEFAULT_GM_PARAMS, FrameHeader, Ref_Frame, RefFrameStore};
+
+// Slots never written save the identity model, PrevGmParams loaded from them match setup_past_independence( )
+impl Default for Ref_Frame {
+    fn default() -> Self {
+        Self {
+            ref_valid: 0,
+            ref_frame_id: 0,
+            ref_frame_type: Default::default(),
+            ref_order_hint: 0,
+            ref_frame_size: Default::default(),
+            saved_order_hints: Default::default(),
+            saved_segmentation_params: Default::default(),
+            saved_loop_filter_ref_deltas: Default::default(),
+            saved_loop_filter_mode_deltas: Default::default(),
+            saved_gm_params: DEFAULT_GM_PARAMS,
+            saved_film_grain_params: Default::default(),
+        }
+    }
+}
+
+impl RefFrameStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ref_frame(&self, i: usize) -> &Ref_Frame {
+        &self.ref_frames[i]
+    }
+
+    // RefValid[ i ] = 0 and RefOrderHint[ i ] = 0 for a shown key frame
+    pub(crate) fn reset(&mut self) {
+        for ref_frame in self.ref_frames.iter_mut() {
+            ref_frame.ref_valid = 0;
+            ref_frame.ref_order_hint = 0;
+        }
+    }
+
+    // 5.9.4 Reference frame marking function
+    pub(crate) fn mark_ref_frames(&mut self, current_frame_id: u32, id_len: u32, diff_len: u32) {
+        let window = 1u32 << diff_len;
+        for ref_frame in self.ref_frames.iter_mut() {
+            let ref_frame_id = ref_frame.ref_frame_id;
+            let outside = if current_frame_id > window {
+                ref_frame_id > current_frame_id || ref_frame_id < current_frame_id - window
+            } else {
+                ref_frame_id > current_frame_id && ref_frame_id < (1u32 << id_len) + current_frame_id - window
+            };
+            if outside {
+                ref_frame.ref_valid = 0;
+            }
+        }
+    }
+
+    // ref_order_hint[ i ] that disagrees with RefOrderHint[ i ] invalidates the slot
+    pub(crate) fn check_ref_order_hint(&mut self, i: usize, ref_order_hint: u8) {
+        let ref_frame = &mut self.ref_frames[i];
+        if ref_frame.ref_order_hint != ref_order_hint {
+            ref_frame.ref_valid = 0;
+            ref_frame.ref_order_hint = ref_order_hint;
+        }
+    }
+
+    // 7.8 Set frame refs process, ref_frame_idx from last_frame_idx and gold_frame_idx
+    pub(crate) fn set_frame_refs(
+        &self,
+        sequence_header: &OBU_Sequence_Header,
+        order_hint: u8,
+        last_frame_idx: u8,
+        gold_frame_idx: u8,
+    ) -> Result<[u8; REFS_PER_FRAME], Error> {
+        let mut ref_frame_idx: [Option<usize>; REFS_PER_FRAME] = [None; REFS_PER_FRAME];
+        ref_frame_idx[0] = Some(last_frame_idx as usize);
+        ref_frame_idx[GOLDEN_FRAME - LAST_FRAME] = Some(gold_frame_idx as usize);
+        let mut used_frame = [false; NUM_REF_FRAMES];
+        used_frame[last_frame_idx as usize] = true;
+        used_frame[gold_frame_idx as usize] = true;
+
+        let cur_frame_hint = 1i32 << (sequence_header.order_hint_bits() as u32 - 1);
+        let shifted_order_hints: [i32; NUM_REF_FRAMES] =
+            std::array::from_fn(|i| cur_frame_hint + sequence_header.get_relative_dist(self.ref_frames[i].ref_order_hint, order_hint));
+        if shifted_order_hints[last_frame_idx as usize] >= cur_frame_hint || shifted_order_hints[gold_frame_idx as usize] >= cur_frame_hint {
+            return Err(Error::ConformanceViolation {
+                requirement: "lastOrderHint and goldOrderHint are before the current frame",
+            });
+        }
+
+        // find_latest_backward( ), find_earliest_backward( ) and find_latest_forward( ) over the unused slots
+        let find = |used_frame: &[bool; NUM_REF_FRAMES], backward: bool, latest: bool| -> Option<usize> {
+            let mut found: Option<usize> = None;
+            for (i, &hint) in shifted_order_hints.iter().enumerate() {
+                if used_frame[i] || (hint >= cur_frame_hint) != backward {
+                    continue;
+                }
+                let better = match found {
+                    None => true,
+                    Some(j) if latest => hint >= shifted_order_hints[j],
+                    Some(j) => hint < shifted_order_hints[j],
+                };
+                if better {
+                    found = Some(i);
+                }
+            }
+            found
+        };
+
+        for (ref_frame, latest) in [(ALTREF_FRAME, true), (BWDREF_FRAME, false), (ALTREF2_FRAME, false)] {
+            if let Some(i) = find(&used_frame, true, latest) {
+                ref_frame_idx[ref_frame - LAST_FRAME] = Some(i);
+                used_frame[i] = true;
+            }
+        }
+
+        // Ref_Frame_List
+        for ref_frame in [LAST2_FRAME, LAST3_FRAME, BWDREF_FRAME, ALTREF2_FRAME, ALTREF_FRAME] {
+            if ref_frame_idx[ref_frame - LAST_FRAME].is_none()
+                && let Some(i) = find(&used_frame, false, true)
+            {
+                ref_frame_idx[ref_frame - LAST_FRAME] = Some(i);
+                used_frame[i] = true;
+            }
+        }
+
+        // Remaining references take the slot with the earliest order hint
+        let mut earliest = 0usize;
+        for (i, &hint) in shifted_order_hints.iter().enumerate() {
+            if hint < shifted_order_hints[earliest] {
+                earliest = i;
+            }
+        }
+        Ok(ref_frame_idx.map(|idx| idx.unwrap_or(earliest) as u8))
+    }
+
+    // 7.20 Reference frame update process
+    pub fn update(&mut self, header: &FrameHeader) {
+        if header.show_existing_frame == 1 {
+            // 7.21 Reference frame loading process, a shown key frame refreshes every slot
+            if header.refresh_frame_flags != 0 {
+                let shown = self.ref_frames[header.frame_to_show_map_idx as usize].clone();
+                self.ref_frames = std::array::from_fn(|_| shown.clone());
+            }
+            return;
+        }
+
+        for (i, ref_frame) in self.ref_frames.iter_mut().enumerate() {
+            if (header.refresh_frame_flags >> i) & 1 == 1 {
+                *ref_frame = Ref_Frame {
+                    ref_valid: 1,
+                    ref_frame_id: header.current_frame_id,
+                    ref_frame_type: header.frame_type,
+                    ref_order_hint: header.order_hint,
+                    ref_frame_size: header.frame_size.clone(),
+                    saved_order_hints: header.order_hints,
+                    saved_segmentation_params: header.segmentation_params.clone(),
+                    saved_loop_filter_ref_deltas: header.loop_filter_params.loop_filter_ref_deltas,
+                    saved_loop_filter_mode_deltas: header.loop_filter_params.loop_filter_mode_deltas,
+                    saved_gm_params: header.global_motion_params.gm_params,
+                    saved_film_grain_params: header.film_grain_params.clone(),
+                };
+            }
+        }
+    }
+}
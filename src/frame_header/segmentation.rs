@@ -0,0 +1,81 @@
+use bitstream_io::FromBitStreamWith;
+
+use crate::{
+    consts::{MAX_SEGMENTS, PRIMARY_REF_NONE, SEGMENTATION_FEATURE_BITS, SEGMENTATION_FEATURE_MAX, SEGMENTATION_FEATURE_SIGNED, SEG_LVL_MAX, SEG_LVL_REF_FRAME},
+    generics::su,
+    Error,
+};
+
+use super::Segmentation_Params;
+
+impl Segmentation_Params {
+
+    // 5.9.14 Segmentation params syntax, previous holds the values of load_previous( ) when primary_ref_frame is set
+    pub(crate) fn from_reader<R: bitstream_io::BitRead + ?Sized>(
+        r: &mut R,
+        primary_ref_frame: u8,
+        previous: Option<&Segmentation_Params>,
+    ) -> Result<Self, Error> {
+        let mut params = Segmentation_Params {
+            segmentation_enabled: r.read::<1, u8>()?,
+            ..Default::default()
+        };
+
+        if params.segmentation_enabled == 1 {
+            if primary_ref_frame == PRIMARY_REF_NONE {
+                params.segmentation_update_map = 1;
+                params.segmentation_temporal_update = 0;
+                params.segmentation_update_data = 1;
+            } else {
+                params.segmentation_update_map = r.read::<1, u8>()?;
+                if params.segmentation_update_map == 1 {
+                    params.segmentation_temporal_update = r.read::<1, u8>()?;
+                }
+                params.segmentation_update_data = r.read::<1, u8>()?;
+            }
+
+            if params.segmentation_update_data == 1 {
+                for i in 0..MAX_SEGMENTS {
+                    for j in 0..SEG_LVL_MAX {
+                        let feature_enabled = r.read::<1, u8>()?;
+                        params.feature_enabled[i][j] = feature_enabled;
+                        let mut clipped_value = 0i16;
+                        if feature_enabled == 1 {
+                            let bits_to_read = SEGMENTATION_FEATURE_BITS[j];
+                            let limit = SEGMENTATION_FEATURE_MAX[j];
+                            clipped_value = if SEGMENTATION_FEATURE_SIGNED[j] == 1 {
+                                let feature_value = su::from_reader(r, &(1 + bits_to_read))?.value();
+                                feature_value.clamp(-limit as i32, limit as i32) as i16
+                            } else {
+                                let feature_value: u32 = if bits_to_read > 0 { r.read_var(bits_to_read)? } else { 0u32 };
+                                feature_value.min(limit as u32) as i16
+                            };
+                        }
+                        params.feature_data[i][j] = clipped_value;
+                    }
+                }
+            } else if let Some(previous) = previous {
+                // load_segmentation_params( prevFrame )
+                params.feature_enabled = previous.feature_enabled;
+                params.feature_data = previous.feature_data;
+            }
+        }
+
+        for i in 0..MAX_SEGMENTS {
+            for j in 0..SEG_LVL_MAX {
+                if params.feature_enabled[i][j] == 1 {
+                    params.last_active_seg_id = i as u8;
+                    if j >= SEG_LVL_REF_FRAME {
+                        params.seg_id_pre_skip = 1;
+                    }
+                }
+            }
+        }
+        Ok(params)
+    }
+
+    // seg_feature_active_idx( idx, feature )
+    pub fn feature_active(&self, segment_id: usize, feature: usize) -> bool {
+        self.segmentation_enabled == 1 && self.feature_enabled[segment_id][feature] == 1
+    }
+}
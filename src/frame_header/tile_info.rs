@@ -0,0 +1,283 @@
+use bitstream_io::FromBitStreamWith;
+
+use crate::{
+    consts::{MAX_TILE_AREA, MAX_TILE_COLS, MAX_TILE_ROWS, MAX_TILE_WIDTH},
+    generics::ns,
+    Error,
+};
+
+use super::{Frame_Size, TileLayout, TileRect, Tile_Info};
+
+impl Tile_Info {
+
+    // 5.9.15 Tile info syntax
+    pub(crate) fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R, frame_size: &Frame_Size, use_128x128_superblock: u8) -> Result<Self, Error> {
+        let mut tile_info = Tile_Info::default();
+
+        let sb_cols = if use_128x128_superblock != 0 { (frame_size.mi_cols + 31) >> 5 } else { (frame_size.mi_cols + 15) >> 4 };
+        let sb_rows = if use_128x128_superblock != 0 { (frame_size.mi_rows + 31) >> 5 } else { (frame_size.mi_rows + 15) >> 4 };
+        // A frame without superblocks has no tiles to lay out
+        if sb_cols == 0 || sb_rows == 0 {
+            return Err(Error::ConformanceViolation {
+                requirement: "the frame is at least one superblock wide and high",
+            });
+        }
+        let sb_shift = if use_128x128_superblock != 0 { 5u32 } else { 4u32 };
+        let sb_size = sb_shift + 2;
+        let max_tile_width_sb = MAX_TILE_WIDTH >> sb_size;
+        let mut max_tile_area_sb = MAX_TILE_AREA >> (2 * sb_size);
+        let min_log2_tile_cols = tile_log2(max_tile_width_sb, sb_cols);
+        let max_log2_tile_cols = tile_log2(1, sb_cols.min(MAX_TILE_COLS));
+        let max_log2_tile_rows = tile_log2(1, sb_rows.min(MAX_TILE_ROWS));
+        let min_log2_tiles = min_log2_tile_cols.max(tile_log2(max_tile_area_sb, sb_rows * sb_cols));
+
+        tile_info.sb_shift = sb_shift;
+        tile_info.uniform_tile_spacing_flag = r.read::<1, u8>()?;
+
+        if tile_info.uniform_tile_spacing_flag != 0 {
+            tile_info.tile_cols_log2 = min_log2_tile_cols;
+            while tile_info.tile_cols_log2 < max_log2_tile_cols {
+                // increment_tile_cols_log2
+                if r.read::<1, u8>()? == 1 {
+                    tile_info.tile_cols_log2 += 1;
+                } else {
+                    break;
+                }
+            }
+            let tile_width_sb = (sb_cols + (1 << tile_info.tile_cols_log2) - 1) >> tile_info.tile_cols_log2;
+            tile_info.mi_col_starts = (0..sb_cols).step_by(tile_width_sb as usize).map(|start_sb| start_sb << sb_shift).collect();
+            tile_info.mi_col_starts.push(frame_size.mi_cols);
+
+            let min_log2_tile_rows = min_log2_tiles.saturating_sub(tile_info.tile_cols_log2);
+            tile_info.tile_rows_log2 = min_log2_tile_rows;
+            while tile_info.tile_rows_log2 < max_log2_tile_rows {
+                // increment_tile_rows_log2
+                if r.read::<1, u8>()? == 1 {
+                    tile_info.tile_rows_log2 += 1;
+                } else {
+                    break;
+                }
+            }
+            let tile_height_sb = (sb_rows + (1 << tile_info.tile_rows_log2) - 1) >> tile_info.tile_rows_log2;
+            tile_info.mi_row_starts = (0..sb_rows).step_by(tile_height_sb as usize).map(|start_sb| start_sb << sb_shift).collect();
+            tile_info.mi_row_starts.push(frame_size.mi_rows);
+        } else {
+            let mut widest_tile_sb = 0u32;
+            let mut start_sb = 0u32;
+            while start_sb < sb_cols {
+                tile_info.mi_col_starts.push(start_sb << sb_shift);
+                let max_width = (sb_cols - start_sb).min(max_tile_width_sb);
+                let width_in_sbs_minus_1 = ns::from_reader(r, &max_width)?.value();
+                let size_sb = width_in_sbs_minus_1 + 1;
+                widest_tile_sb = widest_tile_sb.max(size_sb);
+                start_sb += size_sb;
+            }
+            tile_info.mi_col_starts.push(frame_size.mi_cols);
+            tile_info.tile_cols_log2 = tile_log2(1, tile_info.tile_cols());
+
+            max_tile_area_sb = if min_log2_tiles > 0 {
+                (sb_rows * sb_cols) >> (min_log2_tiles + 1)
+            } else {
+                sb_rows * sb_cols
+            };
+            let max_tile_height_sb = (max_tile_area_sb / widest_tile_sb).max(1);
+
+            start_sb = 0;
+            while start_sb < sb_rows {
+                tile_info.mi_row_starts.push(start_sb << sb_shift);
+                let max_height = (sb_rows - start_sb).min(max_tile_height_sb);
+                let height_in_sbs_minus_1 = ns::from_reader(r, &max_height)?.value();
+                start_sb += height_in_sbs_minus_1 + 1;
+            }
+            tile_info.mi_row_starts.push(frame_size.mi_rows);
+            tile_info.tile_rows_log2 = tile_log2(1, tile_info.tile_rows());
+        }
+
+        if tile_info.tile_cols_log2 > 0 || tile_info.tile_rows_log2 > 0 {
+            tile_info.context_update_tile_id = r.read_var(tile_info.tile_rows_log2 + tile_info.tile_cols_log2)?;
+            tile_info.tile_size_bytes = r.read::<2, u8>()? + 1;
+        }
+
+        Ok(tile_info)
+    }
+
+    pub fn uniform_tile_spacing_flag(&self) -> u8 {
+        self.uniform_tile_spacing_flag
+    }
+
+    // TileCols
+    pub fn tile_cols(&self) -> u32 {
+        self.mi_col_starts.len().saturating_sub(1) as u32
+    }
+
+    // TileRows
+    pub fn tile_rows(&self) -> u32 {
+        self.mi_row_starts.len().saturating_sub(1) as u32
+    }
+
+    pub fn tile_cols_log2(&self) -> u32 {
+        self.tile_cols_log2
+    }
+
+    pub fn tile_rows_log2(&self) -> u32 {
+        self.tile_rows_log2
+    }
+
+    pub fn num_tiles(&self) -> u32 {
+        self.tile_cols() * self.tile_rows()
+    }
+
+    pub fn mi_col_starts(&self) -> &[u32] {
+        &self.mi_col_starts
+    }
+
+    pub fn mi_row_starts(&self) -> &[u32] {
+        &self.mi_row_starts
+    }
+
+    pub fn context_update_tile_id(&self) -> u32 {
+        self.context_update_tile_id
+    }
+
+    // TileSizeBytes, only coded when the frame has more than one tile
+    pub fn tile_size_bytes(&self) -> u8 {
+        self.tile_size_bytes
+    }
+
+    pub fn layout(&self, frame_size: &Frame_Size) -> TileLayout {
+        let tile_cols = self.tile_cols();
+        let tile_rows = self.tile_rows();
+        let mut tiles = Vec::with_capacity((tile_cols * tile_rows) as usize);
+
+        for tile_row in 0..tile_rows {
+            for tile_col in 0..tile_cols {
+                let mi_row_start = self.mi_row_starts[tile_row as usize];
+                let mi_row_end = self.mi_row_starts[tile_row as usize + 1];
+                let mi_col_start = self.mi_col_starts[tile_col as usize];
+                let mi_col_end = self.mi_col_starts[tile_col as usize + 1];
+                let sb_mask = (1u32 << self.sb_shift) - 1;
+
+                // MI units are 4x4 luma samples, the last tile is cropped to the frame
+                let x = mi_col_start * 4;
+                let y = mi_row_start * 4;
+                tiles.push(TileRect {
+                    tile_num: tile_row * tile_cols + tile_col,
+                    tile_row,
+                    tile_col,
+                    mi_row_start,
+                    mi_row_end,
+                    mi_col_start,
+                    mi_col_end,
+                    sb_row_start: mi_row_start >> self.sb_shift,
+                    sb_row_end: (mi_row_end + sb_mask) >> self.sb_shift,
+                    sb_col_start: mi_col_start >> self.sb_shift,
+                    sb_col_end: (mi_col_end + sb_mask) >> self.sb_shift,
+                    x,
+                    y,
+                    width: (mi_col_end * 4).min(frame_size.frame_width).saturating_sub(x),
+                    height: (mi_row_end * 4).min(frame_size.frame_height).saturating_sub(y),
+                });
+            }
+        }
+
+        TileLayout {
+            tile_cols,
+            tile_rows,
+            superblock_size: 4 << self.sb_shift,
+            tiles,
+        }
+    }
+}
+
+// 5.9.16 Tile size calculation function
+fn tile_log2(blk_size: u32, target: u32) -> u32 {
+    let mut k = 0u32;
+    while (blk_size << k) < target {
+        k += 1;
+    }
+    k
+}
+
+impl TileLayout {
+    pub fn tile_cols(&self) -> u32 {
+        self.tile_cols
+    }
+
+    pub fn tile_rows(&self) -> u32 {
+        self.tile_rows
+    }
+
+    pub fn superblock_size(&self) -> u32 {
+        self.superblock_size
+    }
+
+    pub fn tiles(&self) -> &[TileRect] {
+        &self.tiles
+    }
+
+    pub fn tile(&self, tile_num: u32) -> Option<&TileRect> {
+        self.tiles.get(tile_num as usize)
+    }
+}
+
+impl TileRect {
+    pub fn tile_num(&self) -> u32 {
+        self.tile_num
+    }
+
+    pub fn tile_row(&self) -> u32 {
+        self.tile_row
+    }
+
+    pub fn tile_col(&self) -> u32 {
+        self.tile_col
+    }
+
+    pub fn mi_row_start(&self) -> u32 {
+        self.mi_row_start
+    }
+
+    pub fn mi_row_end(&self) -> u32 {
+        self.mi_row_end
+    }
+
+    pub fn mi_col_start(&self) -> u32 {
+        self.mi_col_start
+    }
+
+    pub fn mi_col_end(&self) -> u32 {
+        self.mi_col_end
+    }
+
+    pub fn sb_row_start(&self) -> u32 {
+        self.sb_row_start
+    }
+
+    pub fn sb_row_end(&self) -> u32 {
+        self.sb_row_end
+    }
+
+    pub fn sb_col_start(&self) -> u32 {
+        self.sb_col_start
+    }
+
+    pub fn sb_col_end(&self) -> u32 {
+        self.sb_col_end
+    }
+
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
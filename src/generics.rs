@@ -1,6 +1,6 @@
-use std::u32;
+use bitstream_io::{FromBitStream, FromBitStreamWith, ToBitStream};
 
-use bitstream_io::FromBitStream;
+use crate::Error;
 
 // 4.10.3 UVLC
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -12,10 +12,14 @@ impl uvlc {
     pub fn new(value: u32) -> Self {
         Self { value }
     }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
 }
 
 impl FromBitStream for uvlc {
-    type Error = std::io::Error;
+    type Error = Error;
 
     fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
     where
@@ -29,23 +33,80 @@ impl FromBitStream for uvlc {
         if leading_zeros >= 32 {
             return Ok(Self { value: u32::MAX });
         }
-        r.read_var(leading_zeros).map(|a| Self { value: a })
+        let value: u32 = if leading_zeros == 0 { 0u32 } else { r.read_var(leading_zeros)? };
+        Ok(Self { value: value + ((1u32 << leading_zeros) - 1) })
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+impl ToBitStream for uvlc {
+    type Error = std::io::Error;
+
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        // 32 or more leading zeros always decode as (1 << 32) - 1
+        if self.value == u32::MAX {
+            w.write_var(32, 0u32)?;
+            return w.write_bit(true);
+        }
+
+        let leading_zeros = floor_log2(self.value + 1);
+        if leading_zeros > 0 {
+            w.write_var(leading_zeros, 0u32)?;
+        }
+        w.write_bit(true)?;
+        if leading_zeros > 0 {
+            w.write_var(leading_zeros, self.value + 1 - (1u32 << leading_zeros))?;
+        }
+        Ok(())
+    }
+}
+
+// 4.10.5 leb128
+#[derive(Debug, Eq, Clone)]
 pub struct leb_128 {
     value: u64,
+    min_bytes: u8,              // padded with 0x80 continuation bytes up to this length when written
+}
+
+// Padding only changes how the value is written, not the value
+impl PartialEq for leb_128 {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
 }
 
 impl leb_128 {
     pub fn new(value: u64) -> Self {
-        Self { value }
+        Self { value, min_bytes: 1 }
+    }
+
+    // Encoders may pad leb128 values, e.g. to patch obu_size in place later
+    pub fn with_min_bytes(value: u64, min_bytes: u8) -> Self {
+        Self { value, min_bytes: min_bytes.clamp(1, 8) }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn min_bytes(&self) -> u8 {
+        self.min_bytes
+    }
+
+    // Number of bytes the value takes when written
+    pub fn size(&self) -> usize {
+        let mut bytes = 1usize;
+        while bytes < 8 && self.value >> (7 * bytes) != 0 {
+            bytes += 1;
+        }
+        bytes.max(self.min_bytes as usize)
     }
 }
 
 impl FromBitStream for leb_128 {
-    type Error = std::io::Error;
+    type Error = Error;
 
     fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
     where
@@ -58,12 +119,222 @@ impl FromBitStream for leb_128 {
             value |= ((buf[0] & 0x7f) as u64) << (i * 7);
 
             if buf[0] & 0x80 == 0 {
-                return Ok(Self { value: value });
+                return Ok(Self { value, min_bytes: i + 1 });
             }
         }
-        Err(std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            "leb128 exeeded 8 bytes",
-        ))
+        Err(Error::ConformanceViolation {
+            requirement: "leb128 uses at most 8 bytes",
+        })
+    }
+}
+
+impl ToBitStream for leb_128 {
+    type Error = std::io::Error;
+
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        if self.value >> 56 != 0 {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "leb128 value exceeds 8 bytes"));
+        }
+
+        let size = self.size();
+        for i in 0..size {
+            let mut byte = ((self.value >> (i * 7)) & 0x7f) as u8;
+            if i + 1 < size {
+                byte |= 0x80;
+            }
+            w.write::<8, u8>(byte)?;
+        }
+        Ok(())
+    }
+}
+
+// 4.10.6 su(n)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct su {
+    value: i32,
+}
+
+impl su {
+    pub fn new(value: i32) -> Self {
+        Self { value }
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+impl<'a> FromBitStreamWith<'a> for su {
+    type Context = u32; // n
+    type Error = Error;
+
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R, n: &Self::Context) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        if *n == 0 || *n > 32 {
+            return Err(Error::InvalidSyntax { element: "su(n)", value: *n as u64 });
+        }
+        let value: u32 = r.read_var(*n)?;
+        let sign_mask = 1i64 << (*n - 1);
+        let mut value = value as i64;
+        if value & sign_mask != 0 {
+            value -= 2 * sign_mask;
+        }
+        Ok(Self { value: value as i32 })
+    }
+}
+
+// 4.10.7 ns(n)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ns {
+    value: u32,
+}
+
+impl ns {
+    pub fn new(value: u32) -> Self {
+        Self { value }
+    }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+impl<'a> FromBitStreamWith<'a> for ns {
+    type Context = u32; // n
+    type Error = Error;
+
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R, n: &Self::Context) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        if *n == 0 {
+            return Err(Error::InvalidSyntax { element: "ns(n)", value: 0 });
+        }
+        let w = floor_log2(*n) + 1;
+        let m = (1u32 << w) - *n;
+        let v: u32 = r.read_var(w - 1)?;
+        if v < m {
+            return Ok(Self { value: v });
+        }
+        let extra_bit: u32 = r.read::<1, u32>()?;
+        Ok(Self { value: (v << 1) - m + extra_bit })
+    }
+}
+
+// 5.9.28 decode_subexp( numSyms )
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct subexp {
+    value: u32,
+}
+
+impl subexp {
+    pub fn new(value: u32) -> Self {
+        Self { value }
+    }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+impl<'a> FromBitStreamWith<'a> for subexp {
+    type Context = u32; // numSyms
+    type Error = Error;
+
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R, num_syms: &Self::Context) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        let mut i = 0u32;
+        let mut mk = 0u32;
+        let k = 3u32;
+        loop {
+            let b2 = if i != 0 { k + i - 1 } else { k };
+            let a = 1u32 << b2;
+            if *num_syms <= mk + 3 * a {
+                let subexp_final_bits = ns::from_reader(r, &(*num_syms - mk))?.value();
+                return Ok(Self { value: subexp_final_bits + mk });
+            }
+            // subexp_more_bits
+            if r.read::<1, u8>()? == 1 {
+                i += 1;
+                mk += a;
+            } else {
+                let subexp_bits: u32 = r.read_var(b2)?;
+                return Ok(Self { value: subexp_bits + mk });
+            }
+        }
+    }
+}
+
+// 5.9.26 decode_signed_subexp_with_ref( low, high, r )
+pub(crate) fn decode_signed_subexp_with_ref<R: bitstream_io::BitRead + ?Sized>(r: &mut R, low: i32, high: i32, reference: i32) -> Result<i32, Error> {
+    if !(low..high).contains(&reference) {
+        return Err(Error::ConformanceViolation {
+            requirement: "subexponential references lie between low and high",
+        });
+    }
+    let x = decode_unsigned_subexp_with_ref(r, (high - low) as u32, (reference - low) as u32)?;
+    Ok(x as i32 + low)
+}
+
+// 5.9.27 decode_unsigned_subexp_with_ref( mx, r )
+pub(crate) fn decode_unsigned_subexp_with_ref<R: bitstream_io::BitRead + ?Sized>(r: &mut R, mx: u32, reference: u32) -> Result<u32, Error> {
+    let v = subexp::from_reader(r, &mx)?.value();
+    if (reference << 1) <= mx {
+        Ok(inverse_recenter(reference, v))
+    } else {
+        Ok(mx - 1 - inverse_recenter(mx - 1 - reference, v))
+    }
+}
+
+// 5.9.29 inverse_recenter( r, v )
+pub(crate) fn inverse_recenter(r: u32, v: u32) -> u32 {
+    if v > 2 * r {
+        v
+    } else if v & 1 == 1 {
+        r - ((v + 1) >> 1)
+    } else {
+        r + (v >> 1)
+    }
+}
+
+// 4.7 FloorLog2(x)
+pub fn floor_log2(x: u32) -> u32 {
+    31 - x.leading_zeros()
+}
+
+// 5.3.4 Trailing bits syntax, payload_bits is where parsing of the OBU payload stopped
+pub(crate) fn trailing_bits(payload: &[u8], payload_bits: u64) -> Result<(), Error> {
+    if payload.is_empty() || trailing_one_bit(payload) == Some(payload_bits) {
+        return Ok(());
+    }
+    Err(Error::PayloadSizeMismatch {
+        obu_size: payload.len() as u64,
+        payload_bits,
+    })
+}
+
+// Position of trailing_one_bit, the last bit set in the payload
+pub(crate) fn trailing_one_bit(payload: &[u8]) -> Option<u64> {
+    let last = payload.iter().rposition(|&byte| byte != 0)?;
+    Some(last as u64 * 8 + 7 - payload[last].trailing_zeros() as u64)
+}
+
+// 5.3.5 Byte alignment syntax
+pub(crate) fn byte_alignment<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<(), Error> {
+    while !r.byte_aligned() {
+        // zero_bit
+        if r.read_bit()? {
+            return Err(Error::ConformanceViolation {
+                requirement: "byte_alignment( ) bits are zero",
+            });
+        }
     }
+    Ok(())
 }
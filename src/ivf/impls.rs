@@ -0,0 +1,181 @@
+use std::io::Read;
+
+use bitstream_io::{ByteRead, ByteReader, FromByteStream, LittleEndian};
+
+use crate::{Error, ObuReader};
+
+use super::{IvfReader, IVF_Frame, IVF_Frame_Header, IVF_Header, IVF_FOURCC_AV1, IVF_FRAME_HEADER_SIZE, IVF_MAX_FRAME_SIZE, IVF_SIGNATURE};
+
+impl FromByteStream for IVF_Header {
+    type Error = Error;
+
+    fn from_reader<R: ByteRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: Sized {
+        let signature = r.read::<[u8; 4]>()?;
+        if signature != IVF_SIGNATURE {
+            return Err(Error::InvalidSyntax { element: "IVF signature", value: u32::from_le_bytes(signature) as u64 });
+        }
+
+        let version = r.read::<u16>()?;
+        let header_size = r.read::<u16>()?;
+        let fourcc = r.read::<[u8; 4]>()?;
+        let width = r.read::<u16>()?;
+        let height = r.read::<u16>()?;
+        let timebase_denominator = r.read::<u32>()?;
+        let timebase_numerator = r.read::<u32>()?;
+        let frame_count = r.read::<u32>()?;
+        r.skip(4)?; // unused
+
+        if header_size < 32 {
+            return Err(Error::InvalidSyntax { element: "IVF header size", value: header_size as u64 });
+        }
+        // Skip any header extension
+        r.skip(header_size as u32 - 32)?;
+
+        if fourcc != IVF_FOURCC_AV1 {
+            return Err(Error::Unsupported { feature: "IVF fourcc other than AV01" });
+        }
+
+        Ok(Self {
+            version,
+            header_size,
+            fourcc,
+            width,
+            height,
+            timebase_denominator,
+            timebase_numerator,
+            frame_count,
+        })
+    }
+}
+
+impl IVF_Header {
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    pub fn fourcc(&self) -> [u8; 4] {
+        self.fourcc
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    // Seconds per pts tick as (numerator, denominator)
+    pub fn timebase(&self) -> (u32, u32) {
+        (self.timebase_numerator, self.timebase_denominator)
+    }
+
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+}
+
+impl FromByteStream for IVF_Frame_Header {
+    type Error = Error;
+
+    fn from_reader<R: ByteRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: Sized {
+        Ok(Self {
+            frame_size: r.read::<u32>()?,
+            pts: r.read::<u64>()?,
+        })
+    }
+}
+
+impl IVF_Frame {
+    pub fn pts(&self) -> u64 {
+        self.pts
+    }
+
+    pub fn timebase(&self) -> (u32, u32) {
+        (self.timebase_numerator, self.timebase_denominator)
+    }
+
+    // Presentation time in seconds
+    pub fn timestamp(&self) -> f64 {
+        if self.timebase_denominator == 0 {
+            return 0f64;
+        }
+        self.pts as f64 * self.timebase_numerator as f64 / self.timebase_denominator as f64
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    // The temporal unit stored in this frame
+    pub fn obus(&self) -> ObuReader<'_> {
+        ObuReader::new(&self.data)
+    }
+}
+
+impl<R: std::io::Read> IvfReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, Error> {
+        let header = ByteReader::endian(&mut reader, LittleEndian).parse::<IVF_Header>()?;
+        Ok(Self { reader, header })
+    }
+
+    pub fn header(&self) -> &IVF_Header {
+        &self.header
+    }
+
+    // Returns None at a clean end of file, a frame header cut short is an error
+    pub fn read_frame(&mut self) -> Result<Option<IVF_Frame>, Error> {
+        let mut frame_header = [0u8; IVF_FRAME_HEADER_SIZE];
+        let read = read_up_to(&mut self.reader, &mut frame_header)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if read < IVF_FRAME_HEADER_SIZE {
+            return Err(Error::UnexpectedEof);
+        }
+        let frame_header = ByteReader::endian(&frame_header[..], LittleEndian).parse::<IVF_Frame_Header>()?;
+        if frame_header.frame_size > IVF_MAX_FRAME_SIZE {
+            return Err(Error::InvalidSyntax { element: "IVF frame size", value: frame_header.frame_size as u64 });
+        }
+
+        // Grows with the data actually read rather than allocating what the file claims
+        let mut data = Vec::new();
+        (&mut self.reader).take(frame_header.frame_size as u64).read_to_end(&mut data)?;
+        if data.len() < frame_header.frame_size as usize {
+            return Err(Error::UnexpectedEof);
+        }
+
+        Ok(Some(IVF_Frame {
+            pts: frame_header.pts,
+            timebase_numerator: self.header.timebase_numerator,
+            timebase_denominator: self.header.timebase_denominator,
+            data,
+        }))
+    }
+}
+
+// Fills buf until the reader runs out, returns how many bytes were read
+fn read_up_to<R: std::io::Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize, Error> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(read)
+}
+
+impl<R: std::io::Read> Iterator for IvfReader<R> {
+    type Item = Result<IVF_Frame, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_frame().transpose()
+    }
+}
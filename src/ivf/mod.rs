@@ -0,0 +1,43 @@
+mod impls;
+
+pub const IVF_SIGNATURE: [u8; 4] = *b"DKIF";
+pub const IVF_FOURCC_AV1: [u8; 4] = *b"AV01";
+
+const IVF_FRAME_HEADER_SIZE: usize = 12;
+// Larger frame sizes are taken as a corrupt file rather than allocated
+const IVF_MAX_FRAME_SIZE: u32 = 256 << 20;
+
+// 32 byte IVF file header, all fields little endian
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IVF_Header {
+    version: u16,                   // 16 bits
+    header_size: u16,               // 16 bits
+    fourcc: [u8; 4],                // 32 bits
+    width: u16,                     // 16 bits
+    height: u16,                    // 16 bits
+    timebase_denominator: u32,      // 32 bits
+    timebase_numerator: u32,        // 32 bits
+    frame_count: u32,               // 32 bits
+}
+
+// 12 byte IVF frame header
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IVF_Frame_Header {
+    frame_size: u32,                // 32 bits
+    pts: u64,                       // 64 bits
+}
+
+// One IVF frame, holds exactly one temporal unit for AV1
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IVF_Frame {
+    pts: u64,
+    timebase_numerator: u32,
+    timebase_denominator: u32,
+    data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct IvfReader<R: std::io::Read> {
+    reader: R,
+    header: IVF_Header,
+}
@@ -0,0 +1,215 @@
+use crate::{obu::OBU_Sequence_Header, temporal_unit::TemporalUnit};
+
+use super::{LevelChecker, Level_Violation, Level, LEVELS};
+
+impl Level {
+    // None for reserved values and for 31, which has no level constraints
+    pub fn from_seq_level_idx(seq_level_idx: u8) -> Option<Self> {
+        LEVELS.iter().find(|level| level.seq_level_idx == seq_level_idx).copied()
+    }
+
+    pub fn seq_level_idx(&self) -> u8 {
+        self.seq_level_idx
+    }
+
+    // Level X.Y, X = 2 + (seq_level_idx >> 2), Y = seq_level_idx & 3
+    pub fn major(&self) -> u8 {
+        2 + (self.seq_level_idx >> 2)
+    }
+
+    pub fn minor(&self) -> u8 {
+        self.seq_level_idx & 3
+    }
+
+    pub fn max_pic_size(&self) -> u64 {
+        self.max_pic_size
+    }
+
+    pub fn max_h_size(&self) -> u32 {
+        self.max_h_size
+    }
+
+    pub fn max_v_size(&self) -> u32 {
+        self.max_v_size
+    }
+
+    pub fn max_display_rate(&self) -> u64 {
+        self.max_display_rate
+    }
+
+    pub fn max_decode_rate(&self) -> u64 {
+        self.max_decode_rate
+    }
+
+    pub fn max_header_rate(&self) -> u32 {
+        self.max_header_rate
+    }
+
+    pub fn main_mbps(&self) -> f64 {
+        self.main_mbps
+    }
+
+    pub fn high_mbps(&self) -> Option<f64> {
+        self.high_mbps
+    }
+
+    // MinCompBasis
+    pub fn min_comp_basis(&self, seq_tier: u8) -> Option<u8> {
+        if seq_tier == 0 { Some(self.main_cr) } else { self.high_cr }
+    }
+
+    pub fn max_tiles(&self) -> u32 {
+        self.max_tiles
+    }
+
+    pub fn max_tile_cols(&self) -> u32 {
+        self.max_tile_cols
+    }
+
+    // MaxBitrate in bits per second, Mbps of the tier times BitrateProfileFactor
+    pub fn max_bitrate(&self, seq_tier: u8, seq_profile: u8) -> Option<u64> {
+        let mbps = if seq_tier == 0 { self.main_mbps } else { self.high_mbps? };
+        let bitrate_profile_factor = match seq_profile {
+            0 => 1.0,
+            1 => 2.0,
+            _ => 3.0,
+        };
+        Some((mbps * bitrate_profile_factor * 1_000_000.0) as u64)
+    }
+}
+
+impl Level_Violation {
+    // Name of the Annex A limit, for MinCompBasis the values are frame sizes in bytes
+    pub fn limit(&self) -> &'static str {
+        self.limit
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn maximum(&self) -> u64 {
+        self.maximum
+    }
+}
+
+impl LevelChecker {
+    // Uses seq_level_idx and seq_tier of the chosen operating point
+    pub fn new(sequence_header: &OBU_Sequence_Header) -> Self {
+        let operating_point = sequence_header.operating_point();
+        let mut checker = Self {
+            level: Level::from_seq_level_idx(sequence_header.seq_level_idx()[operating_point]),
+            seq_tier: sequence_header.seq_tier()[operating_point],
+            seq_profile: sequence_header.seq_profile(),
+            still_picture: sequence_header.still_picture(),
+            frame_rate: sequence_header.timing_info().and_then(|timing_info| timing_info.frame_rate()),
+            temporal_units: 0,
+            bytes: 0,
+            violations: Vec::new(),
+        };
+
+        checker.check_frame_size(sequence_header.max_frame_width(), sequence_header.max_frame_height());
+        checker
+    }
+
+    pub fn level(&self) -> Option<&Level> {
+        self.level.as_ref()
+    }
+
+    // Frame sizes, tile counts and, with a constant frame rate in Timing_Info, the sample and header rates
+    pub fn check_temporal_unit(&mut self, temporal_unit: &TemporalUnit) {
+        self.temporal_units += 1;
+        self.bytes += temporal_unit.obus().iter().map(|obu| obu.total_size() as u64).sum::<u64>();
+
+        let Some(level) = self.level else {
+            return;
+        };
+
+        let mut display_samples = 0u64;
+        let mut decode_samples = 0u64;
+        for frame in temporal_unit.frames() {
+            let frame_header = frame.frame_header();
+            let frame_size = frame_header.frame_size();
+            let samples = frame_size.upscaled_width() as u64 * frame_size.frame_height() as u64;
+            if frame.is_shown() {
+                display_samples += samples;
+            }
+            if frame_header.show_existing_frame() == 1 {
+                continue;
+            }
+
+            decode_samples += samples;
+            self.check_frame_size(frame_size.upscaled_width(), frame_size.frame_height());
+            self.check("MaxTiles", frame_header.tile_info().num_tiles() as u64, level.max_tiles as u64);
+            self.check("MaxTileCols", frame_header.tile_info().tile_cols() as u64, level.max_tile_cols as u64);
+        }
+
+        if let Some(frame_rate) = self.frame_rate {
+            let frame_headers = temporal_unit.frames().len() as u64;
+            self.check("MaxDisplayRate", (display_samples as f64 * frame_rate) as u64, level.max_display_rate);
+            self.check("MaxDecodeRate", (decode_samples as f64 * frame_rate) as u64, level.max_decode_rate);
+            self.check("MaxHeaderRate", (frame_headers as f64 * frame_rate) as u64, level.max_header_rate as u64);
+        }
+        self.check_compressed_ratio(temporal_unit, level, self.frame_rate.map(|frame_rate| decode_samples as f64 * frame_rate));
+    }
+
+    // Limits exceeded so far, the bitrate is averaged over all checked temporal units
+    pub fn violations(&self) -> Vec<Level_Violation> {
+        let mut checker = self.clone();
+        let max_bitrate = self.level.and_then(|level| level.max_bitrate(self.seq_tier, self.seq_profile));
+        if let (Some(max_bitrate), Some(frame_rate), 1..) = (max_bitrate, self.frame_rate, self.temporal_units) {
+            let bitrate = self.bytes as f64 * 8.0 * frame_rate / self.temporal_units as f64;
+            checker.check("MaxBitrate", bitrate as u64, max_bitrate);
+        }
+        checker.violations
+    }
+
+    // UpscaledWidth and FrameHeight
+    fn check_frame_size(&mut self, width: u32, height: u32) {
+        let Some(level) = self.level else {
+            return;
+        };
+        self.check("MaxPicSize", width as u64 * height as u64, level.max_pic_size);
+        self.check("MaxHSize", width as u64, level.max_h_size as u64);
+        self.check("MaxVSize", height as u64, level.max_v_size as u64);
+    }
+
+    // CompressedRatio of each coded frame is at least MinPicCompressRatio, reported as the
+    // tile bytes of the frame against the largest size that ratio allows
+    fn check_compressed_ratio(&mut self, temporal_unit: &TemporalUnit, level: Level, decoded_sample_rate: Option<f64>) {
+        let Some(min_comp_basis) = level.min_comp_basis(self.seq_tier) else {
+            return;
+        };
+        // SpeedAdj is DecodedSampleRate / MaxDisplayRate, still pictures only need 0.8
+        let min_pic_compress_ratio = match (self.still_picture, decoded_sample_rate) {
+            (1, _) => 0.8,
+            (_, Some(decoded_sample_rate)) => (min_comp_basis as f64 * decoded_sample_rate / level.max_display_rate as f64).max(0.8),
+            (_, None) => return,
+        };
+        let pic_size_profile_factor = match self.seq_profile {
+            0 => 15,
+            1 => 30,
+            _ => 36,
+        };
+
+        for frame in temporal_unit.frames().iter().filter(|frame| frame.frame_header().show_existing_frame() == 0) {
+            let frame_size = frame.frame_header().frame_size();
+            let uncompressed_size = (frame_size.upscaled_width() as u64 * frame_size.frame_height() as u64 * pic_size_profile_factor) >> 3;
+            let compressed_size = frame.tile_groups().iter().map(|tile_group| tile_group.tile_data_size() as u64).sum::<u64>();
+            // CompressedSize leaves out 128 bytes of headers
+            let max_compressed_size = (uncompressed_size as f64 / min_pic_compress_ratio) as u64 + 128;
+            self.check("MinCompBasis", compressed_size, max_compressed_size);
+        }
+    }
+
+    // Keeps one violation per limit with the largest value
+    fn check(&mut self, limit: &'static str, value: u64, maximum: u64) {
+        if value <= maximum {
+            return;
+        }
+        match self.violations.iter_mut().find(|violation| violation.limit == limit) {
+            Some(violation) => violation.value = violation.value.max(value),
+            None => self.violations.push(Level_Violation { limit, value, maximum }),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+mod impls;
+
+// A.3 Levels, limits for one seq_level_idx
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Level {
+    seq_level_idx: u8,
+    max_pic_size: u64,      // MaxPicSize, luma samples
+    max_h_size: u32,        // MaxHSize
+    max_v_size: u32,        // MaxVSize
+    max_display_rate: u64,  // MaxDisplayRate, luma samples per second
+    max_decode_rate: u64,   // MaxDecodeRate, luma samples per second
+    max_header_rate: u32,   // MaxHeaderRate, frame headers per second
+    main_mbps: f64,         // MainMbps
+    high_mbps: Option<f64>, // HighMbps, levels below 4.0 have no high tier
+    main_cr: u8,            // MinCompBasis of the main tier
+    high_cr: Option<u8>,    // MinCompBasis of the high tier
+    max_tiles: u32,         // MaxTiles
+    max_tile_cols: u32,     // MaxTileCols
+}
+
+// A limit of the declared level the stream goes beyond, value is the largest one seen
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Level_Violation {
+    limit: &'static str,
+    value: u64,
+    maximum: u64,
+}
+
+// A.3 Checks a stream against the level and tier of the chosen operating point
+#[derive(Debug, PartialEq, Clone)]
+pub struct LevelChecker {
+    level: Option<Level>,          // None for seq_level_idx 31 and reserved levels
+    seq_tier: u8,
+    seq_profile: u8,
+    still_picture: u8,
+    frame_rate: Option<f64>,       // temporal units per second from Timing_Info
+    temporal_units: u64,
+    bytes: u64,                    // size of all OBUs of the checked temporal units
+    violations: Vec<Level_Violation>,
+}
+
+#[rustfmt::skip]
+const LEVELS: [Level; 14] = [
+    level(0,   147_456,  2048, 1152,    4_423_680,    5_529_600, 150,   1.5, None,        2, None,      8,  4),
+    level(1,   278_784,  2816, 1584,    8_363_520,   10_454_400, 150,   3.0, None,        2, None,      8,  4),
+    level(4,   665_856,  4352, 2448,   19_975_680,   24_969_600, 150,   6.0, None,        2, None,     16,  6),
+    level(5, 1_065_024,  5504, 3096,   31_950_720,   39_938_400, 150,  10.0, None,        2, None,     16,  6),
+    level(8, 2_359_296,  6144, 3456,   70_778_880,   77_856_768, 300,  12.0, Some(30.0),  4, Some(4), 32,  8),
+    level(9, 2_359_296,  6144, 3456,  141_557_760,  155_713_536, 300,  20.0, Some(50.0),  4, Some(4), 32,  8),
+    level(12, 8_912_896, 8192, 4352,  267_386_880,  273_715_200, 300,  30.0, Some(100.0), 6, Some(4), 64,  8),
+    level(13, 8_912_896, 8192, 4352,  534_773_760,  547_430_400, 300,  40.0, Some(160.0), 8, Some(4), 64,  8),
+    level(14, 8_912_896, 8192, 4352, 1_069_547_520, 1_094_860_800, 300, 60.0, Some(240.0), 8, Some(4), 64,  8),
+    level(15, 8_912_896, 8192, 4352, 1_069_547_520, 1_176_502_272, 300, 60.0, Some(240.0), 8, Some(4), 64,  8),
+    level(16, 35_651_584, 16384, 8704, 1_069_547_520, 1_176_502_272, 300, 60.0, Some(240.0), 8, Some(4), 128, 16),
+    level(17, 35_651_584, 16384, 8704, 2_139_095_040, 2_189_721_600, 300, 100.0, Some(480.0), 8, Some(4), 128, 16),
+    level(18, 35_651_584, 16384, 8704, 4_278_190_080, 4_379_443_200, 300, 160.0, Some(800.0), 8, Some(4), 128, 16),
+    level(19, 35_651_584, 16384, 8704, 4_278_190_080, 4_706_009_088, 300, 160.0, Some(800.0), 8, Some(4), 128, 16),
+];
+
+#[allow(clippy::too_many_arguments)]
+const fn level(
+    seq_level_idx: u8,
+    max_pic_size: u64,
+    max_h_size: u32,
+    max_v_size: u32,
+    max_display_rate: u64,
+    max_decode_rate: u64,
+    max_header_rate: u32,
+    main_mbps: f64,
+    high_mbps: Option<f64>,
+    main_cr: u8,
+    high_cr: Option<u8>,
+    max_tiles: u32,
+    max_tile_cols: u32,
+) -> Level {
+    Level {
+        seq_level_idx,
+        max_pic_size,
+        max_h_size,
+        max_v_size,
+        max_display_rate,
+        max_decode_rate,
+        max_header_rate,
+        main_mbps,
+        high_mbps,
+        main_cr,
+        high_cr,
+        max_tiles,
+        max_tile_cols,
+    }
+}
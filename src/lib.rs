@@ -1,5 +1,35 @@
+// Type and field names follow the AV1 specification on purpose.
+#![allow(non_camel_case_types)]
+#![allow(clippy::upper_case_acronyms)]
+
+mod error;
 mod obu;
 mod generics;
 mod consts;
+mod annexb;
+mod ivf;
+mod decoder;
+mod frame_header;
+mod tile_group;
+mod metadata;
+mod temporal_unit;
+mod av1c;
+mod level;
+mod decoder_model;
 
-pub use generics::leb_128;
\ No newline at end of file
+pub use error::Error;
+pub use generics::{leb_128, ns, su, subexp, uvlc};
+pub use consts::{CHROMA_SAMPLE_POSITION, COLOR_PRIMARIES, FRAME_RESTORATION_TYPE, FRAME_TYPE, GM_TYPE, INTERPOLATION_FILTER, MATRIX_COEFFICIENTS, OBU_TYPE, TRANSFER_CHARACTERISTICS, TX_MODE};
+pub use consts::{ALTREF2_FRAME, ALTREF_FRAME, BWDREF_FRAME, GOLDEN_FRAME, INTRA_FRAME, LAST2_FRAME, LAST3_FRAME, LAST_FRAME, TOTAL_REFS_PER_FRAME};
+pub use consts::{MAX_SEGMENTS, SEG_LVL_ALT_LF_Y_V, SEG_LVL_ALT_Q, SEG_LVL_GLOBALMV, SEG_LVL_MAX, SEG_LVL_REF_FRAME, SEG_LVL_SKIP};
+pub use obu::{ChromaSubsampling, Color_Config, Decoder_Model_Info, ObuReader, OperatingPoint, OperatingPointSelector, Operating_Parameters_Info, Timing_Info, OBU, OBU_Extension_Header, OBU_Header, OBU_Sequence_Header};
+pub use annexb::{detect_format, AnnexBReader, AnnexB_Frame_Unit, AnnexB_Frame_Units, AnnexB_OBUs, AnnexB_Temporal_Unit, BitstreamFormat};
+pub use ivf::{IvfReader, IVF_Frame, IVF_Header, IVF_FOURCC_AV1, IVF_SIGNATURE};
+pub use decoder::{DecodeStatus, DecodedFrame, DecodedObu, Decoder};
+pub use frame_header::{Cdef_Params, Delta_Lf_Params, Delta_Q_Params, Film_Grain_Params, FrameHeader, Frame_Size, Global_Motion_Params, Loop_Filter_Params, Lr_Params, Quantization_Params, RefFrameStore, Ref_Frame, Segmentation_Params, TileLayout, TileRect, Tile_Info};
+pub use tile_group::{Tile, TileGroup};
+pub use metadata::{Metadata_HDR_CLL, Metadata_HDR_MDCV, Metadata_ITUT_T35, Metadata_Scalability, Metadata_Timecode, Scalability_Structure, Temporal_Group_Entry, OBU_Metadata};
+pub use temporal_unit::{CodedFrame, TemporalUnit, TemporalUnits};
+pub use av1c::AV1CodecConfigurationRecord;
+pub use level::{Level, LevelChecker, Level_Violation};
+pub use decoder_model::{DecoderModel, Decoder_Model_Violation};
@@ -0,0 +1,201 @@
+use crate::consts::{METADATA_TYPE_HDR_CLL, METADATA_TYPE_HDR_MDCV, METADATA_TYPE_ITUT_T35, METADATA_TYPE_SCALABILITY, METADATA_TYPE_TIMECODE};
+
+use super::{Metadata_HDR_CLL, Metadata_HDR_MDCV, Metadata_ITUT_T35, Metadata_Scalability, Metadata_Timecode, Scalability_Structure, Temporal_Group_Entry, OBU_Metadata};
+
+impl OBU_Metadata {
+    pub fn metadata_type(&self) -> u64 {
+        match self {
+            Self::HDR_CLL(_) => METADATA_TYPE_HDR_CLL,
+            Self::HDR_MDCV(_) => METADATA_TYPE_HDR_MDCV,
+            Self::SCALABILITY(_) => METADATA_TYPE_SCALABILITY,
+            Self::ITUT_T35(_) => METADATA_TYPE_ITUT_T35,
+            Self::TIMECODE(_) => METADATA_TYPE_TIMECODE,
+            Self::OTHER { metadata_type, .. } => *metadata_type,
+        }
+    }
+}
+
+impl Metadata_HDR_CLL {
+    pub fn max_cll(&self) -> u16 {
+        self.max_cll
+    }
+
+    pub fn max_fall(&self) -> u16 {
+        self.max_fall
+    }
+}
+
+impl Metadata_HDR_MDCV {
+    pub fn primary_chromaticity_x(&self) -> [u16; 3] {
+        self.primary_chromaticity_x
+    }
+
+    pub fn primary_chromaticity_y(&self) -> [u16; 3] {
+        self.primary_chromaticity_y
+    }
+
+    pub fn white_point_chromaticity_x(&self) -> u16 {
+        self.white_point_chromaticity_x
+    }
+
+    pub fn white_point_chromaticity_y(&self) -> u16 {
+        self.white_point_chromaticity_y
+    }
+
+    pub fn luminance_max(&self) -> u32 {
+        self.luminance_max
+    }
+
+    pub fn luminance_min(&self) -> u32 {
+        self.luminance_min
+    }
+
+    // CIE 1931 xy of the red, green and blue primaries
+    pub fn primaries(&self) -> [(f64, f64); 3] {
+        [0, 1, 2].map(|i| (self.primary_chromaticity_x[i] as f64 / 65536.0, self.primary_chromaticity_y[i] as f64 / 65536.0))
+    }
+
+    pub fn white_point(&self) -> (f64, f64) {
+        (self.white_point_chromaticity_x as f64 / 65536.0, self.white_point_chromaticity_y as f64 / 65536.0)
+    }
+
+    // cd/m2
+    pub fn max_luminance(&self) -> f64 {
+        self.luminance_max as f64 / 256.0
+    }
+
+    // cd/m2
+    pub fn min_luminance(&self) -> f64 {
+        self.luminance_min as f64 / 16384.0
+    }
+}
+
+impl Metadata_Scalability {
+    pub fn scalability_mode_idc(&self) -> u8 {
+        self.scalability_mode_idc
+    }
+
+    pub fn scalability_structure(&self) -> Option<&Scalability_Structure> {
+        self.scalability_structure.as_ref()
+    }
+}
+
+impl Scalability_Structure {
+    pub fn spatial_layers_cnt_minus_1(&self) -> u8 {
+        self.spatial_layers_cnt_minus_1
+    }
+
+    pub fn spatial_layer_dimensions_present_flag(&self) -> u8 {
+        self.spatial_layer_dimensions_present_flag
+    }
+
+    pub fn spatial_layer_description_present_flag(&self) -> u8 {
+        self.spatial_layer_description_present_flag
+    }
+
+    pub fn temporal_group_description_present_flag(&self) -> u8 {
+        self.temporal_group_description_present_flag
+    }
+
+    pub fn scalability_structure_reserved_3bits(&self) -> u8 {
+        self.scalability_structure_reserved_3bits
+    }
+
+    pub fn spatial_layer_max_width(&self) -> &[u16] {
+        &self.spatial_layer_max_width
+    }
+
+    pub fn spatial_layer_max_height(&self) -> &[u16] {
+        &self.spatial_layer_max_height
+    }
+
+    pub fn spatial_layer_ref_id(&self) -> &[u8] {
+        &self.spatial_layer_ref_id
+    }
+
+    pub fn temporal_group_size(&self) -> usize {
+        self.temporal_group.len()
+    }
+
+    pub fn temporal_group(&self) -> &[Temporal_Group_Entry] {
+        &self.temporal_group
+    }
+}
+
+impl Temporal_Group_Entry {
+    pub fn temporal_group_temporal_id(&self) -> u8 {
+        self.temporal_group_temporal_id
+    }
+
+    pub fn temporal_group_temporal_switching_up_point_flag(&self) -> u8 {
+        self.temporal_group_temporal_switching_up_point_flag
+    }
+
+    pub fn temporal_group_spatial_switching_up_point_flag(&self) -> u8 {
+        self.temporal_group_spatial_switching_up_point_flag
+    }
+
+    pub fn temporal_group_ref_cnt(&self) -> usize {
+        self.temporal_group_ref_pic_diff.len()
+    }
+
+    pub fn temporal_group_ref_pic_diff(&self) -> &[u8] {
+        &self.temporal_group_ref_pic_diff
+    }
+}
+
+impl Metadata_ITUT_T35 {
+    pub fn itu_t_t35_country_code(&self) -> u8 {
+        self.itu_t_t35_country_code
+    }
+
+    pub fn itu_t_t35_country_code_extension_byte(&self) -> Option<u8> {
+        self.itu_t_t35_country_code_extension_byte
+    }
+
+    pub fn itu_t_t35_payload_bytes(&self) -> &[u8] {
+        &self.itu_t_t35_payload_bytes
+    }
+}
+
+impl Metadata_Timecode {
+    pub fn counting_type(&self) -> u8 {
+        self.counting_type
+    }
+
+    pub fn full_timestamp_flag(&self) -> u8 {
+        self.full_timestamp_flag
+    }
+
+    pub fn discontinuity_flag(&self) -> u8 {
+        self.discontinuity_flag
+    }
+
+    pub fn cnt_dropped_flag(&self) -> u8 {
+        self.cnt_dropped_flag
+    }
+
+    pub fn n_frames(&self) -> u16 {
+        self.n_frames
+    }
+
+    pub fn seconds_value(&self) -> Option<u8> {
+        self.seconds_value
+    }
+
+    pub fn minutes_value(&self) -> Option<u8> {
+        self.minutes_value
+    }
+
+    pub fn hours_value(&self) -> Option<u8> {
+        self.hours_value
+    }
+
+    pub fn time_offset_length(&self) -> u8 {
+        self.time_offset_length
+    }
+
+    pub fn time_offset_value(&self) -> u32 {
+        self.time_offset_value
+    }
+}
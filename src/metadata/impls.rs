@@ -0,0 +1,207 @@
+use std::io::Cursor;
+
+use bitstream_io::{BigEndian, BitRead, BitReader, FromBitStream};
+
+use crate::{
+    consts::{METADATA_TYPE_HDR_CLL, METADATA_TYPE_HDR_MDCV, METADATA_TYPE_ITUT_T35, METADATA_TYPE_SCALABILITY, METADATA_TYPE_TIMECODE, SCALABILITY_SS},
+    generics::{leb_128, trailing_bits, trailing_one_bit},
+    Error,
+};
+
+use super::{Metadata_HDR_CLL, Metadata_HDR_MDCV, Metadata_ITUT_T35, Metadata_Scalability, Metadata_Timecode, Scalability_Structure, Temporal_Group_Entry, OBU_Metadata};
+
+impl OBU_Metadata {
+
+    // 5.8.1 General metadata OBU syntax, payload includes the trailing bits
+    pub fn metadata_obu(payload: &[u8]) -> Result<Self, Error> {
+        let mut r = BitReader::endian(Cursor::new(payload), BigEndian);
+        let metadata_type = leb_128::from_reader(&mut r)?.value();
+
+        let metadata = match metadata_type {
+            METADATA_TYPE_HDR_CLL => Self::HDR_CLL(Metadata_HDR_CLL::from_reader(&mut r)?),
+            METADATA_TYPE_HDR_MDCV => Self::HDR_MDCV(Metadata_HDR_MDCV::from_reader(&mut r)?),
+            METADATA_TYPE_SCALABILITY => Self::SCALABILITY(Metadata_Scalability::from_reader(&mut r)?),
+            METADATA_TYPE_ITUT_T35 => {
+                let itu_t_t35_country_code = r.read::<8, u8>()?;
+                let itu_t_t35_country_code_extension_byte = if itu_t_t35_country_code == 0xFF { Some(r.read::<8, u8>()?) } else { None };
+                return Ok(Self::ITUT_T35(Metadata_ITUT_T35 {
+                    itu_t_t35_country_code,
+                    itu_t_t35_country_code_extension_byte,
+                    itu_t_t35_payload_bytes: payload_bytes(payload, r.position_in_bits()?)?.to_vec(),
+                }));
+            }
+            METADATA_TYPE_TIMECODE => Self::TIMECODE(Metadata_Timecode::from_reader(&mut r)?),
+            _ => {
+                return Ok(Self::OTHER {
+                    metadata_type,
+                    payload: payload_bytes(payload, r.position_in_bits()?)?.to_vec(),
+                });
+            }
+        };
+
+        trailing_bits(payload, r.position_in_bits()?)?;
+        Ok(metadata)
+    }
+}
+
+// Bytes from position up to trailing_bits( ), which has to start on a byte boundary here
+fn payload_bytes(payload: &[u8], position: u64) -> Result<&[u8], Error> {
+    match trailing_one_bit(payload) {
+        Some(end) if end % 8 == 0 && end >= position => Ok(&payload[(position / 8) as usize..(end / 8) as usize]),
+        _ => Err(Error::PayloadSizeMismatch {
+            obu_size: payload.len() as u64,
+            payload_bits: position,
+        }),
+    }
+}
+
+impl FromBitStream for Metadata_HDR_CLL {
+    type Error = Error;
+
+    // 5.8.3 Metadata high dynamic range content light level syntax
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            max_cll: r.read::<16, u16>()?,
+            max_fall: r.read::<16, u16>()?,
+        })
+    }
+}
+
+impl FromBitStream for Metadata_HDR_MDCV {
+    type Error = Error;
+
+    // 5.8.4 Metadata high dynamic range mastering display color volume syntax
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        let mut mdcv = Metadata_HDR_MDCV::default();
+        for i in 0..3 {
+            mdcv.primary_chromaticity_x[i] = r.read::<16, u16>()?;
+            mdcv.primary_chromaticity_y[i] = r.read::<16, u16>()?;
+        }
+        mdcv.white_point_chromaticity_x = r.read::<16, u16>()?;
+        mdcv.white_point_chromaticity_y = r.read::<16, u16>()?;
+        mdcv.luminance_max = r.read::<32, u32>()?;
+        mdcv.luminance_min = r.read::<32, u32>()?;
+        Ok(mdcv)
+    }
+}
+
+impl FromBitStream for Metadata_Scalability {
+    type Error = Error;
+
+    // 5.8.5 Metadata scalability syntax
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        let scalability_mode_idc = r.read::<8, u8>()?;
+        let scalability_structure = if scalability_mode_idc == SCALABILITY_SS {
+            Some(Scalability_Structure::from_reader(r)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            scalability_mode_idc,
+            scalability_structure,
+        })
+    }
+}
+
+impl FromBitStream for Scalability_Structure {
+    type Error = Error;
+
+    // 5.8.6 Scalability structure syntax
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        let mut structure = Scalability_Structure {
+            spatial_layers_cnt_minus_1: r.read::<2, u8>()?,
+            spatial_layer_dimensions_present_flag: r.read::<1, u8>()?,
+            spatial_layer_description_present_flag: r.read::<1, u8>()?,
+            temporal_group_description_present_flag: r.read::<1, u8>()?,
+            scalability_structure_reserved_3bits: r.read::<3, u8>()?,
+            ..Default::default()
+        };
+
+        if structure.spatial_layer_dimensions_present_flag != 0 {
+            for _ in 0..=structure.spatial_layers_cnt_minus_1 {
+                structure.spatial_layer_max_width.push(r.read::<16, u16>()?);
+                structure.spatial_layer_max_height.push(r.read::<16, u16>()?);
+            }
+        }
+
+        if structure.spatial_layer_description_present_flag != 0 {
+            for _ in 0..=structure.spatial_layers_cnt_minus_1 {
+                structure.spatial_layer_ref_id.push(r.read::<8, u8>()?);
+            }
+        }
+
+        if structure.temporal_group_description_present_flag != 0 {
+            let temporal_group_size = r.read::<8, u8>()?;
+            for _ in 0..temporal_group_size {
+                let mut entry = Temporal_Group_Entry {
+                    temporal_group_temporal_id: r.read::<3, u8>()?,
+                    temporal_group_temporal_switching_up_point_flag: r.read::<1, u8>()?,
+                    temporal_group_spatial_switching_up_point_flag: r.read::<1, u8>()?,
+                    ..Default::default()
+                };
+                let temporal_group_ref_cnt = r.read::<3, u8>()?;
+                for _ in 0..temporal_group_ref_cnt {
+                    entry.temporal_group_ref_pic_diff.push(r.read::<8, u8>()?);
+                }
+                structure.temporal_group.push(entry);
+            }
+        }
+
+        Ok(structure)
+    }
+}
+
+impl FromBitStream for Metadata_Timecode {
+    type Error = Error;
+
+    // 5.8.7 Metadata timecode syntax
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        let mut timecode = Metadata_Timecode {
+            counting_type: r.read::<5, u8>()?,
+            full_timestamp_flag: r.read::<1, u8>()?,
+            discontinuity_flag: r.read::<1, u8>()?,
+            cnt_dropped_flag: r.read::<1, u8>()?,
+            n_frames: r.read::<9, u16>()?,
+            ..Default::default()
+        };
+
+        if timecode.full_timestamp_flag != 0 {
+            timecode.seconds_value = Some(r.read::<6, u8>()?);
+            timecode.minutes_value = Some(r.read::<6, u8>()?);
+            timecode.hours_value = Some(r.read::<5, u8>()?);
+        } else if r.read::<1, u8>()? == 1 {
+            // seconds_flag
+            timecode.seconds_value = Some(r.read::<6, u8>()?);
+            // minutes_flag
+            if r.read::<1, u8>()? == 1 {
+                timecode.minutes_value = Some(r.read::<6, u8>()?);
+                // hours_flag
+                if r.read::<1, u8>()? == 1 {
+                    timecode.hours_value = Some(r.read::<5, u8>()?);
+                }
+            }
+        }
+
+        timecode.time_offset_length = r.read::<5, u8>()?;
+        if timecode.time_offset_length > 0 {
+            timecode.time_offset_value = r.read_var(timecode.time_offset_length as u32)?;
+        }
+
+        Ok(timecode)
+    }
+}
@@ -0,0 +1,97 @@
+mod impls;
+mod accessors;
+
+/*
+metadata_type	Name of metadata_type
+0	Reserved for AOM use
+1	METADATA_TYPE_HDR_CLL
+2	METADATA_TYPE_HDR_MDCV
+3	METADATA_TYPE_SCALABILITY
+4	METADATA_TYPE_ITUT_T35
+5	METADATA_TYPE_TIMECODE
+6-31	Unregistered user private
+32 or greater	Reserved for AOM use
+*/
+// 5.8.1 General metadata OBU syntax
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum OBU_Metadata {
+    HDR_CLL(Metadata_HDR_CLL),
+    HDR_MDCV(Metadata_HDR_MDCV),
+    SCALABILITY(Metadata_Scalability),
+    ITUT_T35(Metadata_ITUT_T35),
+    TIMECODE(Metadata_Timecode),
+    OTHER {
+        metadata_type: u64, // leb128()
+        payload: Vec<u8>,   // without trailing bits
+    },
+}
+
+// 5.8.3 Metadata high dynamic range content light level syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Metadata_HDR_CLL {
+    max_cll: u16,                       // 16 bits
+    max_fall: u16,                      // 16 bits
+}
+
+// 5.8.4 Metadata high dynamic range mastering display color volume syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Metadata_HDR_MDCV {
+    primary_chromaticity_x: [u16; 3],   // 16 bits each, 0.16 fixed point
+    primary_chromaticity_y: [u16; 3],   // 16 bits each, 0.16 fixed point
+    white_point_chromaticity_x: u16,    // 16 bits, 0.16 fixed point
+    white_point_chromaticity_y: u16,    // 16 bits, 0.16 fixed point
+    luminance_max: u32,                 // 32 bits, 24.8 fixed point
+    luminance_min: u32,                 // 32 bits, 18.14 fixed point
+}
+
+// 5.8.5 Metadata scalability syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Metadata_Scalability {
+    scalability_mode_idc: u8,           // 8 bits
+    scalability_structure: Option<Scalability_Structure>, // only for SCALABILITY_SS
+}
+
+// 5.8.6 Scalability structure syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Scalability_Structure {
+    spatial_layers_cnt_minus_1: u8,     // 2 bits
+    spatial_layer_dimensions_present_flag: u8, // 1 bit
+    spatial_layer_description_present_flag: u8, // 1 bit
+    temporal_group_description_present_flag: u8, // 1 bit
+    scalability_structure_reserved_3bits: u8, // 3 bits
+    spatial_layer_max_width: Vec<u16>,  // 16 bits per spatial layer
+    spatial_layer_max_height: Vec<u16>, // 16 bits per spatial layer
+    spatial_layer_ref_id: Vec<u8>,      // 8 bits per spatial layer
+    temporal_group: Vec<Temporal_Group_Entry>, // temporal_group_size entries
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Temporal_Group_Entry {
+    temporal_group_temporal_id: u8,     // 3 bits
+    temporal_group_temporal_switching_up_point_flag: u8, // 1 bit
+    temporal_group_spatial_switching_up_point_flag: u8, // 1 bit
+    temporal_group_ref_pic_diff: Vec<u8>, // temporal_group_ref_cnt entries of 8 bits
+}
+
+// 5.8.2 Metadata ITUT T35 syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Metadata_ITUT_T35 {
+    itu_t_t35_country_code: u8,         // 8 bits
+    itu_t_t35_country_code_extension_byte: Option<u8>, // 8 bits, when the country code is 0xFF
+    itu_t_t35_payload_bytes: Vec<u8>,   // up to the trailing bits
+}
+
+// 5.8.7 Metadata timecode syntax
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Metadata_Timecode {
+    counting_type: u8,                  // 5 bits
+    full_timestamp_flag: u8,            // 1 bit
+    discontinuity_flag: u8,             // 1 bit
+    cnt_dropped_flag: u8,               // 1 bit
+    n_frames: u16,                      // 9 bits
+    seconds_value: Option<u8>,          // 6 bits
+    minutes_value: Option<u8>,          // 6 bits
+    hours_value: Option<u8>,            // 5 bits
+    time_offset_length: u8,             // 5 bits
+    time_offset_value: u32,             // time_offset_length bits
+}
@@ -0,0 +1,345 @@
+use crate::consts::{CHROMA_SAMPLE_POSITION, COLOR_PRIMARIES, MATRIX_COEFFICIENTS, TRANSFER_CHARACTERISTICS};
+
+use super::{ChromaSubsampling, Color_Config, Decoder_Model_Info, OBU_Extension_Header, OBU_Header, OBU_Sequence_Header, Operating_Parameters_Info, Timing_Info};
+
+impl OBU_Header {
+    pub fn obu_forbidden_bit(&self) -> u8 {
+        self.obu_forbidden_bit
+    }
+
+    pub fn obu_type(&self) -> crate::consts::OBU_TYPE {
+        self.obu_type
+    }
+
+    pub fn obu_extension_flag(&self) -> u8 {
+        self.obu_extension_flag
+    }
+
+    pub fn obu_has_size_field(&self) -> u8 {
+        self.obu_has_size_field
+    }
+
+    pub fn obu_extension_header(&self) -> Option<&OBU_Extension_Header> {
+        self.obu_extension_header.as_ref()
+    }
+}
+
+impl OBU_Extension_Header {
+    // 7.1 Drop OBUs that are not part of the chosen operating point
+    pub fn in_operating_point(&self, operating_point_idc: u16) -> bool {
+        let in_temporal_layer = (operating_point_idc >> self.temporal_id) & 1;
+        let in_spatial_layer = (operating_point_idc >> (self.spatial_id + 8)) & 1;
+        in_temporal_layer != 0 && in_spatial_layer != 0
+    }
+
+    pub fn temporal_id(&self) -> u8 {
+        self.temporal_id
+    }
+
+    pub fn spatial_id(&self) -> u8 {
+        self.spatial_id
+    }
+}
+
+impl OBU_Sequence_Header {
+    pub fn seq_profile(&self) -> u8 {
+        self.seq_profile
+    }
+
+    pub fn still_picture(&self) -> u8 {
+        self.still_picture
+    }
+
+    pub fn reduced_still_picture_header(&self) -> u8 {
+        self.reduced_still_picture_header
+    }
+
+    pub fn timing_info(&self) -> Option<&Timing_Info> {
+        self.timing_info.as_ref()
+    }
+
+    pub fn decoder_model_info(&self) -> Option<&Decoder_Model_Info> {
+        self.decoder_model_info.as_ref()
+    }
+
+    // operating_points_cnt_minus_1 + 1
+    pub fn operating_points_cnt(&self) -> usize {
+        self.operating_point_idc.len()
+    }
+
+    pub fn operating_point_idc(&self) -> &[u16] {
+        &self.operating_point_idc
+    }
+
+    pub fn seq_level_idx(&self) -> &[u8] {
+        &self.seq_level_idx
+    }
+
+    pub fn seq_tier(&self) -> &[u8] {
+        &self.seq_tier
+    }
+
+    pub fn decoder_model_present_for_this_op(&self) -> &[u8] {
+        &self.decoder_model_present_for_this_op
+    }
+
+    // One entry per operating point with decoder_model_present_for_this_op set
+    pub fn operating_parameters_info(&self) -> Option<&Operating_Parameters_Info> {
+        self.operating_parameters_info.as_ref()
+    }
+
+    pub fn initial_display_delay_present_flag(&self) -> u8 {
+        self.initial_display_delay_present_flag
+    }
+
+    pub fn initial_display_delay_present_for_this_op(&self) -> &[u8] {
+        &self.initial_display_delay_present_for_this_op
+    }
+
+    // One entry per operating point with initial_display_delay_present_for_this_op set
+    pub fn initial_display_delay_minus_1(&self) -> Option<&[u8]> {
+        self.initial_display_delay_minus_1.as_deref()
+    }
+
+    pub fn operating_point(&self) -> usize {
+        self.operating_point
+    }
+
+    // OperatingPointIdc of the chosen operating point
+    pub fn c_operating_point_idc(&self) -> u16 {
+        self.c_operating_point_idc
+    }
+
+    pub fn frame_width_bits_minus_1(&self) -> u8 {
+        self.frame_width_bits_minus_1
+    }
+
+    pub fn frame_height_bits_minus_1(&self) -> u8 {
+        self.frame_height_bits_minus_1
+    }
+
+    pub fn max_frame_width_minus_one(&self) -> u16 {
+        self.max_frame_width_minus_one
+    }
+
+    pub fn max_frame_height_minus_one(&self) -> u16 {
+        self.max_frame_height_minus_one
+    }
+
+    pub fn max_frame_width(&self) -> u32 {
+        self.max_frame_width_minus_one as u32 + 1
+    }
+
+    pub fn max_frame_height(&self) -> u32 {
+        self.max_frame_height_minus_one as u32 + 1
+    }
+
+    pub fn frame_id_numbers_present_flag(&self) -> u8 {
+        self.delta_frame_id_length_minus_2.is_some() as u8
+    }
+
+    pub fn delta_frame_id_length_minus_2(&self) -> Option<u8> {
+        self.delta_frame_id_length_minus_2
+    }
+
+    pub fn additional_frame_id_length_minus_1(&self) -> Option<u8> {
+        self.additional_frame_id_length_minus_1
+    }
+
+    pub fn use_128x128_superblock(&self) -> u8 {
+        self.use_128x128_superblock
+    }
+
+    pub fn enable_filter_intra(&self) -> u8 {
+        self.enable_filter_intra
+    }
+
+    pub fn enable_intra_edge_filter(&self) -> u8 {
+        self.enable_intra_edge_filter
+    }
+
+    pub fn enable_interintra_compound(&self) -> u8 {
+        self.enable_interintra_compound
+    }
+
+    pub fn enable_masked_compound(&self) -> u8 {
+        self.enable_masked_compound
+    }
+
+    pub fn enable_warped_motion(&self) -> u8 {
+        self.enable_warped_motion
+    }
+
+    pub fn enable_dual_filter(&self) -> u8 {
+        self.enable_dual_filter
+    }
+
+    pub fn enable_order_hint(&self) -> u8 {
+        self.enable_order_hint
+    }
+
+    pub fn enable_jnt_comp(&self) -> u8 {
+        self.enable_jnt_comp
+    }
+
+    pub fn enable_ref_frame_mvs(&self) -> u8 {
+        self.enable_ref_frame_mvs
+    }
+
+    pub fn seq_force_screen_content_tools(&self) -> u8 {
+        self.seq_force_screen_content_tools
+    }
+
+    pub fn seq_force_integer_mv(&self) -> u8 {
+        self.seq_force_integer_mv
+    }
+
+    // OrderHintBits
+    pub fn order_hint_bits(&self) -> u8 {
+        self.order_hint_bits
+    }
+
+    pub fn enable_superres(&self) -> u8 {
+        self.enable_superres
+    }
+
+    pub fn enable_cdef(&self) -> u8 {
+        self.enable_cdef
+    }
+
+    pub fn enable_restoration(&self) -> u8 {
+        self.enable_restoration
+    }
+
+    pub fn color_config(&self) -> &Color_Config {
+        &self.color_config
+    }
+
+    pub fn film_grain_params_present(&self) -> u8 {
+        self.film_grain_params_present
+    }
+}
+
+impl Timing_Info {
+    pub fn num_units_in_display_tick(&self) -> u32 {
+        self.num_units_in_display_tick
+    }
+
+    pub fn time_scale(&self) -> u32 {
+        self.time_scale
+    }
+
+    pub fn equal_picture_interval(&self) -> u8 {
+        self.equal_picture_interval
+    }
+
+    pub fn num_ticks_per_picture_minus_1(&self) -> Option<u32> {
+        self.num_ticks_per_picture_minus_1.as_ref().map(|ticks| ticks.value())
+    }
+
+    // Pictures per second, only defined for a constant picture interval
+    pub fn frame_rate(&self) -> Option<f64> {
+        let ticks_per_picture = self.num_ticks_per_picture_minus_1()? as f64 + 1f64;
+        if self.num_units_in_display_tick == 0 {
+            return None;
+        }
+        Some(self.time_scale as f64 / (self.num_units_in_display_tick as f64 * ticks_per_picture))
+    }
+}
+
+impl Decoder_Model_Info {
+    pub fn buffer_delay_length_minus_1(&self) -> u8 {
+        self.buffer_delay_length_minus_1
+    }
+
+    pub fn num_units_in_decoding_tick(&self) -> u32 {
+        self.num_units_in_decoding_tick
+    }
+
+    pub fn buffer_removal_delay_length_minus_1(&self) -> u8 {
+        self.buffer_removal_delay_length_minus_1
+    }
+
+    pub fn frame_presentation_delay_length_minus_1(&self) -> u8 {
+        self.frame_presentation_delay_length_minus_1
+    }
+}
+
+impl Operating_Parameters_Info {
+    pub fn decoder_buffer_delay(&self) -> &[u32] {
+        &self.decoder_buffer_delay
+    }
+
+    pub fn encoder_buffer_delay(&self) -> &[u32] {
+        &self.encoder_buffer_delay
+    }
+
+    pub fn low_delay_mode_flag(&self) -> &[u8] {
+        &self.low_delay_mode_flag
+    }
+}
+
+impl Color_Config {
+    pub fn high_bitdepth(&self) -> u8 {
+        self.high_bitdepth
+    }
+
+    // BitDepth
+    pub fn bit_depth(&self) -> u8 {
+        self.bit_depth
+    }
+
+    pub fn mono_chrome(&self) -> u8 {
+        self.mono_chrome
+    }
+
+    // NumPlanes
+    pub fn num_planes(&self) -> u8 {
+        self.num_planes
+    }
+
+    pub fn color_description_present_flag(&self) -> u8 {
+        self.color_description_present_flag
+    }
+
+    pub fn color_primaries(&self) -> COLOR_PRIMARIES {
+        self.color_primaries
+    }
+
+    pub fn transfer_characteristics(&self) -> TRANSFER_CHARACTERISTICS {
+        self.transfer_characteristics
+    }
+
+    pub fn matrix_coefficients(&self) -> MATRIX_COEFFICIENTS {
+        self.matrix_coefficients
+    }
+
+    pub fn color_range(&self) -> u8 {
+        self.color_range
+    }
+
+    pub fn subsampling_x(&self) -> u8 {
+        self.subsampling_x
+    }
+
+    pub fn subsampling_y(&self) -> u8 {
+        self.subsampling_y
+    }
+
+    pub fn chroma_sample_position(&self) -> CHROMA_SAMPLE_POSITION {
+        self.chroma_sample_position
+    }
+
+    pub fn separate_uv_delta_q(&self) -> u8 {
+        self.separate_uv_delta_q
+    }
+
+    pub fn chroma_subsampling(&self) -> ChromaSubsampling {
+        match (self.mono_chrome, self.subsampling_x, self.subsampling_y) {
+            (1, _, _) => ChromaSubsampling::Mono,
+            (_, 1, 1) => ChromaSubsampling::I420,
+            (_, 1, 0) => ChromaSubsampling::I422,
+            _ => ChromaSubsampling::I444,
+        }
+    }
+}
@@ -1,7 +1,41 @@
 // Custom handler error
 
+use crate::Error;
 
-pub fn choose_operating_point() -> Result<usize,std::io::Error> {
+
+// Picks an operating point from the parsed operating_point_idc, seq_level_idx and seq_tier lists
+pub type OperatingPointSelector = fn(operating_point_idc: &[u16], seq_level_idx: &[u8], seq_tier: &[u8]) -> usize;
+
+// 7.1 choose_operating_point( ) is left to external means, 0 unless configured
+#[derive(Debug, Clone, Copy)]
+pub enum OperatingPoint {
+    Index(usize),
+    Select(OperatingPointSelector),
+}
+
+impl Default for OperatingPoint {
+    fn default() -> Self {
+        Self::Index(0)
+    }
+}
+
+pub fn choose_operating_point(
+    operating_point: &OperatingPoint,
+    operating_point_idc: &[u16],
+    seq_level_idx: &[u8],
+    seq_tier: &[u8],
+) -> Result<usize, Error> {
     log::debug!("obu->handlers->choose_operating_point()");
-    Ok(0usize)
-}
\ No newline at end of file
+    let index = match operating_point {
+        OperatingPoint::Index(index) => *index,
+        OperatingPoint::Select(select) => select(operating_point_idc, seq_level_idx, seq_tier),
+    };
+
+    if index >= operating_point_idc.len() {
+        return Err(Error::InvalidOperatingPoint {
+            operating_point: index,
+            operating_points_cnt: operating_point_idc.len(),
+        });
+    }
+    Ok(index)
+}
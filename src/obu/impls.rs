@@ -1,71 +1,144 @@
-use bitstream_io::FromBitStream;
+use std::io::Cursor;
 
-use crate::{consts::{self, OBU_TYPE}, generics::uvlc, leb_128};
+use bitstream_io::{BigEndian, BitReader, FromBitStream, ToBitStream, ToBitStreamWith};
 
-use super::{handlers::choose_operating_point, Color_Config, Decoder_Model_Info, OBU_Extension_Header, OBU_Header, OBU_Sequence_Header, Operating_Parameters_Info, Timing_Info, OBU};
+use crate::{consts::{self, OBU_TYPE}, generics::uvlc, leb_128, Error};
 
-impl OBU {
-    
-    pub fn open_bitstream_unit<R: bitstream_io::BitRead + ?Sized>(
-        r: &mut R,
-        sz: u64,
-    ) -> Result<OBU, std::io::Error> {
+use super::{handlers::{choose_operating_point, OperatingPoint}, Color_Config, Decoder_Model_Info, OBU_Extension_Header, OBU_Header, OBU_Sequence_Header, ObuReader, Operating_Parameters_Info, Timing_Info, OBU};
+
+impl<'a> OBU<'a> {
+
+    // 5.3.1 General OBU syntax
+    // `data` starts at the OBU header and may run past the OBU, its bytes are taken from the front
+    // Without obu_size the OBU takes all of `data`
+    pub fn open_bitstream_unit(data: &'a [u8]) -> Result<OBU<'a>, Error> {
+        let (header, obu_size, header_size) = Self::open_header(data)?;
+        Self::with_payload(header, obu_size, header_size, data)
+    }
+
+    // obu_header( ) and obu_size with their size in bytes, the payload may still be missing from `data`
+    pub(crate) fn open_header(data: &[u8]) -> Result<(OBU_Header, leb_128, usize), Error> {
+        let mut r = BitReader::endian(Cursor::new(data), BigEndian);
+        let (header, obu_size) = Self::obu_header_and_size(&mut r, data.len())
+            .map_err(|e| e.at(0, r.position_in_bits().unwrap_or(0)))?;
+        let header_size = (r.position_in_bits()? / 8) as usize;
+        Ok((header, obu_size, header_size))
+    }
+
+    // Completes an OBU whose header open_header read from the start of `data`
+    pub(crate) fn with_payload(header: OBU_Header, obu_size: leb_128, header_size: usize, data: &'a [u8]) -> Result<OBU<'a>, Error> {
+        let payload_end = usize::try_from(obu_size.value())
+            .ok()
+            .and_then(|size| header_size.checked_add(size))
+            .filter(|end| *end <= data.len())
+            .ok_or(Error::UnexpectedEof)
+            .map_err(|e| e.at(0, header_size as u64 * 8))?;
+
+        Ok(OBU {
+            obu_size,
+            obu_header: header,
+            header_size,
+            payload: &data[header_size..payload_end],
+        })
+    }
+
+    fn obu_header_and_size<R: bitstream_io::BitRead + ?Sized>(r: &mut R, sz: usize) -> Result<(OBU_Header, leb_128), Error> {
         let header = OBU_Header::from_reader(r)?;
         let obu_size = if header.obu_has_size_field == 1 {
             leb_128::from_reader(r)?
         } else {
-            leb_128::new(sz - 1 - header.obu_extension_flag as u64)
+            let sz = (sz as u64)
+                .checked_sub(1 + header.obu_extension_flag as u64)
+                .ok_or(Error::UnexpectedEof)?;
+            leb_128::new(sz)
         };
-        let operating_point_idc: u8 = 0u8;
-
-        //[!] OperatingPointIdc
-        if header.obu_type != OBU_TYPE::OBU_SEQUENCE_HEADER
-            && header.obu_type != OBU_TYPE::OBU_TEMPORAL_DELIMITER
-            && operating_point_idc != 0
-            && header.obu_extension_flag == 1
-            && header.obu_extension_header.is_some()
-        {
-            let extra_headers = header.obu_extension_header.clone().unwrap();
-            let in_temporal_layer = (operating_point_idc >> extra_headers.temporal_id) & 1u8;
-            let in_spatial_layer = (operating_point_idc >> (extra_headers.spatial_id + 8u8)) & 1u8;
-
-            if in_temporal_layer != 0u8 || in_spatial_layer == 0u8 {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    "Layer filtering: not in temporal or spatial layer",
-                ));
-            }
-        }
+        Ok((header, obu_size))
+    }
 
-        let obu_sequence_header = if header.obu_type == OBU_TYPE::OBU_SEQUENCE_HEADER {
-            Some(OBU_Sequence_Header::sequence_header_obu(r)?)
-        } else {
-            None
-        };
+    pub fn obu_header(&self) -> &OBU_Header {
+        &self.obu_header
+    }
+
+    pub fn obu_type(&self) -> OBU_TYPE {
+        self.obu_header.obu_type
+    }
+
+    pub fn obu_extension_header(&self) -> Option<&OBU_Extension_Header> {
+        self.obu_header.obu_extension_header.as_ref()
+    }
+
+    pub fn obu_size(&self) -> u64 {
+        self.obu_size.value()
+    }
 
-        Ok(OBU { obu_size, obu_header: header })
+    pub fn payload(&self) -> &'a [u8] {
+        self.payload
+    }
+
+    // Header and size field in bytes
+    pub fn header_size(&self) -> usize {
+        self.header_size
+    }
+
+    // Header, size field and payload in bytes
+    pub fn total_size(&self) -> usize {
+        self.header_size + self.payload.len()
     }
 }
 
-impl FromBitStream for OBU {
+impl<'a> ToBitStream for OBU<'a> {
     type Error = std::io::Error;
 
-    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
+    // 5.3.1 General OBU syntax, writes the OBU back with its original obu_size encoding
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
     where
         Self: Sized,
     {
-        let obu_header = OBU_Header::from_reader(r)?;
-        let obu_size = leb_128::from_reader(r)?;
+        self.obu_header.to_writer(w)?;
+        if self.obu_header.obu_has_size_field == 1 {
+            self.obu_size.to_writer(w)?;
+        }
+        w.write_bytes(self.payload)
+    }
+}
 
-        Ok(Self {
-            obu_size,
-            obu_header,
-        })
+impl<'a> ObuReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0, obu_index: 0 }
+    }
+
+    // Byte offset of the next OBU
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<'a> Iterator for ObuReader<'a> {
+    type Item = Result<OBU<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.data.len() {
+            return None;
+        }
+
+        let obu_index = self.obu_index;
+        self.obu_index += 1;
+        match OBU::open_bitstream_unit(&self.data[self.position..]) {
+            Ok(obu) => {
+                self.position += obu.total_size();
+                Some(Ok(obu))
+            }
+            Err(e) => {
+                // No way to resync without a size, stop after the error
+                self.position = self.data.len();
+                Some(Err(e.with_obu_index(obu_index)))
+            }
+        }
     }
 }
 
 impl FromBitStream for OBU_Header {
-    type Error = std::io::Error;
+    type Error = Error;
 
     fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
     where
@@ -93,9 +166,45 @@ impl FromBitStream for OBU_Header {
     }
 }
 
-impl FromBitStream for OBU_Extension_Header {
+impl OBU_Header {
+    // Header for writing an OBU, obu_size is always present
+    pub fn new(obu_type: OBU_TYPE, obu_extension_header: Option<OBU_Extension_Header>) -> Self {
+        Self {
+            obu_forbidden_bit: 0,
+            obu_type,
+            obu_extension_flag: obu_extension_header.is_some() as u8,
+            obu_has_size_field: 1,
+            obu_reserved_1bit: 0,
+            obu_extension_header,
+        }
+    }
+}
+
+impl ToBitStream for OBU_Header {
     type Error = std::io::Error;
 
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        w.write::<1, u8>(self.obu_forbidden_bit)?;
+        self.obu_type.to_writer(w)?;
+        w.write::<1, u8>(self.obu_extension_flag)?;
+        w.write::<1, u8>(self.obu_has_size_field)?;
+        w.write::<1, u8>(self.obu_reserved_1bit)?;
+        if self.obu_extension_flag == 1 {
+            self.obu_extension_header
+                .as_ref()
+                .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "OBU extension header missing"))?
+                .to_writer(w)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromBitStream for OBU_Extension_Header {
+    type Error = Error;
+
     fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
     where
         Self: Sized,
@@ -108,11 +217,25 @@ impl FromBitStream for OBU_Extension_Header {
     }
 }
 
+impl ToBitStream for OBU_Extension_Header {
+    type Error = std::io::Error;
+
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        w.write::<3, u8>(self.temporal_id)?;
+        w.write::<2, u8>(self.spatial_id)?;
+        w.write::<3, u8>(self.extension_header_reserved_3bits)?;
+        Ok(())
+    }
+}
+
 
 impl OBU_Sequence_Header {
     
     // 5.5.1 General sequence header OBU syntax
-    fn sequence_header_obu<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, std::io::Error> 
+    pub fn sequence_header_obu<R: bitstream_io::BitRead + ?Sized>(r: &mut R, operating_point: &OperatingPoint) -> Result<Self, Error>
     where
         Self: Sized,
     {
@@ -120,25 +243,25 @@ impl OBU_Sequence_Header {
         let still_picture = r.read::<1, u8>()?;
         let reduced_still_picture_header = r.read::<1, u8>()?;
 
-        let mut timing_info_present_flag: u8 = 0u8;
-        let mut decoder_model_info_present_flag: u8 = 0u8;
+        let mut timing_info: Option<Timing_Info> = None;
         let mut decoder_model_info: Option<Decoder_Model_Info> = None;
-        let mut initial_display_delay_present_flag: u8 = 0u8;
-        let mut operating_points_cnt_minus_1: u8 = 0u8;
-        let mut operating_point_idc: Vec<u16> = vec![0u16];
-
-        let mut seq_level_idx: Vec<u8> = vec![r.read::<5, u8>()?];
-
-        let mut seq_tier: Vec<u8> = vec![0u8];
-        let mut decoder_model_present_for_this_op: Vec<u8> = vec![0u8];
+        let mut operating_point_idc: Vec<u16> = Vec::new();
+        let mut seq_level_idx: Vec<u8> = Vec::new();
+        let mut seq_tier: Vec<u8> = Vec::new();
+        let mut decoder_model_present_for_this_op: Vec<u8> = Vec::new();
         let mut operating_parameters_info: Option<Operating_Parameters_Info> = None;
-        let mut initial_display_delay_present_for_this_op: Vec<u8> = vec![0u8];
+        let mut initial_display_delay_present_for_this_op: Vec<u8> = Vec::new();
         let mut initial_display_delay_minus_1: Option<Vec<u8>> = None;
-        let mut timing_info: Option<Timing_Info> = None;
+        let mut initial_display_delay_present_flag = 0u8;
 
-        if reduced_still_picture_header == 0{
-
-            timing_info_present_flag = r.read::<1,u8>()?;
+        if reduced_still_picture_header != 0 {
+            operating_point_idc.push(0u16);
+            seq_level_idx.push(r.read::<5, u8>()?);
+            seq_tier.push(0u8);
+            decoder_model_present_for_this_op.push(0u8);
+            initial_display_delay_present_for_this_op.push(0u8);
+        } else {
+            let timing_info_present_flag = r.read::<1,u8>()?;
 
             // Timing_Info
             if timing_info_present_flag == 1 {
@@ -152,40 +275,37 @@ impl OBU_Sequence_Header {
                 0u8
             };
 
-            let decoder_model_info: Option<Decoder_Model_Info> = if decoder_model_info_present_flag != 0u8 {
-                Some(Decoder_Model_Info::from_reader(r)?)
-            } else {
-                None
-            };
-            
+            if decoder_model_info_present_flag != 0u8 {
+                decoder_model_info = Some(Decoder_Model_Info::from_reader(r)?);
+            }
+
             // Operating_point_idc
             // seq_level_idx
             // seq_tier
             // Operating_Parameters_Info
             // initial_display_delay_minus_1
             initial_display_delay_present_flag = r.read::<1, u8>()?;
-            operating_points_cnt_minus_1 = r.read::<5, u8>()?;
-            for i in 0..operating_points_cnt_minus_1 as usize {
+            let operating_points_cnt_minus_1 = r.read::<5, u8>()?;
+            for _ in 0..=operating_points_cnt_minus_1 as usize {
                 operating_point_idc.push(r.read::<12,u16>()?);
-                seq_level_idx.push(r.read::<5,u8>()?);
+                let level = r.read::<5,u8>()?;
+                seq_level_idx.push(level);
 
                 // seq_tier
-                if *seq_level_idx.last().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "Seq level idx not present"))? > 7 {
+                if level > 7 {
                     seq_tier.push(r.read::<1, u8>()?);
                 } else {
                     seq_tier.push(0);
                 }
 
                 // Operating_Parameters_Info
-                if decoder_model_info_present_flag != 0u8  {
-                    decoder_model_present_for_this_op.push(r.read::<1, u8>()?);
-                    if *decoder_model_present_for_this_op.last().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "Decoder model present for this op not present"))? != 0 {
-                        let decoder_model_info = decoder_model_info.as_ref().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "Decoder model info not present"))?;
-
-                        if operating_parameters_info.is_none() {
-                            operating_parameters_info = Some(Operating_Parameters_Info::new());
-                        }
-                        operating_parameters_info.as_mut().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "Operating parameters info not present"))?.from_reader(r,decoder_model_info)?;
+                if let Some(decoder_model_info) = decoder_model_info.as_ref() {
+                    let decoder_model_present = r.read::<1, u8>()?;
+                    decoder_model_present_for_this_op.push(decoder_model_present);
+                    if decoder_model_present != 0 {
+                        operating_parameters_info
+                            .get_or_insert_with(Operating_Parameters_Info::new)
+                            .operating_parameters_info(r, decoder_model_info)?;
                     }
                 } else {
                     decoder_model_present_for_this_op.push(0);
@@ -193,23 +313,20 @@ impl OBU_Sequence_Header {
 
                 // initial_display_delay_minus_1
                 if initial_display_delay_present_flag != 0 {
-                    initial_display_delay_present_for_this_op.push(r.read::<1, u8>()?);
-                    if initial_display_delay_present_for_this_op.last().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "Initial display delay present for this op not present"))?.to_owned() != 0u8 {
-                        if initial_display_delay_minus_1.is_none() {
-                            initial_display_delay_minus_1 = Some(Vec::new());
-                        }
-                        initial_display_delay_minus_1.as_mut().ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "Initial display delay minus 1 not present"))?.push(r.read::<4, u8>()?);
+                    let initial_display_delay_present = r.read::<1, u8>()?;
+                    initial_display_delay_present_for_this_op.push(initial_display_delay_present);
+                    if initial_display_delay_present != 0u8 {
+                        initial_display_delay_minus_1.get_or_insert_with(Vec::new).push(r.read::<4, u8>()?);
                     }
+                } else {
+                    initial_display_delay_present_for_this_op.push(0u8);
                 }
             }
         }
 
         // Operating point
-        let operating_point_index = choose_operating_point()?;
-        if operating_point_index >= operating_point_idc.len() {
-            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Operating point index out of bounds"));
-        }
-        let c_operating_point_idc = operating_point_idc[operating_point_index];
+        let operating_point = choose_operating_point(operating_point, &operating_point_idc, &seq_level_idx, &seq_tier)?;
+        let c_operating_point_idc = operating_point_idc[operating_point];
         let frame_width_bits_minus_1 = r.read::<4, u8>()?;
         let frame_height_bits_minus_1 = r.read::<4, u8>()?;
         let max_frame_width_minus_one: u16 = r.read_var(frame_width_bits_minus_1 as u32 + 1u32)?;  // 2**4 = 16 bits
@@ -246,7 +363,7 @@ impl OBU_Sequence_Header {
         let mut seq_force_integer_mv: u8 = consts::SELECT_INTEGER_MV;
         let mut order_hint_bits: u8 = 0u8;
 
-        if reduced_still_picture_header != 0 {
+        if reduced_still_picture_header == 0 {
             enable_interintra_compound = r.read::<1,u8>()?;
             enable_masked_compound = r.read::<1,u8>()?;
             enable_warped_motion = r.read::<1,u8>()?;
@@ -286,6 +403,7 @@ impl OBU_Sequence_Header {
         Ok(Self {
             seq_profile,
             still_picture,
+            reduced_still_picture_header,
             timing_info,
             decoder_model_info,
             operating_point_idc,
@@ -293,9 +411,13 @@ impl OBU_Sequence_Header {
             seq_tier,
             decoder_model_present_for_this_op,
             operating_parameters_info,
+            initial_display_delay_present_flag,
             initial_display_delay_present_for_this_op,
             initial_display_delay_minus_1,
+            operating_point,
             c_operating_point_idc,
+            frame_width_bits_minus_1,
+            frame_height_bits_minus_1,
             max_frame_width_minus_one,
             max_frame_height_minus_one,
             delta_frame_id_length_minus_2,
@@ -322,11 +444,152 @@ impl OBU_Sequence_Header {
 
     }
 
+    // get_relative_dist( a, b ), signed distance between two order hints modulo 1 << OrderHintBits
+    pub fn get_relative_dist(&self, a: u8, b: u8) -> i32 {
+        if self.enable_order_hint == 0 {
+            return 0;
+        }
+        let diff = a as i32 - b as i32;
+        let m = 1i32 << (self.order_hint_bits as u32 - 1);
+        (diff & (m - 1)) - (diff & m)
+    }
+
+    // Equal syntax elements, operating_point and c_operating_point_idc are chosen by the decoder
+    pub(crate) fn same_syntax(&self, other: &Self) -> bool {
+        let other = Self {
+            operating_point: self.operating_point,
+            c_operating_point_idc: self.c_operating_point_idc,
+            ..other.clone()
+        };
+        *self == other
+    }
+
 }
 
-impl FromBitStream for Timing_Info {
+impl ToBitStream for OBU_Sequence_Header {
     type Error = std::io::Error;
 
+    // 5.5.1 General sequence header OBU syntax, without the trailing bits
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        w.write::<3, u8>(self.seq_profile)?;
+        w.write::<1, u8>(self.still_picture)?;
+        w.write::<1, u8>(self.reduced_still_picture_header)?;
+
+        if self.reduced_still_picture_header != 0 {
+            w.write::<5, u8>(self.seq_level_idx[0])?;
+        } else {
+            w.write::<1, u8>(self.timing_info.is_some() as u8)?;
+            if let Some(timing_info) = &self.timing_info {
+                timing_info.to_writer(w)?;
+                w.write::<1, u8>(self.decoder_model_info.is_some() as u8)?;
+                if let Some(decoder_model_info) = &self.decoder_model_info {
+                    decoder_model_info.to_writer(w)?;
+                }
+            }
+
+            w.write::<1, u8>(self.initial_display_delay_present_flag)?;
+            w.write::<5, u8>(self.operating_point_idc.len() as u8 - 1)?;
+            let mut operating_parameters_idx = 0usize;
+            let mut initial_display_delay_idx = 0usize;
+            for i in 0..self.operating_point_idc.len() {
+                w.write::<12, u16>(self.operating_point_idc[i])?;
+                w.write::<5, u8>(self.seq_level_idx[i])?;
+                if self.seq_level_idx[i] > 7 {
+                    w.write::<1, u8>(self.seq_tier[i])?;
+                }
+
+                if let Some(decoder_model_info) = &self.decoder_model_info {
+                    w.write::<1, u8>(self.decoder_model_present_for_this_op[i])?;
+                    if self.decoder_model_present_for_this_op[i] != 0 {
+                        self.operating_parameters_info
+                            .as_ref()
+                            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Operating parameters info missing"))?
+                            .write_operating_parameters_info(w, operating_parameters_idx, decoder_model_info)?;
+                        operating_parameters_idx += 1;
+                    }
+                }
+
+                if self.initial_display_delay_present_flag != 0 {
+                    w.write::<1, u8>(self.initial_display_delay_present_for_this_op[i])?;
+                    if self.initial_display_delay_present_for_this_op[i] != 0 {
+                        let initial_display_delay_minus_1 = self
+                            .initial_display_delay_minus_1
+                            .as_ref()
+                            .and_then(|delays| delays.get(initial_display_delay_idx))
+                            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Initial display delay missing"))?;
+                        w.write::<4, u8>(*initial_display_delay_minus_1)?;
+                        initial_display_delay_idx += 1;
+                    }
+                }
+            }
+        }
+
+        w.write::<4, u8>(self.frame_width_bits_minus_1)?;
+        w.write::<4, u8>(self.frame_height_bits_minus_1)?;
+        w.write_var(self.frame_width_bits_minus_1 as u32 + 1, self.max_frame_width_minus_one)?;
+        w.write_var(self.frame_height_bits_minus_1 as u32 + 1, self.max_frame_height_minus_one)?;
+        if self.reduced_still_picture_header == 0 {
+            w.write::<1, u8>(self.delta_frame_id_length_minus_2.is_some() as u8)?;
+        }
+        if let (Some(delta_frame_id_length_minus_2), Some(additional_frame_id_length_minus_1)) = (self.delta_frame_id_length_minus_2, self.additional_frame_id_length_minus_1) {
+            w.write::<4, u8>(delta_frame_id_length_minus_2)?;
+            w.write::<3, u8>(additional_frame_id_length_minus_1)?;
+        }
+
+        w.write::<1, u8>(self.use_128x128_superblock)?;
+        w.write::<1, u8>(self.enable_filter_intra)?;
+        w.write::<1, u8>(self.enable_intra_edge_filter)?;
+
+        if self.reduced_still_picture_header == 0 {
+            w.write::<1, u8>(self.enable_interintra_compound)?;
+            w.write::<1, u8>(self.enable_masked_compound)?;
+            w.write::<1, u8>(self.enable_warped_motion)?;
+            w.write::<1, u8>(self.enable_dual_filter)?;
+            w.write::<1, u8>(self.enable_order_hint)?;
+
+            if self.enable_order_hint != 0 {
+                w.write::<1, u8>(self.enable_jnt_comp)?;
+                w.write::<1, u8>(self.enable_ref_frame_mvs)?;
+            }
+
+            // seq_choose_screen_content_tools
+            if self.seq_force_screen_content_tools == consts::SELECT_SCREEN_CONTENT_TOOLS {
+                w.write::<1, u8>(1)?;
+            } else {
+                w.write::<1, u8>(0)?;
+                w.write::<1, u8>(self.seq_force_screen_content_tools)?;
+            }
+
+            if self.seq_force_screen_content_tools > 0u8 {
+                // seq_choose_integer_mv
+                if self.seq_force_integer_mv == consts::SELECT_INTEGER_MV {
+                    w.write::<1, u8>(1)?;
+                } else {
+                    w.write::<1, u8>(0)?;
+                    w.write::<1, u8>(self.seq_force_integer_mv)?;
+                }
+            }
+
+            if self.enable_order_hint != 0 {
+                w.write::<3, u8>(self.order_hint_bits - 1)?;
+            }
+        }
+
+        w.write::<1, u8>(self.enable_superres)?;
+        w.write::<1, u8>(self.enable_cdef)?;
+        w.write::<1, u8>(self.enable_restoration)?;
+        self.color_config.to_writer(w, &self.seq_profile)?;
+        w.write::<1, u8>(self.film_grain_params_present)?;
+        Ok(())
+    }
+}
+
+impl FromBitStream for Timing_Info {
+    type Error = Error;
+
     fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
     where
         Self: Sized {
@@ -348,9 +611,25 @@ impl FromBitStream for Timing_Info {
     }
 }
 
-impl FromBitStream for Decoder_Model_Info {
+impl ToBitStream for Timing_Info {
     type Error = std::io::Error;
 
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized {
+        w.write::<32, u32>(self.num_units_in_display_tick)?;
+        w.write::<32, u32>(self.time_scale)?;
+        w.write::<1, u8>(self.equal_picture_interval)?;
+        if let Some(num_ticks_per_picture_minus_1) = &self.num_ticks_per_picture_minus_1 {
+            num_ticks_per_picture_minus_1.to_writer(w)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromBitStream for Decoder_Model_Info {
+    type Error = Error;
+
     fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R) -> Result<Self, Self::Error>
     where
         Self: Sized {
@@ -368,9 +647,23 @@ impl FromBitStream for Decoder_Model_Info {
         }
 }
 
+impl ToBitStream for Decoder_Model_Info {
+    type Error = std::io::Error;
+
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized {
+        w.write::<5, u8>(self.buffer_delay_length_minus_1)?;
+        w.write::<32, u32>(self.num_units_in_decoding_tick)?;
+        w.write::<5, u8>(self.buffer_removal_delay_length_minus_1)?;
+        w.write::<5, u8>(self.frame_presentation_delay_length_minus_1)?;
+        Ok(())
+    }
+}
+
 impl Operating_Parameters_Info {
 
-    pub fn new() -> Self {
+    fn new() -> Self {
         Self {
             decoder_buffer_delay: Vec::new(),
             encoder_buffer_delay: Vec::new(),
@@ -378,47 +671,53 @@ impl Operating_Parameters_Info {
         }
     }
 
-    fn from_reader<R: bitstream_io::BitRead + ?Sized>(&mut self, r: &mut R, decoder_model_info: &Decoder_Model_Info) -> Result<(), std::io::Error>
-    where
-        Self: Sized {
+    // 5.5.5 Operating parameters info syntax
+    fn operating_parameters_info<R: bitstream_io::BitRead + ?Sized>(&mut self, r: &mut R, decoder_model_info: &Decoder_Model_Info) -> Result<(), Error> {
             let n = decoder_model_info.buffer_delay_length_minus_1 as u32 + 1; // max 32 = 2**5+1
             self.decoder_buffer_delay.push(r.read_var(n)?);
             self.encoder_buffer_delay.push(r.read_var(n)?);
             self.low_delay_mode_flag.push(r.read::<1, u8>()?);
             Ok(())
     }
+
+    // Writes the idx-th entry, entries only exist for operating points with decoder_model_present_for_this_op
+    fn write_operating_parameters_info<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W, idx: usize, decoder_model_info: &Decoder_Model_Info) -> Result<(), std::io::Error> {
+        let n = decoder_model_info.buffer_delay_length_minus_1 as u32 + 1;
+        w.write_var(n, self.decoder_buffer_delay[idx])?;
+        w.write_var(n, self.encoder_buffer_delay[idx])?;
+        w.write::<1, u8>(self.low_delay_mode_flag[idx])?;
+        Ok(())
+    }
 }
 
 impl Color_Config {
-    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R, seq_profile: u8) -> Result<Self, std::io::Error>
+    // 5.5.2 Color config syntax
+    fn from_reader<R: bitstream_io::BitRead + ?Sized>(r: &mut R, seq_profile: u8) -> Result<Self, Error>
     where
         Self: Sized {
-            let high_bit_depth: u8 = r.read::<1,u8>()?;
-
-            let bit_depth = if seq_profile == 2u8 && high_bit_depth != 0u8 {
-                12u8
-            } else {
-                10u8
-            };
-
-            let mono_chrome = if seq_profile == 1u8 {
-                0u8
-            } else {
-                r.read::<1, u8>()?
-            };
+        let high_bitdepth = r.read::<1, u8>()?;
+        let bit_depth = if seq_profile == 2 && high_bitdepth != 0 {
+            // twelve_bit
+            if r.read::<1, u8>()? != 0 { 12u8 } else { 10u8 }
+        } else if high_bitdepth != 0 {
+            10u8
+        } else {
+            8u8
+        };
 
-            let num_planes = if mono_chrome == 0u8 {
-                3u8
-            } else {
-                1u8
-            };
+        let mono_chrome = if seq_profile == 1 {
+            0u8
+        } else {
+            r.read::<1, u8>()?
+        };
+        let num_planes = if mono_chrome != 0 { 1u8 } else { 3u8 };
 
         let mut color_primaries = consts::COLOR_PRIMARIES::CP_UNSPECIFIED;
         let mut transfer_characteristics = consts::TRANSFER_CHARACTERISTICS::TC_UNSPECIFIED;
         let mut matrix_coefficients = consts::MATRIX_COEFFICIENTS::MC_UNSPECIFIED;
 
-        // color_description_present_flag
-        if r.read::<1,u8>()? != 0u8 {
+        let color_description_present_flag = r.read::<1, u8>()?;
+        if color_description_present_flag != 0 {
             color_primaries = consts::COLOR_PRIMARIES::from_reader(r)?;
             transfer_characteristics = consts::TRANSFER_CHARACTERISTICS::from_reader(r)?;
             matrix_coefficients = consts::MATRIX_COEFFICIENTS::from_reader(r)?;
@@ -427,72 +726,54 @@ impl Color_Config {
         let color_range: u8;
         let subsampling_x: u8;
         let subsampling_y: u8;
-        let mut chroma_sample_position: consts::CHROMA_SAMPLE_POSITION = consts::CHROMA_SAMPLE_POSITION::CSP_UNKNOWN;
-        let separate_uv_delta_q: u8;
+        let mut chroma_sample_position = consts::CHROMA_SAMPLE_POSITION::CSP_UNKNOWN;
 
         if mono_chrome != 0 {
-            color_range = r.read::<1, u8>()?;
-            subsampling_x = 1u8;
-            subsampling_y = 1u8;
-            separate_uv_delta_q = 0u8;
-
             return Ok(Self {
+                high_bitdepth,
                 bit_depth,
                 mono_chrome,
                 num_planes,
+                color_description_present_flag,
                 color_primaries,
                 transfer_characteristics,
                 matrix_coefficients,
-                color_range,
-                subsampling_x,
-                subsampling_y,
+                color_range: r.read::<1, u8>()?,
+                subsampling_x: 1,
+                subsampling_y: 1,
                 chroma_sample_position,
-                separate_uv_delta_q,
+                separate_uv_delta_q: 0,
             });
-        } else if color_primaries == consts::COLOR_PRIMARIES::CP_BT_709 
-            && transfer_characteristics == consts::TRANSFER_CHARACTERISTICS::TC_SRGB 
+        } else if color_primaries == consts::COLOR_PRIMARIES::CP_BT_709
+            && transfer_characteristics == consts::TRANSFER_CHARACTERISTICS::TC_SRGB
             && matrix_coefficients == consts::MATRIX_COEFFICIENTS::MC_IDENTITY {
             color_range = 1u8;
             subsampling_x = 0u8;
             subsampling_y = 0u8;
         } else {
             color_range = r.read::<1, u8>()?;
-            
-            match seq_profile {
-                0 => {
-                    subsampling_x = 1u8;
-                    subsampling_y = 1u8;
-                },
-                1 => {
-                    subsampling_x = r.read::<1, u8>()?;
-                    subsampling_y = r.read::<1, u8>()?;
-                },
-                _ => {
-                    if bit_depth == 12 {
-                        subsampling_x = r.read::<1, u8>()?;
-                        if subsampling_x != 0 {
-                            subsampling_y = r.read::<1, u8>()?;
-                        } else {
-                            subsampling_y = 0u8;
-                        }
-                    } else {
-                        subsampling_x = 1u8;
-                        subsampling_y = 0u8;
-                    }
+            (subsampling_x, subsampling_y) = match seq_profile {
+                0 => (1u8, 1u8),
+                1 => (0u8, 0u8),
+                _ if bit_depth == 12 => {
+                    let subsampling_x = r.read::<1, u8>()?;
+                    let subsampling_y = if subsampling_x != 0 { r.read::<1, u8>()? } else { 0u8 };
+                    (subsampling_x, subsampling_y)
                 }
-            }
+                _ => (1u8, 0u8),
+            };
 
             if subsampling_x != 0 && subsampling_y != 0 {
                 chroma_sample_position = consts::CHROMA_SAMPLE_POSITION::from_reader(r)?;
             }
         }
 
-        separate_uv_delta_q = r.read::<1, u8>()?;
-
         Ok(Self {
+            high_bitdepth,
             bit_depth,
             mono_chrome,
             num_planes,
+            color_description_present_flag,
             color_primaries,
             transfer_characteristics,
             matrix_coefficients,
@@ -500,8 +781,57 @@ impl Color_Config {
             subsampling_x,
             subsampling_y,
             chroma_sample_position,
-            separate_uv_delta_q,
+            separate_uv_delta_q: r.read::<1, u8>()?,
         })
+    }
+}
+
+impl<'a> ToBitStreamWith<'a> for Color_Config {
+    type Context = u8; // seq_profile
+    type Error = std::io::Error;
+
+    // 5.5.2 Color config syntax
+    fn to_writer<W: bitstream_io::BitWrite + ?Sized>(&self, w: &mut W, seq_profile: &Self::Context) -> Result<(), Self::Error>
+    where
+        Self: Sized {
+        w.write::<1, u8>(self.high_bitdepth)?;
+        if *seq_profile == 2 && self.high_bitdepth != 0 {
+            // twelve_bit
+            w.write::<1, u8>((self.bit_depth == 12) as u8)?;
+        }
+
+        if *seq_profile != 1 {
+            w.write::<1, u8>(self.mono_chrome)?;
+        }
 
+        w.write::<1, u8>(self.color_description_present_flag)?;
+        if self.color_description_present_flag != 0 {
+            self.color_primaries.to_writer(w)?;
+            self.transfer_characteristics.to_writer(w)?;
+            self.matrix_coefficients.to_writer(w)?;
+        }
+
+        if self.mono_chrome != 0 {
+            w.write::<1, u8>(self.color_range)?;
+            return Ok(());
+        } else if self.color_primaries == consts::COLOR_PRIMARIES::CP_BT_709
+            && self.transfer_characteristics == consts::TRANSFER_CHARACTERISTICS::TC_SRGB
+            && self.matrix_coefficients == consts::MATRIX_COEFFICIENTS::MC_IDENTITY {
+            // color_range and subsampling are implied
+        } else {
+            w.write::<1, u8>(self.color_range)?;
+            if *seq_profile > 1 && self.bit_depth == 12 {
+                w.write::<1, u8>(self.subsampling_x)?;
+                if self.subsampling_x != 0 {
+                    w.write::<1, u8>(self.subsampling_y)?;
+                }
+            }
+            if self.subsampling_x != 0 && self.subsampling_y != 0 {
+                self.chroma_sample_position.to_writer(w)?;
+            }
         }
-}
\ No newline at end of file
+
+        w.write::<1, u8>(self.separate_uv_delta_q)?;
+        Ok(())
+    }
+}
@@ -1,12 +1,25 @@
 mod impls;
 mod handlers;
+mod accessors;
+
+pub use handlers::{OperatingPoint, OperatingPointSelector};
 
 use crate::{consts::{self, OBU_TYPE}, generics::uvlc, leb_128};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct OBU {
+pub struct OBU<'a> {
     obu_size: leb_128,          // leb128
     obu_header: OBU_Header,     // 16 bits
+    header_size: usize,         // bytes before the payload (header + obu_size)
+    payload: &'a [u8],          // obu_size bytes
+}
+
+// 5.3.1 / 7.5 Iterates the OBUs of a low-overhead bitstream
+#[derive(Debug, Clone)]
+pub struct ObuReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    obu_index: usize,
 }
 
 
@@ -31,6 +44,7 @@ pub struct OBU_Extension_Header {
 pub struct OBU_Sequence_Header {
     seq_profile: u8,                    // 3 bits
     still_picture: u8,                  // 1 bit
+    reduced_still_picture_header: u8,   // 1 bit
     timing_info: Option<Timing_Info>,
     decoder_model_info: Option<Decoder_Model_Info>,
     operating_point_idc: Vec<u16>,      // 12 bits
@@ -38,9 +52,13 @@ pub struct OBU_Sequence_Header {
     seq_tier: Vec<u8>,                       // 1 bit
     decoder_model_present_for_this_op: Vec<u8>, // 1 bit
     operating_parameters_info: Option<Operating_Parameters_Info>,
+    initial_display_delay_present_flag: u8, // 1 bit
     initial_display_delay_present_for_this_op: Vec<u8>, // 1 bit
     initial_display_delay_minus_1: Option<Vec<u8>>,    // 4 bits
+    operating_point: usize,             // chosen operating point
     c_operating_point_idc: u16,         // 12 bits
+    frame_width_bits_minus_1: u8,       // 4 bits
+    frame_height_bits_minus_1: u8,      // 4 bits
     max_frame_width_minus_one: u16,     //  2**frame_width_bits_minus_1+1 
     max_frame_height_minus_one: u16,    //  2**frame_height_bits_minus_1+1
     delta_frame_id_length_minus_2: Option<u8>, // 4 bits
@@ -65,6 +83,15 @@ pub struct OBU_Sequence_Header {
     film_grain_params_present: u8,       // 1 bit
 }
 
+// Chroma format derived from mono_chrome, subsampling_x and subsampling_y
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ChromaSubsampling {
+    Mono,
+    I420,
+    I422,
+    I444,
+}
+
 // 5.5.3 Timing info syntax
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Timing_Info {
@@ -94,9 +121,11 @@ pub struct Operating_Parameters_Info {
 // 5.5.2 Color Config
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Color_Config {
+    high_bitdepth: u8,                  // 1 bit
     bit_depth: u8,
     mono_chrome: u8,
     num_planes: u8,
+    color_description_present_flag: u8, // 1 bit
     color_primaries: consts::COLOR_PRIMARIES,
     transfer_characteristics: consts::TRANSFER_CHARACTERISTICS,
     matrix_coefficients: consts::MATRIX_COEFFICIENTS,
@@ -0,0 +1,220 @@
+use crate::{
+    consts::OBU_TYPE,
+    decoder::{DecodedObu, Decoder},
+    frame_header::FrameHeader,
+    metadata::OBU_Metadata,
+    obu::{OperatingPoint, OBU_Sequence_Header},
+    tile_group::TileGroup,
+    Error, OBU,
+};
+
+use super::{CodedFrame, TemporalUnit, TemporalUnits};
+
+impl<'a, I> TemporalUnits<'a, I>
+where
+    I: Iterator<Item = Result<OBU<'a>, Error>>,
+{
+    pub fn new(obus: I) -> Self {
+        Self {
+            obus,
+            decoder: Decoder::new(),
+            pending: None,
+            obu_index: 0,
+            done: false,
+        }
+    }
+
+    // Applies to the next sequence header
+    pub fn set_operating_point(&mut self, operating_point: OperatingPoint) {
+        self.decoder.set_operating_point(operating_point);
+    }
+
+    // State after the last returned unit
+    pub fn decoder(&self) -> &Decoder {
+        &self.decoder
+    }
+
+    fn next_obu(&mut self) -> Option<Result<(usize, OBU<'a>), Error>> {
+        let obu = self.obus.next()?;
+        let obu_index = self.obu_index;
+        self.obu_index += 1;
+        Some(obu.map(|obu| (obu_index, obu)))
+    }
+
+    fn temporal_unit(&mut self, obu_index: usize, obu: OBU<'a>) -> Result<TemporalUnit<'a>, Error> {
+        // 7.5 The first OBU of a temporal unit is a temporal delimiter
+        if obu.obu_type() != OBU_TYPE::OBU_TEMPORAL_DELIMITER {
+            return Err(Error::ConformanceViolation {
+                requirement: "a temporal unit starts with a temporal delimiter",
+            }
+            .at(obu_index, 0));
+        }
+
+        let mut temporal_unit = TemporalUnit::default();
+        temporal_unit.push(&mut self.decoder, obu).map_err(|e| e.at(obu_index, 0))?;
+
+        while let Some(next) = self.next_obu() {
+            let (obu_index, obu) = next?;
+            if obu.obu_type() == OBU_TYPE::OBU_TEMPORAL_DELIMITER {
+                self.pending = Some(obu);
+                break;
+            }
+            temporal_unit.push(&mut self.decoder, obu).map_err(|e| e.at(obu_index, 0))?;
+        }
+
+        if temporal_unit.frames.last().is_some_and(|frame| !frame.is_complete()) {
+            return Err(Error::ConformanceViolation {
+                requirement: "all tile groups of a frame are in the temporal unit of its frame header",
+            }
+            .at(self.obu_index - 1, 0));
+        }
+        Ok(temporal_unit)
+    }
+}
+
+impl<'a, I> Iterator for TemporalUnits<'a, I>
+where
+    I: Iterator<Item = Result<OBU<'a>, Error>>,
+{
+    type Item = Result<TemporalUnit<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let first = match self.pending.take() {
+            Some(obu) => Ok((self.obu_index - 1, obu)),
+            None => self.next_obu()?,
+        };
+        let temporal_unit = first.and_then(|(obu_index, obu)| self.temporal_unit(obu_index, obu));
+
+        // The decoder state is unknown after an error, so there is no unit to resume at
+        if temporal_unit.is_err() {
+            self.done = true;
+        }
+        Some(temporal_unit)
+    }
+}
+
+impl<'a> TemporalUnit<'a> {
+    // Decodes one OBU and files it under the unit, errors without a location are placed at the OBU header
+    fn push(&mut self, decoder: &mut Decoder, obu: OBU<'a>) -> Result<(), Error> {
+        match decoder.decode_obu(&obu)? {
+            DecodedObu::Dropped(_) => return Ok(()),
+            DecodedObu::SequenceHeader { new_coded_video_sequence } => {
+                if !self.frames.is_empty() {
+                    return Err(Error::ConformanceViolation {
+                        requirement: "sequence headers precede the first frame header of a temporal unit",
+                    });
+                }
+                self.sequence_header = decoder.sequence_header().cloned();
+                self.new_coded_video_sequence |= new_coded_video_sequence;
+            }
+            DecodedObu::FrameHeader => self.push_frame_header(decoder)?,
+            DecodedObu::Frame(tile_group) => {
+                self.push_frame_header(decoder)?;
+                self.push_tile_group(tile_group)?;
+            }
+            DecodedObu::TileGroup(tile_group) => self.push_tile_group(tile_group)?,
+            DecodedObu::Metadata(metadata) => self.metadata.push(metadata),
+            DecodedObu::TemporalDelimiter | DecodedObu::FrameHeaderCopy | DecodedObu::Padding | DecodedObu::Skipped(_) => {}
+        }
+
+        // Frame header copies and tile groups count towards the frame in front of them
+        if let Some(frame) = self.frames.last_mut() {
+            frame.size += obu.total_size();
+        }
+        self.obus.push(obu);
+        Ok(())
+    }
+
+    fn push_frame_header(&mut self, decoder: &Decoder) -> Result<(), Error> {
+        if self.frames.last().is_some_and(|frame| !frame.is_complete()) {
+            return Err(Error::ConformanceViolation {
+                requirement: "all tile groups of a frame precede the next frame header",
+            });
+        }
+
+        if let Some(frame_header) = decoder.frame_header() {
+            self.frames.push(CodedFrame {
+                frame_header: frame_header.clone(),
+                tile_groups: Vec::new(),
+                size: 0,
+            });
+        }
+        Ok(())
+    }
+
+    // 6.10.1 tg_start is equal to the tg_end of the previous tile group of the frame plus 1
+    fn push_tile_group(&mut self, tile_group: TileGroup<'a>) -> Result<(), Error> {
+        let frame = self
+            .frames
+            .last_mut()
+            .filter(|frame| !frame.is_complete())
+            .ok_or(Error::ConformanceViolation {
+                requirement: "a frame header precedes each tile group",
+            })?;
+
+        if tile_group.tg_start() != frame.next_tile() {
+            return Err(Error::ConformanceViolation {
+                requirement: "tg_start follows the tg_end of the previous tile group",
+            });
+        }
+        frame.tile_groups.push(tile_group);
+        Ok(())
+    }
+
+    pub fn obus(&self) -> &[OBU<'a>] {
+        &self.obus
+    }
+
+    pub fn sequence_header(&self) -> Option<&OBU_Sequence_Header> {
+        self.sequence_header.as_ref()
+    }
+
+    pub fn new_coded_video_sequence(&self) -> bool {
+        self.new_coded_video_sequence
+    }
+
+    pub fn metadata(&self) -> &[OBU_Metadata] {
+        &self.metadata
+    }
+
+    pub fn frames(&self) -> &[CodedFrame<'a>] {
+        &self.frames
+    }
+
+    // Frames output by the unit, shown frames and show_existing_frame headers
+    pub fn shown_frames(&self) -> impl Iterator<Item = &CodedFrame<'a>> {
+        self.frames.iter().filter(|frame| frame.is_shown())
+    }
+}
+
+impl<'a> CodedFrame<'a> {
+    pub fn frame_header(&self) -> &FrameHeader {
+        &self.frame_header
+    }
+
+    pub fn tile_groups(&self) -> &[TileGroup<'a>] {
+        &self.tile_groups
+    }
+
+    // Bytes from its frame header OBU up to the next frame header, OBUs in front of the first one are not included
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_shown(&self) -> bool {
+        self.frame_header.show_existing_frame() == 1 || self.frame_header.show_frame() == 1
+    }
+
+    // A show_existing_frame header has no tile groups
+    pub fn is_complete(&self) -> bool {
+        self.frame_header.show_existing_frame() == 1 || self.next_tile() == self.frame_header.tile_info().num_tiles()
+    }
+
+    fn next_tile(&self) -> u32 {
+        self.tile_groups.last().map_or(0, |tile_group| tile_group.tg_end() + 1)
+    }
+}
@@ -0,0 +1,31 @@
+mod impls;
+
+use crate::{decoder::Decoder, frame_header::FrameHeader, metadata::OBU_Metadata, obu::OBU_Sequence_Header, tile_group::TileGroup, OBU};
+
+// 7.5 All OBUs from one temporal delimiter up to the next
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct TemporalUnit<'a> {
+    obus: Vec<OBU<'a>>,                             // OBUs of the chosen operating point in bitstream order
+    sequence_header: Option<OBU_Sequence_Header>,   // last sequence header inside the unit
+    new_coded_video_sequence: bool,                 // a sequence header of the unit changed the active one
+    metadata: Vec<OBU_Metadata>,
+    frames: Vec<CodedFrame<'a>>,                    // in decoding order
+}
+
+// A frame header and the tile groups that carry its tiles
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CodedFrame<'a> {
+    frame_header: FrameHeader,
+    tile_groups: Vec<TileGroup<'a>>,
+    size: usize,                                    // bytes of its frame header OBU and the OBUs up to the next one
+}
+
+// Groups the OBUs of a stream into temporal units, works on ObuReader and AnnexB_OBUs alike
+#[derive(Debug, Clone)]
+pub struct TemporalUnits<'a, I> {
+    obus: I,
+    decoder: Decoder,
+    pending: Option<OBU<'a>>,  // temporal delimiter that ended the previous unit
+    obu_index: usize,          // OBUs taken from `obus` so far
+    done: bool,                // set after the stream ended or an error
+}
@@ -0,0 +1,50 @@
+use super::{Tile, TileGroup};
+
+impl<'a> TileGroup<'a> {
+    pub fn tile_start_and_end_present_flag(&self) -> u8 {
+        self.tile_start_and_end_present_flag
+    }
+
+    pub fn tg_start(&self) -> u32 {
+        self.tg_start
+    }
+
+    pub fn tg_end(&self) -> u32 {
+        self.tg_end
+    }
+
+    pub fn tiles(&self) -> &[Tile<'a>] {
+        &self.tiles
+    }
+
+    pub fn num_tiles(&self) -> u32 {
+        self.tiles.len() as u32
+    }
+
+    // Coded tile bytes, not counting the tile size fields
+    pub fn tile_data_size(&self) -> usize {
+        self.tiles.iter().map(|tile| tile.data.len()).sum()
+    }
+}
+
+impl<'a> Tile<'a> {
+    pub fn tile_num(&self) -> u32 {
+        self.tile_num
+    }
+
+    pub fn tile_row(&self) -> u32 {
+        self.tile_row
+    }
+
+    pub fn tile_col(&self) -> u32 {
+        self.tile_col
+    }
+
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+}
@@ -0,0 +1,75 @@
+use std::io::Cursor;
+
+use bitstream_io::{BigEndian, BitRead, BitReader};
+
+use crate::{frame_header::Tile_Info, generics::byte_alignment, Error};
+
+use super::{Tile, TileGroup};
+
+impl<'a> TileGroup<'a> {
+
+    // 5.11.1 General tile group OBU syntax, data starts at the tile group and runs to the end of the OBU
+    pub fn tile_group_obu(data: &'a [u8], tile_info: &Tile_Info) -> Result<Self, Error> {
+        let mut r = BitReader::endian(Cursor::new(data), BigEndian);
+
+        let num_tiles = tile_info.num_tiles();
+        let mut tile_start_and_end_present_flag = 0u8;
+        if num_tiles > 1 {
+            tile_start_and_end_present_flag = r.read::<1, u8>()?;
+        }
+
+        let (tg_start, tg_end) = if num_tiles == 1 || tile_start_and_end_present_flag == 0 {
+            (0, num_tiles - 1)
+        } else {
+            let tile_bits = tile_info.tile_cols_log2() + tile_info.tile_rows_log2();
+            (r.read_var::<u32>(tile_bits)?, r.read_var::<u32>(tile_bits)?)
+        };
+        if tg_start > tg_end || tg_end >= num_tiles {
+            return Err(Error::InvalidSyntax { element: "tg_end", value: tg_end as u64 });
+        }
+
+        byte_alignment(&mut r)?;
+        let mut position = (r.position_in_bits()? / 8) as usize;
+
+        let tile_size_bytes = tile_info.tile_size_bytes() as usize;
+        let tile_cols = tile_info.tile_cols();
+        let mut tiles = Vec::with_capacity((tg_end - tg_start + 1) as usize);
+        for tile_num in tg_start..=tg_end {
+            let tile_size = if tile_num == tg_end {
+                data.len() - position
+            } else {
+                // tile_size_minus_1, le(TileSizeBytes)
+                let size_field = data
+                    .get(position..position + tile_size_bytes)
+                    .ok_or(Error::UnexpectedEof)?;
+                position += tile_size_bytes;
+                size_field.iter().rev().fold(0usize, |size, &byte| (size << 8) | byte as usize) + 1
+            };
+
+            let tile_data = data
+                .get(position..position + tile_size)
+                .ok_or(Error::UnexpectedEof)?;
+            position += tile_size;
+
+            tiles.push(Tile::new(tile_num, tile_cols, tile_data));
+        }
+
+        Ok(TileGroup {
+            tile_start_and_end_present_flag,
+            tg_start,
+            tg_end,
+            tiles,
+        })
+    }
+}
+
+impl<'a> Tile<'a> {
+    pub(crate) fn new(tile_num: u32, tile_cols: u32, data: &'a [u8]) -> Self {
+        Self {
+            tile_num,
+            tile_row: tile_num / tile_cols,
+            tile_col: tile_num % tile_cols,
+            data,
+        }
+    }
+}
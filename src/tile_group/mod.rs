@@ -0,0 +1,20 @@
+mod impls;
+mod accessors;
+
+// 5.11.1 General tile group OBU syntax
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TileGroup<'a> {
+    tile_start_and_end_present_flag: u8, // 1 bit
+    tg_start: u32,                       // tileBits
+    tg_end: u32,                         // tileBits
+    tiles: Vec<Tile<'a>>,
+}
+
+// Coded data of one tile, borrowed from the OBU payload
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Tile<'a> {
+    tile_num: u32,
+    tile_row: u32,
+    tile_col: u32,
+    data: &'a [u8],                      // tileSize bytes
+}
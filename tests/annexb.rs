@@ -0,0 +1,98 @@
+mod common;
+
+use common::{hex, BASIC_1080P};
+use rust_av1_dec::{detect_format, AnnexBReader, BitstreamFormat, Error, OBU_TYPE};
+
+// Two temporal units, the first with a padded 2 byte temporal_unit_size:
+// [ frame unit [ TD, sequence header ] ], [ frame unit [ padding ], frame unit [ padding with obu_size ] ]
+fn stream() -> Vec<u8> {
+    let mut stream = vec![0x90, 0x00]; // temporal_unit_size 16
+    stream.push(15); // frame_unit_size
+    stream.extend([1, 0x10]); // obu_length, TD without obu_size
+    stream.push(12); // obu_length
+    stream.push(0x08); // sequence header without obu_size
+    stream.extend(hex(BASIC_1080P));
+
+    stream.push(10); // temporal_unit_size
+    stream.extend([4, 3, 0x78, 0xaa, 0xbb]);
+    stream.extend([4, 3, 0x7a, 0x01, 0xcc]);
+    stream
+}
+
+#[test]
+fn temporal_and_frame_unit_sizes() {
+    let stream = stream();
+    assert_eq!(detect_format(&stream), BitstreamFormat::AnnexB);
+
+    let mut reader = AnnexBReader::new(&stream);
+    let temporal_unit = reader.next().unwrap().unwrap();
+    assert_eq!(temporal_unit.data(), &stream[2..18]);
+    assert_eq!(reader.position(), 18);
+
+    let frame_units: Vec<_> = temporal_unit.frame_units().map(Result::unwrap).collect();
+    assert_eq!(frame_units.len(), 1);
+    let obus: Vec<_> = frame_units[0].obus().map(Result::unwrap).collect();
+    assert_eq!(obus.len(), 2);
+    assert_eq!(obus[0].obu_type(), OBU_TYPE::OBU_TEMPORAL_DELIMITER);
+    assert_eq!(obus[0].obu_size(), 0);
+    assert_eq!(obus[1].obu_type(), OBU_TYPE::OBU_SEQUENCE_HEADER);
+    assert_eq!(obus[1].obu_header().obu_has_size_field(), 0);
+    // obu_size is obu_length minus the header
+    assert_eq!(obus[1].obu_size(), 11);
+    assert_eq!(obus[1].payload(), hex(BASIC_1080P));
+
+    let temporal_unit = reader.next().unwrap().unwrap();
+    let frame_units: Vec<_> = temporal_unit.frame_units().map(Result::unwrap).collect();
+    assert_eq!(frame_units.len(), 2);
+    for frame_unit in &frame_units {
+        let obus: Vec<_> = frame_unit.obus().map(Result::unwrap).collect();
+        assert_eq!(obus.len(), 1);
+        assert_eq!(obus[0].obu_type(), OBU_TYPE::OBU_PADDING);
+    }
+    assert_eq!(frame_units[0].obus().next().unwrap().unwrap().payload(), [0xaa, 0xbb]);
+    assert_eq!(frame_units[1].obus().next().unwrap().unwrap().payload(), [0xcc]);
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn sizes_past_the_end() {
+    // temporal_unit_size larger than the data left
+    let mut stream = stream();
+    stream.extend([20, 4, 3, 0x78]);
+    let mut reader = AnnexBReader::new(&stream);
+    assert!(reader.nth(2).unwrap().is_err_and(|e| matches!(e, Error::UnexpectedEof)));
+    assert!(reader.next().is_none());
+
+    // frame_unit_size larger than its temporal unit
+    let stream = [3, 5, 1, 0x10];
+    let temporal_unit = AnnexBReader::new(&stream).next().unwrap().unwrap();
+    assert!(temporal_unit.frame_units().next().unwrap().is_err());
+
+    // obu_length shorter than the OBU header, the error names the OBU
+    let stream = [4, 3, 1, 0x14, 0x00];
+    let temporal_unit = AnnexBReader::new(&stream).next().unwrap().unwrap();
+    let frame_unit = temporal_unit.frame_units().next().unwrap().unwrap();
+    let mut obus = frame_unit.obus();
+    let error = obus.next().unwrap().unwrap_err();
+    assert_eq!(error.obu_index(), Some(0));
+    assert!(matches!(error.kind(), Error::UnexpectedEof));
+
+    // obu_size 3 of the second OBU runs past its obu_length of 4
+    let stream = [10, 9, 3, 0x7a, 0x01, 0xcc, 4, 0x7a, 0x03, 0xaa, 0xbb];
+    let temporal_unit = AnnexBReader::new(&stream).next().unwrap().unwrap();
+    let frame_unit = temporal_unit.frame_units().next().unwrap().unwrap();
+    let mut obus = frame_unit.obus();
+    assert_eq!(obus.next().unwrap().unwrap().payload(), [0xcc]);
+    let error = obus.next().unwrap().unwrap_err();
+    assert_eq!((error.obu_index(), error.bit_offset()), (Some(1), Some(16)));
+    assert!(matches!(error.kind(), Error::ConformanceViolation { requirement: "obu_size fits within obu_length" }));
+}
+
+#[test]
+fn detects_low_overhead_streams() {
+    // TD with obu_size followed by the sequence header OBU
+    let mut stream = hex("12000a0b");
+    stream.extend(hex(BASIC_1080P));
+    assert_eq!(detect_format(&stream), BitstreamFormat::LowOverhead);
+    assert_eq!(detect_format(&[]), BitstreamFormat::LowOverhead);
+}
@@ -0,0 +1,199 @@
+use std::io::Cursor;
+
+use bitstream_io::{BigEndian, BitReader, BitWrite, BitWriter, ToBitStream};
+use rust_av1_dec::{AV1CodecConfigurationRecord, OperatingPoint, OBU_Sequence_Header};
+
+// seq_profile, high_bitdepth, twelve_bit, BitDepth
+const BIT_DEPTHS: &[(u8, u8, Option<u8>, u8)] = &[
+    (0, 0, None, 8),
+    (0, 1, None, 10),
+    (1, 0, None, 8),
+    (1, 1, None, 10),
+    (2, 0, None, 8),
+    (2, 1, Some(0), 10),
+    (2, 1, Some(1), 12),
+];
+
+// color_primaries, transfer_characteristics, matrix_coefficients when color_description_present_flag is 1
+const COLOR_DESCRIPTIONS: &[Option<(u8, u8, u8)>] = &[
+    None,
+    Some((9, 16, 9)),  // BT.2020 PQ
+    Some((1, 13, 0)),  // sRGB with identity matrix, implies 4:4:4 full range
+];
+
+#[derive(Debug)]
+struct Case {
+    seq_profile: u8,
+    high_bitdepth: u8,
+    twelve_bit: Option<u8>,
+    bit_depth: u8,
+    mono_chrome: u8,
+    color_description: Option<(u8, u8, u8)>,
+    subsampling: (u8, u8),
+}
+
+impl Case {
+    fn srgb(&self) -> bool {
+        self.mono_chrome == 0 && self.color_description == Some((1, 13, 0))
+    }
+
+    fn chroma_sample_position(&self) -> u8 {
+        if self.mono_chrome == 0 && self.subsampling == (1, 1) { 2 } else { 0 }
+    }
+
+    fn color_range(&self) -> u8 {
+        if self.srgb() { 1 } else { 0 }
+    }
+
+    fn separate_uv_delta_q(&self) -> u8 {
+        if self.mono_chrome == 0 { 1 } else { 0 }
+    }
+}
+
+// Every profile x bit depth x mono_chrome x color description x subsampling allowed by 5.5.2
+fn cases() -> Vec<Case> {
+    let mut cases = Vec::new();
+    for &(seq_profile, high_bitdepth, twelve_bit, bit_depth) in BIT_DEPTHS {
+        let mono_chromes: &[u8] = if seq_profile == 1 { &[0] } else { &[0, 1] };
+        for &mono_chrome in mono_chromes {
+            for &color_description in COLOR_DESCRIPTIONS {
+                let subsamplings: &[(u8, u8)] = match seq_profile {
+                    _ if mono_chrome == 1 => &[(1, 1)],
+                    _ if color_description == Some((1, 13, 0)) => &[(0, 0)],
+                    0 => &[(1, 1)],
+                    1 => &[(0, 0)],
+                    _ if bit_depth == 12 => &[(0, 0), (1, 0), (1, 1)],
+                    _ => &[(1, 0)],
+                };
+                for &subsampling in subsamplings {
+                    cases.push(Case {
+                        seq_profile,
+                        high_bitdepth,
+                        twelve_bit,
+                        bit_depth,
+                        mono_chrome,
+                        color_description,
+                        subsampling,
+                    });
+                }
+            }
+        }
+    }
+    cases
+}
+
+// Reduced still picture sequence header around the color config, returns the payload and the bits before trailing_bits( )
+fn sequence_header(case: &Case) -> (Vec<u8>, u64) {
+    let mut w = BitWriter::endian(Vec::new(), BigEndian);
+    let mut bits = 0u64;
+    let mut f = |n: u32, value: u32| {
+        w.write_var(n, value).unwrap();
+        bits += n as u64;
+    };
+
+    f(3, case.seq_profile as u32);
+    f(1, 1); // still_picture
+    f(1, 1); // reduced_still_picture_header
+    f(5, 8); // seq_level_idx
+    f(4, 10); // frame_width_bits_minus_1
+    f(4, 10); // frame_height_bits_minus_1
+    f(11, 1919);
+    f(11, 1079);
+    f(1, 0); // use_128x128_superblock
+    f(1, 1); // enable_filter_intra
+    f(1, 1); // enable_intra_edge_filter
+    f(1, 0); // enable_superres
+    f(1, 1); // enable_cdef
+    f(1, 1); // enable_restoration
+
+    // 5.5.2 Color config syntax
+    f(1, case.high_bitdepth as u32);
+    if let Some(twelve_bit) = case.twelve_bit {
+        f(1, twelve_bit as u32);
+    }
+    if case.seq_profile != 1 {
+        f(1, case.mono_chrome as u32);
+    }
+    f(1, case.color_description.is_some() as u32);
+    if let Some((color_primaries, transfer_characteristics, matrix_coefficients)) = case.color_description {
+        f(8, color_primaries as u32);
+        f(8, transfer_characteristics as u32);
+        f(8, matrix_coefficients as u32);
+    }
+    if case.mono_chrome == 1 {
+        f(1, case.color_range() as u32);
+    } else {
+        if !case.srgb() {
+            f(1, case.color_range() as u32);
+            if case.bit_depth == 12 && case.seq_profile == 2 {
+                f(1, case.subsampling.0 as u32);
+                if case.subsampling.0 == 1 {
+                    f(1, case.subsampling.1 as u32);
+                }
+            }
+            if case.subsampling == (1, 1) {
+                f(2, case.chroma_sample_position() as u32);
+            }
+        }
+        f(1, case.separate_uv_delta_q() as u32);
+    }
+
+    f(1, 1); // film_grain_params_present
+    w.write_bit(true).unwrap();
+    w.byte_align().unwrap();
+    (w.into_writer(), bits)
+}
+
+#[test]
+fn color_config_table() {
+    let cases = cases();
+    assert_eq!(cases.len(), 40);
+
+    for case in &cases {
+        let (payload, bits) = sequence_header(case);
+        let mut r = BitReader::endian(Cursor::new(&payload), BigEndian);
+        let sequence_header = OBU_Sequence_Header::sequence_header_obu(&mut r, &OperatingPoint::default()).unwrap();
+        assert_eq!(r.position_in_bits().unwrap(), bits, "{case:?}");
+        assert_eq!(sequence_header.film_grain_params_present(), 1, "{case:?}");
+
+        let color_config = sequence_header.color_config();
+        assert_eq!(color_config.high_bitdepth(), case.high_bitdepth, "{case:?}");
+        assert_eq!(color_config.bit_depth(), case.bit_depth, "{case:?}");
+        assert_eq!(color_config.mono_chrome(), case.mono_chrome, "{case:?}");
+        assert_eq!(color_config.num_planes(), if case.mono_chrome == 1 { 1 } else { 3 }, "{case:?}");
+        assert_eq!(color_config.color_description_present_flag(), case.color_description.is_some() as u8, "{case:?}");
+        let (color_primaries, transfer_characteristics, matrix_coefficients) = case.color_description.unwrap_or((2, 2, 2));
+        assert_eq!(u8::from(color_config.color_primaries()), color_primaries, "{case:?}");
+        assert_eq!(u8::from(color_config.transfer_characteristics()), transfer_characteristics, "{case:?}");
+        assert_eq!(u8::from(color_config.matrix_coefficients()), matrix_coefficients, "{case:?}");
+        assert_eq!(color_config.color_range(), case.color_range(), "{case:?}");
+        assert_eq!((color_config.subsampling_x(), color_config.subsampling_y()), case.subsampling, "{case:?}");
+        assert_eq!(color_config.chroma_sample_position() as u8, case.chroma_sample_position(), "{case:?}");
+        assert_eq!(color_config.separate_uv_delta_q(), case.separate_uv_delta_q(), "{case:?}");
+
+        let mut w = BitWriter::endian(Vec::new(), BigEndian);
+        sequence_header.to_writer(&mut w).unwrap();
+        w.write_bit(true).unwrap();
+        w.byte_align().unwrap();
+        assert_eq!(w.into_writer(), payload, "{case:?}");
+
+        let codec_string = format!(
+            "av01.{}.08M.{:02}.{}.{}{}{}.{:02}.{:02}.{:02}.{}",
+            case.seq_profile,
+            case.bit_depth,
+            case.mono_chrome,
+            case.subsampling.0,
+            case.subsampling.1,
+            case.chroma_sample_position(),
+            color_primaries,
+            transfer_characteristics,
+            matrix_coefficients,
+            case.color_range(),
+        );
+        assert_eq!(sequence_header.codec_string(), codec_string, "{case:?}");
+
+        let record = AV1CodecConfigurationRecord::from_sequence_header(&sequence_header).unwrap();
+        assert_eq!(record.bit_depth(), case.bit_depth, "{case:?}");
+        assert_eq!(record.twelve_bit(), case.twelve_bit.unwrap_or(0), "{case:?}");
+    }
+}
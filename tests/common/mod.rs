@@ -0,0 +1,89 @@
+// Bitstream builders shared by the integration tests, each test crate uses a part of them
+#![allow(dead_code)]
+
+// Sequence header OBU payload of basic_1080p in round_trip.rs: 8-bit 4:2:0, 64x64 superblocks,
+// 7 order hint bits, every inter tool enabled and screen content tools selected per frame
+pub const BASIC_1080P: &str = "00000042abbfc373ffe601";
+
+pub fn hex(s: &str) -> Vec<u8> {
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+// f(n) writer for OBU payloads
+#[derive(Default)]
+pub struct Bits {
+    pub bits: Vec<bool>,
+}
+
+impl Bits {
+    pub fn f(&mut self, n: u32, value: u32) -> &mut Self {
+        for i in (0..n).rev() {
+            self.bits.push((value >> i) & 1 == 1);
+        }
+        self
+    }
+
+    // Zero bits up to the next byte
+    pub fn bytes(&self) -> Vec<u8> {
+        self.bits
+            .chunks(8)
+            .map(|chunk| chunk.iter().enumerate().fold(0u8, |byte, (i, bit)| byte | ((*bit as u8) << (7 - i))))
+            .collect()
+    }
+
+    // 5.3.4 trailing_bits( )
+    pub fn trailing_bits(&mut self) -> Vec<u8> {
+        self.f(1, 1);
+        self.bytes()
+    }
+}
+
+// OBU header byte, obu_size in leb128 and the payload
+pub fn obu(header: u8, payload: &[u8]) -> Vec<u8> {
+    let mut data = vec![header];
+    let mut size = payload.len();
+    while size >= 0x80 {
+        data.push(0x80 | (size & 0x7f) as u8);
+        size >>= 7;
+    }
+    data.push(size as u8);
+    data.extend(payload);
+    data
+}
+
+pub fn temporal_delimiter() -> Vec<u8> {
+    obu(0x12, &[])
+}
+
+// Shown key frame of BASIC_1080P at order_hint 0, 1 << tile_cols_log2 uniformly spaced tile columns
+// in one tile row with one byte tile_size_minus_1
+pub fn key_frame_header(tile_cols_log2: u32) -> Bits {
+    let mut bits = Bits::default();
+    bits.f(1, 0).f(2, 0).f(1, 1); // show_existing_frame, frame_type, show_frame
+    bits.f(1, 0).f(1, 0).f(1, 0); // disable_cdf_update, allow_screen_content_tools, frame_size_override_flag
+    bits.f(7, 0); // order_hint
+    bits.f(1, 0).f(1, 0); // render_and_frame_size_different, disable_frame_end_update_cdf
+    bits.f(1, 1); // uniform_tile_spacing_flag
+    for _ in 0..tile_cols_log2 {
+        bits.f(1, 1); // increment_tile_cols_log2
+    }
+    bits.f(1, 0).f(1, 0); // increment_tile_cols_log2, increment_tile_rows_log2
+    if tile_cols_log2 > 0 {
+        bits.f(tile_cols_log2, 0).f(2, 0); // context_update_tile_id, tile_size_bytes_minus_1
+    }
+    bits.f(8, 100).f(1, 0).f(1, 0).f(1, 0).f(1, 0); // base_q_idx, delta_coded x 3, using_qmatrix
+    bits.f(1, 0).f(1, 0); // segmentation_enabled, delta_q_present
+    bits.f(6, 0).f(6, 0).f(3, 0).f(1, 0); // loop_filter_level[ 0 ], [ 1 ], loop_filter_sharpness, loop_filter_delta_enabled
+    bits.f(2, 0).f(2, 0).f(4, 0).f(2, 0).f(4, 0).f(2, 0); // cdef_damping_minus_3, cdef_bits, strengths
+    bits.f(2, 0).f(2, 0).f(2, 0); // lr_type
+    bits.f(1, 0).f(1, 0); // tx_mode_select, reduced_tx_set
+    bits
+}
+
+// Tile group OBU with tile_start_and_end_present_flag 1, tg_start and tg_end of one bit,
+// then the last tile without a size
+pub fn tile_group(tg_start: u32, tg_end: u32, tile: &[u8]) -> Vec<u8> {
+    let mut payload = Bits::default().f(1, 1).f(1, tg_start).f(1, tg_end).bytes();
+    payload.extend(tile);
+    obu(0x22, &payload)
+}
@@ -0,0 +1,240 @@
+mod common;
+
+use common::{hex, key_frame_header, obu, temporal_delimiter, tile_group, Bits, BASIC_1080P};
+use rust_av1_dec::{DecodedObu, Decoder, Error, OperatingPoint, OBU, OBU_TYPE};
+
+const FRAME: u8 = 0x32;
+
+// Sequence header OBU payload of round_trip.rs with three operating points, operating_point_idc 0x103, 0x101 and 0x100
+const TIMING_DECODER_MODEL: &str = "0400000fa40003a983a400000fa6948840d3be95e66202504009190c82abbfc373ffe601";
+
+fn decode<'a>(decoder: &mut Decoder, data: &'a [u8]) -> DecodedObu<'a> {
+    decoder.decode_obu(&OBU::open_bitstream_unit(data).unwrap()).unwrap()
+}
+
+#[test]
+fn repeated_sequence_header() {
+    let mut decoder = Decoder::new();
+    let basic = obu(0x0a, &hex(BASIC_1080P));
+    assert_eq!(decode(&mut decoder, &basic), DecodedObu::SequenceHeader { new_coded_video_sequence: true });
+    assert_eq!(decode(&mut decoder, &basic), DecodedObu::SequenceHeader { new_coded_video_sequence: false });
+
+    let timing = obu(0x0a, &hex(TIMING_DECODER_MODEL));
+    assert_eq!(decode(&mut decoder, &timing), DecodedObu::SequenceHeader { new_coded_video_sequence: true });
+    assert_eq!(decoder.sequence_header().unwrap().operating_point(), 0);
+
+    // Choosing another operating point does not make the same header a new coded video sequence
+    decoder.set_operating_point(OperatingPoint::Index(2));
+    assert_eq!(decode(&mut decoder, &timing), DecodedObu::SequenceHeader { new_coded_video_sequence: false });
+    let sequence_header = decoder.sequence_header().unwrap();
+    assert_eq!(sequence_header.operating_point(), 2);
+    assert_eq!(sequence_header.c_operating_point_idc(), 0x100);
+}
+
+#[test]
+fn frame_header_copy() {
+    let mut decoder = Decoder::new();
+    decode(&mut decoder, &obu(0x0a, &hex(BASIC_1080P)));
+    let key_frame = obu(0x1a, &key_frame_header(0).trailing_bits());
+    assert_eq!(decode(&mut decoder, &key_frame), DecodedObu::FrameHeader);
+    let frame_header = decoder.frame_header().unwrap().clone();
+
+    // With SeenFrameHeader set, frame header OBUs are copies whatever their payload
+    assert_eq!(decode(&mut decoder, &obu(0x1a, &[0xff, 0xff])), DecodedObu::FrameHeaderCopy);
+    assert_eq!(decode(&mut decoder, &obu(0x3a, &key_frame[2..])), DecodedObu::FrameHeaderCopy);
+    assert_eq!(decoder.frame_header(), Some(&frame_header));
+
+    // A temporal delimiter clears SeenFrameHeader
+    decode(&mut decoder, &temporal_delimiter());
+    assert_eq!(decode(&mut decoder, &key_frame), DecodedObu::FrameHeader);
+}
+
+#[test]
+fn failed_frame_header_keeps_ref_frames() {
+    let mut decoder = Decoder::new();
+    decode(&mut decoder, &obu(0x0a, &hex(BASIC_1080P)));
+    decode(&mut decoder, &obu(0x1a, &key_frame_header(0).trailing_bits()));
+    decode(&mut decoder, &temporal_delimiter());
+    let ref_frames = decoder.ref_frames().clone();
+    assert!((0..8).all(|i| ref_frames.ref_frame(i).ref_valid() == 1 && ref_frames.ref_frame(i).ref_order_hint() == 0));
+
+    // Error resilient inter frame whose ref_order_hint invalidates every slot, cut off right after them
+    let mut bits = Bits::default();
+    bits.f(1, 0).f(2, 1).f(1, 1); // show_existing_frame, frame_type, show_frame
+    bits.f(1, 1); // error_resilient_mode
+    bits.f(1, 0).f(1, 0).f(1, 0); // disable_cdf_update, allow_screen_content_tools, frame_size_override_flag
+    bits.f(7, 3); // order_hint
+    bits.f(8, 0x01); // refresh_frame_flags
+    for _ in 0..8 {
+        bits.f(7, 5); // ref_order_hint[ i ]
+    }
+    let data = obu(0x1a, &bits.bytes());
+    let error = decoder.decode_obu(&OBU::open_bitstream_unit(&data).unwrap()).unwrap_err();
+    assert!(matches!(error.kind(), Error::UnexpectedEof));
+    assert_eq!(decoder.ref_frames(), &ref_frames);
+}
+
+// Decoder after the sequence header and a two tile key frame header
+fn two_tile_frame() -> Decoder {
+    let mut decoder = Decoder::new();
+    decode(&mut decoder, &obu(0x0a, &hex(BASIC_1080P)));
+    let frame_header = obu(0x1a, &key_frame_header(1).trailing_bits());
+    assert_eq!(decode(&mut decoder, &frame_header), DecodedObu::FrameHeader);
+    decoder
+}
+
+#[test]
+fn tile_groups_in_order() {
+    let mut decoder = two_tile_frame();
+    let first = tile_group(0, 0, &[0xaa, 0xab]);
+    let DecodedObu::TileGroup(tile_group_0) = decode(&mut decoder, &first) else {
+        panic!("expected a tile group");
+    };
+    assert_eq!((tile_group_0.tg_start(), tile_group_0.tg_end()), (0, 0));
+    assert_eq!(tile_group_0.tiles()[0].data(), [0xaa, 0xab]);
+
+    let second = tile_group(1, 1, &[0xbb]);
+    let DecodedObu::TileGroup(tile_group_1) = decode(&mut decoder, &second) else {
+        panic!("expected a tile group");
+    };
+    assert_eq!((tile_group_1.tg_start(), tile_group_1.tg_end()), (1, 1));
+    assert_eq!(tile_group_1.tiles()[0].tile_col(), 1);
+
+    // The last tile ended the frame
+    let error = decoder.decode_obu(&OBU::open_bitstream_unit(&second).unwrap()).unwrap_err();
+    assert!(matches!(error.kind(), Error::ConformanceViolation { .. }));
+}
+
+#[test]
+fn tile_group_out_of_order() {
+    let mut decoder = two_tile_frame();
+    let data = tile_group(1, 1, &[0xbb]);
+    let error = decoder.decode_obu(&OBU::open_bitstream_unit(&data).unwrap()).unwrap_err();
+    assert!(matches!(error.kind(), Error::ConformanceViolation { requirement: "tg_start is equal to TileNum" }));
+
+    // A repeated tile group does not start at TileNum either
+    let mut decoder = two_tile_frame();
+    let data = tile_group(0, 0, &[0xaa]);
+    decode(&mut decoder, &data);
+    let error = decoder.decode_obu(&OBU::open_bitstream_unit(&data).unwrap()).unwrap_err();
+    assert!(matches!(error.kind(), Error::ConformanceViolation { requirement: "tg_start is equal to TileNum" }));
+}
+
+#[test]
+fn frame_obu_tile_start_and_end() {
+    let mut decoder = Decoder::new();
+    decode(&mut decoder, &obu(0x0a, &hex(BASIC_1080P)));
+
+    // Frame header, byte_alignment( ), then tile_start_and_end_present_flag 0 and both tiles
+    let mut payload = key_frame_header(1).bytes();
+    payload.push(0x00);
+    payload.extend([0x01, 0xaa, 0xab]); // tile_size_minus_1, tile 0
+    payload.push(0xbb); // tile 1
+    let data = obu(FRAME, &payload);
+    let DecodedObu::Frame(tile_group) = decode(&mut decoder, &data) else {
+        panic!("expected a frame");
+    };
+    assert_eq!(tile_group.num_tiles(), 2);
+    assert_eq!(tile_group.tiles()[0].data(), [0xaa, 0xab]);
+    assert_eq!(tile_group.tiles()[1].data(), [0xbb]);
+
+    let mut payload = key_frame_header(1).bytes();
+    payload.push(0xe0); // tile_start_and_end_present_flag 1, tg_start 1, tg_end 1
+    payload.push(0xbb);
+    decode(&mut decoder, &temporal_delimiter());
+    let data = obu(FRAME, &payload);
+    let error = decoder.decode_obu(&OBU::open_bitstream_unit(&data).unwrap()).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        Error::ConformanceViolation { requirement: "tile_start_and_end_present_flag is 0 in a frame OBU" }
+    ));
+}
+
+#[test]
+fn frame_obu_over_incomplete_frame() {
+    let mut decoder = two_tile_frame();
+    decode(&mut decoder, &tile_group(0, 0, &[0xaa]));
+    let frame_header = decoder.frame_header().unwrap().clone();
+    let ref_frames = decoder.ref_frames().clone();
+
+    // A frame OBU cannot stand in for the missing tile group
+    let mut payload = key_frame_header(0).bytes();
+    payload.push(0xaa);
+    let data = obu(FRAME, &payload);
+    let error = decoder.decode_obu(&OBU::open_bitstream_unit(&data).unwrap()).unwrap_err();
+    assert_eq!(error.obu_index(), Some(3));
+    assert!(matches!(
+        error.kind(),
+        Error::ConformanceViolation { requirement: "all tile groups of a frame precede the next frame header" }
+    ));
+    assert_eq!(decoder.frame_header(), Some(&frame_header));
+    assert_eq!(decoder.ref_frames(), &ref_frames);
+
+    // The pending frame still takes its last tile group
+    assert!(matches!(decode(&mut decoder, &tile_group(1, 1, &[0xbb])), DecodedObu::TileGroup(_)));
+}
+
+#[test]
+fn layer_dropping() {
+    // HDR_CLL metadata OBUs with an extension header for temporal_id 1 and 0
+    let temporal_layer_1 = hex("2e20060103e8019080");
+    let temporal_layer_0 = hex("2e00060103e8019080");
+    let timing = obu(0x0a, &hex(TIMING_DECODER_MODEL));
+
+    // Operating point 0 holds temporal layers 0 and 1
+    let mut decoder = Decoder::new();
+    decode(&mut decoder, &timing);
+    assert!(matches!(decode(&mut decoder, &temporal_layer_1), DecodedObu::Metadata(_)));
+
+    // Operating point 1 only temporal layer 0, the other OBU is dropped without an error
+    let mut decoder = Decoder::new();
+    decoder.set_operating_point(OperatingPoint::Index(1));
+    decode(&mut decoder, &timing);
+    assert_eq!(decode(&mut decoder, &temporal_layer_1), DecodedObu::Dropped(OBU_TYPE::OBU_METADATA));
+    assert!(decoder.metadata().is_empty());
+    assert!(matches!(decode(&mut decoder, &temporal_layer_0), DecodedObu::Metadata(_)));
+    assert_eq!(decoder.metadata().len(), 1);
+}
+
+#[test]
+fn trailing_bits_and_padding() {
+    let mut decoder = Decoder::new();
+
+    // One byte too many between the sequence header and trailing_bits( )
+    let mut payload = hex(BASIC_1080P);
+    payload.insert(payload.len() - 1, 0x00);
+    let data = obu(0x0a, &payload);
+    let error = decoder.decode_obu(&OBU::open_bitstream_unit(&data).unwrap()).unwrap_err();
+    assert_eq!(error.obu_index(), Some(0));
+    assert!(matches!(error.kind(), Error::PayloadSizeMismatch { obu_size: 12, payload_bits: 87 }));
+
+    // A temporal delimiter has an empty payload or nothing but trailing bits
+    decode(&mut decoder, &temporal_delimiter());
+    decode(&mut decoder, &obu(0x12, &[0x80]));
+    let data = obu(0x12, &[0x01, 0x80]);
+    let error = decoder.decode_obu(&OBU::open_bitstream_unit(&data).unwrap()).unwrap_err();
+    assert!(matches!(error.kind(), Error::PayloadSizeMismatch { .. }));
+
+    // Padding OBUs are skipped whatever they carry
+    assert_eq!(decode(&mut decoder, &obu(0x7a, &[0xde, 0xad, 0x00])), DecodedObu::Padding);
+    assert_eq!(decode(&mut decoder, &obu(0x7a, &[])), DecodedObu::Padding);
+}
+
+#[test]
+fn frame_obu_byte_alignment() {
+    let mut decoder = Decoder::new();
+    decode(&mut decoder, &obu(0x0a, &hex(BASIC_1080P)));
+
+    // The 73 bit key frame header leaves 7 alignment bits, the last one set
+    let bits = key_frame_header(0);
+    assert_eq!(bits.bits.len(), 73);
+    let mut payload = bits.bytes();
+    *payload.last_mut().unwrap() |= 0x01;
+    payload.push(0xaa); // tile data
+    let data = obu(FRAME, &payload);
+    let error = decoder.decode_obu(&OBU::open_bitstream_unit(&data).unwrap()).unwrap_err();
+    assert!(matches!(error.kind(), Error::ConformanceViolation { requirement: "byte_alignment( ) bits are zero" }));
+    assert_eq!(error.bit_offset(), Some(16 + 80));
+    // The header was not activated
+    assert!(decoder.frame_header().is_none());
+}
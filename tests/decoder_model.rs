@@ -0,0 +1,207 @@
+mod common;
+
+use common::{hex, obu, temporal_delimiter, Bits, BASIC_1080P};
+use rust_av1_dec::{DecoderModel, Decoder_Model_Violation, ObuReader, TemporalUnit, TemporalUnits};
+
+// Level 2.0 gives a BitRate of 1.5 Mbps and decodes a 256x128 frame in 32768 / 5529600 seconds
+const BIT_RATE: f64 = 1_500_000.0;
+
+impl Bits {
+    // 4.10.3 uvlc( )
+    fn uvlc(&mut self, value: u32) -> &mut Self {
+        let leading_zeros = 31 - (value + 1).leading_zeros();
+        self.f(leading_zeros, 0).f(1, 1).f(leading_zeros, value + 1 - (1 << leading_zeros))
+    }
+}
+
+// 256x128 at level 2.0 with a decoder model for its one operating point, a tick is 1 ms for display and decoding,
+// with buffer delays of 24 bits and removal and presentation times of 16 bits
+fn sequence_header(ticks_per_picture: Option<u32>, decoder_buffer_delay: u32, low_delay_mode_flag: u32) -> Vec<u8> {
+    let mut bits = Bits::default();
+    bits.f(3, 0).f(1, 0).f(1, 0); // seq_profile, still_picture, reduced_still_picture_header
+    bits.f(1, 1).f(32, 1).f(32, 1000); // timing_info_present_flag, num_units_in_display_tick, time_scale
+    bits.f(1, ticks_per_picture.is_some() as u32); // equal_picture_interval
+    if let Some(ticks_per_picture) = ticks_per_picture {
+        bits.uvlc(ticks_per_picture - 1); // num_ticks_per_picture_minus_1
+    }
+    bits.f(1, 1); // decoder_model_info_present_flag
+    bits.f(5, 23).f(32, 1).f(5, 15).f(5, 15); // buffer_delay_length_minus_1, num_units_in_decoding_tick, removal and presentation lengths
+    bits.f(1, 0).f(5, 0).f(12, 0).f(5, 0); // initial_display_delay_present_flag, operating_points_cnt_minus_1, operating_point_idc, seq_level_idx
+    bits.f(1, 1); // decoder_model_present_for_this_op
+    bits.f(24, decoder_buffer_delay).f(24, 0).f(1, low_delay_mode_flag); // encoder_buffer_delay 0
+    bits.f(4, 15).f(4, 15).f(16, 255).f(16, 127); // frame size bits, max_frame_width_minus_1, max_frame_height_minus_1
+    for bit in "001111111111111001100000000".chars() {
+        bits.f(1, (bit == '1') as u32); // coding tools and color_config of BASIC_1080P
+    }
+    obu(0x0a, &bits.trailing_bits())
+}
+
+// Key frame with one tile, frame_presentation_time only without equal_picture_interval,
+// hidden frames refresh every slot
+fn key_frame_header(show_frame: u32, frame_presentation_time: Option<u32>, buffer_removal_time: Option<u32>) -> Bits {
+    let mut bits = Bits::default();
+    bits.f(1, 0).f(2, 0).f(1, show_frame); // show_existing_frame, frame_type, show_frame
+    if let Some(frame_presentation_time) = frame_presentation_time {
+        bits.f(16, frame_presentation_time);
+    }
+    if show_frame == 0 {
+        bits.f(1, 1).f(1, 0); // showable_frame, error_resilient_mode
+    }
+    bits.f(1, 0).f(1, 0).f(1, 0); // disable_cdf_update, allow_screen_content_tools, frame_size_override_flag
+    bits.f(7, 0); // order_hint
+    bits.f(1, buffer_removal_time.is_some() as u32); // buffer_removal_time_present_flag
+    if let Some(buffer_removal_time) = buffer_removal_time {
+        bits.f(16, buffer_removal_time);
+    }
+    if show_frame == 0 {
+        bits.f(8, 0xff); // refresh_frame_flags
+    }
+    bits.f(1, 0).f(1, 0); // render_and_frame_size_different, disable_frame_end_update_cdf
+    bits.f(1, 1).f(1, 0).f(1, 0); // uniform_tile_spacing_flag, increment_tile_cols_log2, increment_tile_rows_log2
+    bits.f(8, 100).f(1, 0).f(1, 0).f(1, 0).f(1, 0); // base_q_idx, delta_coded x 3, using_qmatrix
+    bits.f(1, 0).f(1, 0); // segmentation_enabled, delta_q_present
+    bits.f(6, 0).f(6, 0).f(3, 0).f(1, 0); // loop_filter_level[ 0 ], [ 1 ], loop_filter_sharpness, loop_filter_delta_enabled
+    bits.f(2, 0).f(2, 0).f(4, 0).f(2, 0).f(4, 0).f(2, 0); // cdef_damping_minus_3, cdef_bits, strengths
+    bits.f(2, 0).f(2, 0).f(2, 0); // lr_type
+    bits.f(1, 0).f(1, 0); // tx_mode_select, reduced_tx_set
+    bits
+}
+
+// Shown key frame OBU with tile_size bytes of tile data
+fn frame(frame_presentation_time: Option<u32>, tile_size: usize) -> Vec<u8> {
+    let mut payload = key_frame_header(1, frame_presentation_time, None).bytes();
+    payload.extend(vec![0xaa; tile_size]);
+    obu(0x32, &payload)
+}
+
+fn run(stream: &[u8]) -> (Vec<TemporalUnit<'_>>, DecoderModel) {
+    let temporal_units: Vec<_> = TemporalUnits::new(ObuReader::new(stream)).map(Result::unwrap).collect();
+    let mut decoder_model = DecoderModel::new(temporal_units[0].sequence_header().unwrap()).unwrap();
+    for temporal_unit in &temporal_units {
+        decoder_model.check_temporal_unit(temporal_unit);
+    }
+    (temporal_units, decoder_model)
+}
+
+#[test]
+fn needs_a_decoder_model() {
+    // BASIC_1080P has no decoder model
+    let stream = [temporal_delimiter(), obu(0x0a, &hex(BASIC_1080P))].concat();
+    let temporal_unit = TemporalUnits::new(ObuReader::new(&stream)).next().unwrap().unwrap();
+    assert!(DecoderModel::new(temporal_unit.sequence_header().unwrap()).is_none());
+
+    let stream = [temporal_delimiter(), sequence_header(None, 9000, 0)].concat();
+    let temporal_unit = TemporalUnits::new(ObuReader::new(&stream)).next().unwrap().unwrap();
+    let decoder_model = DecoderModel::new(temporal_unit.sequence_header().unwrap()).unwrap();
+    assert_eq!(decoder_model.operating_point(), 0);
+    assert!(decoder_model.violations().is_empty());
+}
+
+#[test]
+fn smoothing_buffer_underflow() {
+    // 20000 bytes take more than the 0.1 s decoder_buffer_delay to arrive, the copy and the tile group count towards the frame
+    let frame_header_obu = obu(0x1a, &key_frame_header(1, Some(0), Some(0)).trailing_bits());
+    let tile_group = obu(0x22, &vec![0xaa; 20_000]);
+    let stream = |low_delay_mode_flag| {
+        [temporal_delimiter(), sequence_header(None, 9000, low_delay_mode_flag), frame_header_obu.clone(), frame_header_obu.clone(), tile_group.clone()].concat()
+    };
+
+    let stream_0 = stream(0);
+    let (temporal_units, decoder_model) = run(&stream_0);
+    assert_eq!(temporal_units[0].frames().len(), 1);
+    assert_eq!(temporal_units[0].frames()[0].size(), 2 * frame_header_obu.len() + tile_group.len());
+    assert_eq!(
+        decoder_model.violations(),
+        [Decoder_Model_Violation::SmoothingBufferUnderflow {
+            frame: 0,
+            removal_time: 0.1,
+            last_bit_arrival: stream_0.len() as f64 * 8.0 / BIT_RATE,
+        }]
+    );
+
+    // Low delay mode waits for the frame instead
+    let stream_1 = stream(1);
+    assert!(run(&stream_1).1.violations().is_empty());
+}
+
+#[test]
+fn smoothing_buffer_overflow() {
+    // Frame 0 stays in the buffer until its removal at 10 s, frame 1 arrives long before that
+    let first = [temporal_delimiter(), sequence_header(None, 900_000, 0), frame(Some(0), 100_000)].concat();
+    let second = [temporal_delimiter(), frame(Some(100), 100_000)].concat();
+    let stream = [first.as_slice(), &second].concat();
+    let (_, decoder_model) = run(&stream);
+    assert_eq!(
+        decoder_model.violations(),
+        [Decoder_Model_Violation::SmoothingBufferOverflow {
+            frame: 1,
+            bits: stream.len() as f64 * 8.0,
+            buffer_size: BIT_RATE,
+        }]
+    );
+}
+
+#[test]
+fn presentation_too_early() {
+    // frame_presentation_time 0 again is the presentation time of frame 0, before frame 1 decoded
+    for (frame_presentation_time, too_early) in [(0, true), (10, false)] {
+        let stream = [temporal_delimiter(), sequence_header(None, 9000, 0), frame(Some(0), 1), temporal_delimiter(), frame(Some(frame_presentation_time), 1)].concat();
+        let (_, decoder_model) = run(&stream);
+        match decoder_model.violations() {
+            [Decoder_Model_Violation::PresentationTooEarly { frame: 1, presentation_time, decoded_time }] => {
+                assert!(too_early);
+                assert!(presentation_time < decoded_time);
+            }
+            violations => assert!(!too_early && violations.is_empty(), "{violations:?}"),
+        }
+    }
+
+    // One picture per 1 ms is faster than the 5.9 ms each frame takes to decode
+    for (ticks_per_picture, too_early) in [(1, true), (10, false)] {
+        let stream = [temporal_delimiter(), sequence_header(Some(ticks_per_picture), 9000, 0), frame(None, 1), temporal_delimiter(), frame(None, 1)].concat();
+        let (_, decoder_model) = run(&stream);
+        let violations = decoder_model.violations();
+        assert_eq!(matches!(violations, [Decoder_Model_Violation::PresentationTooEarly { frame: 1, .. }]), too_early, "{violations:?}");
+        assert_eq!(violations.is_empty(), !too_early);
+    }
+}
+
+#[test]
+fn decoded_frame_buffer_overflow() {
+    // Frames presented a second apart pile up while waiting, frame 11 needs an eleventh buffer
+    let mut stream = [temporal_delimiter(), sequence_header(None, 9000, 0), frame(Some(0), 1)].concat();
+    for k in 1..12 {
+        stream.extend(temporal_delimiter());
+        stream.extend(frame(Some(1000 * k), 1));
+    }
+    let (_, decoder_model) = run(&stream);
+    assert_eq!(decoder_model.violations(), [Decoder_Model_Violation::DecodedFrameBufferOverflow { frame: 11, frames: 11 }]);
+}
+
+#[test]
+fn show_existing_frame() {
+    // A hidden key frame, shown by the next temporal unit, then a frame presented at the same time
+    let mut hidden = key_frame_header(0, None, None).bytes();
+    hidden.push(0xaa);
+    let show_existing = obu(0x1a, &Bits::default().f(1, 1).f(3, 0).f(16, 0).trailing_bits());
+    let padding = obu(0x7a, &[0; 50]);
+    let stream = [
+        temporal_delimiter(),
+        sequence_header(None, 9000, 0),
+        obu(0x32, &hidden),
+        temporal_delimiter(),
+        show_existing.clone(),
+        padding.clone(),
+        temporal_delimiter(),
+        frame(Some(0), 1),
+    ]
+    .concat();
+
+    let (temporal_units, decoder_model) = run(&stream);
+    let shown = &temporal_units[1].frames()[0];
+    assert_eq!(shown.frame_header().show_existing_frame(), 1);
+    assert_eq!(shown.size(), show_existing.len() + padding.len());
+
+    // The show_existing_frame header counts as frame 1
+    assert!(matches!(decoder_model.violations(), [Decoder_Model_Violation::PresentationTooEarly { frame: 2, .. }]));
+}
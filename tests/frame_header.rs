@@ -0,0 +1,763 @@
+mod common;
+
+use std::io::Cursor;
+
+use bitstream_io::{BigEndian, BitReader};
+use common::{hex, Bits, BASIC_1080P};
+use rust_av1_dec::{
+    Error, FrameHeader, OperatingPoint, RefFrameStore, OBU_Sequence_Header, ALTREF2_FRAME, ALTREF_FRAME, BWDREF_FRAME, FRAME_RESTORATION_TYPE, GM_TYPE,
+    GOLDEN_FRAME, LAST_FRAME, SEG_LVL_ALT_Q, SEG_LVL_REF_FRAME, TX_MODE,
+};
+
+// BASIC_1080P with film_grain_params_present
+const FILM_GRAIN_SEQUENCE_HEADER: &str = "00000042abbfc373ffe603";
+
+fn sequence_header() -> OBU_Sequence_Header {
+    parse_sequence_header(BASIC_1080P)
+}
+
+fn parse_sequence_header(payload: &str) -> OBU_Sequence_Header {
+    let payload = hex(payload);
+    let mut r = BitReader::endian(Cursor::new(&payload), BigEndian);
+    OBU_Sequence_Header::sequence_header_obu(&mut r, &OperatingPoint::default()).unwrap()
+}
+
+// Descriptors and uncompressed header parts on top of f(n)
+impl Bits {
+    // 4.10.6 su(n)
+    fn su(&mut self, n: u32, value: i32) -> &mut Self {
+        self.f(n, (value as u32) & ((1u32 << n) - 1))
+    }
+
+    // 4.10.7 ns(n)
+    fn ns(&mut self, n: u32, value: u32) -> &mut Self {
+        let w = 32 - n.leading_zeros();
+        let m = (1 << w) - n;
+        if value < m {
+            return self.f(w - 1, value);
+        }
+        self.f(w - 1, m + ((value - m) >> 1)).f(1, (value - m) & 1)
+    }
+
+    // 5.9.28 decode_subexp( numSyms ) inverted
+    fn subexp(&mut self, num_syms: u32, value: u32) -> &mut Self {
+        let (mut i, mut mk, k) = (0, 0, 3);
+        loop {
+            let b2 = if i != 0 { k + i - 1 } else { k };
+            let a = 1 << b2;
+            if num_syms <= mk + 3 * a {
+                return self.ns(num_syms - mk, value - mk);
+            }
+            if value >= mk + a {
+                self.f(1, 1); // subexp_more_bits
+                i += 1;
+                mk += a;
+            } else {
+                return self.f(1, 0).f(b2, value - mk);
+            }
+        }
+    }
+
+    // 5.9.25 read_global_param( ) inverted, writes the gm_params value param coded against PrevGmParams prev
+    fn global_param(&mut self, abs_bits: u32, prec_bits: u32, idx: usize, prev: i32, param: i32) -> &mut Self {
+        let prec_diff = 16 - prec_bits;
+        let round = if idx % 3 == 2 { 1 << 16 } else { 0 };
+        let sub = if idx % 3 == 2 { 1 << prec_bits } else { 0 };
+        let (low, high) = (-(1 << abs_bits), (1 << abs_bits) + 1);
+        let reference = (prev >> prec_diff) - sub;
+        let value = (param - round) >> prec_diff;
+
+        // 5.9.26 and 5.9.27 with inverse_recenter( ) undone
+        let (mx, r, x) = ((high - low) as u32, (reference - low) as u32, (value - low) as u32);
+        let recenter = |r: u32, v: u32| match v {
+            _ if v > 2 * r => v,
+            _ if v >= r => (v - r) << 1,
+            _ => ((r - v) << 1) - 1,
+        };
+        let v = if (r << 1) <= mx { recenter(r, x) } else { recenter(mx - 1 - r, mx - 1 - x) };
+        self.subexp(mx, v)
+    }
+
+    // Shown key frame up to and including tile_info( ) of a single tile
+    fn key_frame(&mut self) -> &mut Self {
+        self.f(1, 0); // show_existing_frame
+        self.f(2, 0); // frame_type
+        self.f(1, 1); // show_frame
+        self.f(1, 0); // disable_cdf_update
+        self.f(1, 0); // allow_screen_content_tools
+        self.f(1, 0); // frame_size_override_flag
+        self.f(7, 0); // order_hint
+        self.f(1, 0); // render_and_frame_size_different
+        self.f(1, 0); // disable_frame_end_update_cdf
+        self.tile_info()
+    }
+
+    // Shown inter frame with primary_ref_frame 0, every reference in slot 0
+    fn inter_frame(&mut self, order_hint: u32, refresh_frame_flags: u32) -> &mut Self {
+        self.inter_frame_with_refs(order_hint, refresh_frame_flags, &[0; 7])
+    }
+
+    fn inter_frame_with_refs(&mut self, order_hint: u32, refresh_frame_flags: u32, ref_frame_idx: &[u32; 7]) -> &mut Self {
+        self.inter_frame_start(order_hint, refresh_frame_flags);
+        self.f(1, 0); // frame_refs_short_signaling
+        for idx in ref_frame_idx {
+            self.f(3, *idx);
+        }
+        self.inter_frame_end()
+    }
+
+    // Inter frame up to and including refresh_frame_flags
+    fn inter_frame_start(&mut self, order_hint: u32, refresh_frame_flags: u32) -> &mut Self {
+        self.f(1, 0); // show_existing_frame
+        self.f(2, 1); // frame_type
+        self.f(1, 1); // show_frame
+        self.f(1, 0); // error_resilient_mode
+        self.f(1, 0); // disable_cdf_update
+        self.f(1, 0); // allow_screen_content_tools
+        self.f(1, 0); // frame_size_override_flag
+        self.f(7, order_hint);
+        self.f(3, 0); // primary_ref_frame
+        self.f(8, refresh_frame_flags)
+    }
+
+    // Inter frame after the references up to and including tile_info( )
+    fn inter_frame_end(&mut self) -> &mut Self {
+        self.f(1, 0); // render_and_frame_size_different
+        self.f(1, 1); // allow_high_precision_mv
+        self.f(1, 1); // is_filter_switchable
+        self.f(1, 1); // is_motion_mode_switchable
+        self.f(1, 0); // use_ref_frame_mvs
+        self.f(1, 0); // disable_frame_end_update_cdf
+        self.tile_info()
+    }
+
+    fn tile_info(&mut self) -> &mut Self {
+        self.f(1, 1); // uniform_tile_spacing_flag
+        self.f(1, 0); // increment_tile_cols_log2
+        self.f(1, 0) // increment_tile_rows_log2
+    }
+
+    // quantization_params( ) with only base_q_idx and no quantizer matrices
+    fn base_q_idx(&mut self, base_q_idx: u32) -> &mut Self {
+        self.f(8, base_q_idx);
+        self.f(1, 0); // DeltaQYDc delta_coded
+        self.f(1, 0); // DeltaQUDc delta_coded
+        self.f(1, 0); // DeltaQUAc delta_coded
+        self.f(1, 0) // using_qmatrix
+    }
+
+    // Loop filter levels 0, a single CDEF strength and no loop restoration
+    fn filters(&mut self) -> &mut Self {
+        self.f(6, 0).f(6, 0); // loop_filter_level[ 0 ], loop_filter_level[ 1 ]
+        self.f(3, 0); // loop_filter_sharpness
+        self.f(1, 0); // loop_filter_delta_enabled
+        self.f(2, 0).f(2, 0); // cdef_damping_minus_3, cdef_bits
+        self.f(4, 0).f(2, 0).f(4, 0).f(2, 0); // cdef_y_pri_strength, cdef_y_sec_strength, cdef_uv_pri_strength, cdef_uv_sec_strength
+        self.f(2, 0).f(2, 0).f(2, 0) // lr_type
+    }
+
+    // TX_MODE_LARGEST, single reference prediction and no warped or global motion
+    fn tools(&mut self, inter: bool) -> &mut Self {
+        self.f(1, 0); // tx_mode_select
+        if inter {
+            self.f(1, 0); // reference_select
+            self.f(1, 0); // allow_warped_motion
+        }
+        self.f(1, 0); // reduced_tx_set
+        if inter {
+            for _ in 0..7 {
+                self.f(1, 0); // is_global
+            }
+        }
+        self
+    }
+}
+
+// Parses a frame header and checks that it consumed every written bit
+fn parse(sequence_header: &OBU_Sequence_Header, ref_frames: &mut RefFrameStore, bits: &Bits) -> FrameHeader {
+    let data = bits.bytes();
+    let mut r = BitReader::endian(Cursor::new(&data), BigEndian);
+    let frame_header = FrameHeader::uncompressed_header(&mut r, sequence_header, None, ref_frames).unwrap();
+    assert_eq!(r.position_in_bits().unwrap(), bits.bits.len() as u64);
+    ref_frames.update(&frame_header);
+    frame_header
+}
+
+#[test]
+fn lossless_key_frame() {
+    let sequence_header = sequence_header();
+    let mut ref_frames = RefFrameStore::new();
+    let mut bits = Bits::default();
+    bits.key_frame().base_q_idx(0);
+    bits.f(1, 0); // segmentation_enabled
+    // delta_q_params( ) is not coded with base_q_idx 0, CodedLossless skips every loop filter and tx_mode_select
+    bits.f(1, 0); // reduced_tx_set
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.quantization_params().base_q_idx(), 0);
+    assert_eq!(frame_header.delta_q_params().delta_q_present(), 0);
+    assert_eq!(frame_header.delta_lf_params().delta_lf_present(), 0);
+    assert!(frame_header.lossless(0));
+    assert!(frame_header.coded_lossless());
+    assert!(frame_header.all_lossless());
+    assert_eq!(frame_header.qm_level(0, 0), 15);
+    assert_eq!(frame_header.loop_filter_params().loop_filter_level(), &[0; 4]);
+    assert_eq!(frame_header.loop_filter_params().loop_filter_ref_deltas(), &[1, 0, 0, 0, -1, 0, -1, -1]);
+    assert_eq!(frame_header.cdef_params().cdef_damping(), 3);
+    assert_eq!(frame_header.cdef_params().cdef_y_pri_strength(), &[0]);
+    assert_eq!(frame_header.lr_params().frame_restoration_type(), &[FRAME_RESTORATION_TYPE::RESTORE_NONE; 3]);
+    assert_eq!(frame_header.lr_params().uses_lr(), 0);
+}
+
+#[test]
+fn quantization_segmentation_and_deltas() {
+    let sequence_header = sequence_header();
+    let mut ref_frames = RefFrameStore::new();
+    let mut bits = Bits::default();
+    bits.key_frame();
+
+    // quantization_params( ), separate_uv_delta_q is 0 so V copies U
+    bits.f(8, 100); // base_q_idx
+    bits.f(1, 1).su(7, -5); // DeltaQYDc
+    bits.f(1, 1).su(7, 3); // DeltaQUDc
+    bits.f(1, 0); // DeltaQUAc
+    bits.f(1, 1).f(4, 5).f(4, 7); // using_qmatrix, qm_y, qm_u
+
+    // segmentation_params( ), primary_ref_frame is PRIMARY_REF_NONE so all features are coded
+    bits.f(1, 1); // segmentation_enabled
+    for segment_id in 0..8 {
+        for feature in 0..8 {
+            match (segment_id, feature) {
+                (1, 0) => bits.f(1, 1).su(9, -200),
+                (3, 0) => bits.f(1, 1).su(9, 255),
+                (2, 1) => bits.f(1, 1).su(7, -63),
+                (2, 5) => bits.f(1, 1).f(3, 7),
+                (4, 6) => bits.f(1, 1),
+                _ => bits.f(1, 0),
+            };
+        }
+    }
+
+    bits.f(1, 1).f(2, 2); // delta_q_present, delta_q_res
+    bits.f(1, 1).f(2, 1).f(1, 1); // delta_lf_present, delta_lf_res, delta_lf_multi
+    bits.filters();
+    bits.tools(false);
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    let quantization_params = frame_header.quantization_params();
+    assert_eq!(quantization_params.base_q_idx(), 100);
+    assert_eq!(quantization_params.delta_q_y_dc(), -5);
+    assert_eq!(quantization_params.diff_uv_delta(), 0);
+    assert_eq!((quantization_params.delta_q_u_dc(), quantization_params.delta_q_u_ac()), (3, 0));
+    assert_eq!((quantization_params.delta_q_v_dc(), quantization_params.delta_q_v_ac()), (3, 0));
+    assert_eq!(quantization_params.using_qmatrix(), 1);
+    assert_eq!((quantization_params.qm_y(), quantization_params.qm_u(), quantization_params.qm_v()), (5, 7, 7));
+
+    let segmentation_params = frame_header.segmentation_params();
+    assert_eq!(segmentation_params.segmentation_enabled(), 1);
+    assert_eq!(segmentation_params.segmentation_update_map(), 1);
+    assert_eq!(segmentation_params.segmentation_temporal_update(), 0);
+    assert_eq!(segmentation_params.segmentation_update_data(), 1);
+    assert_eq!(segmentation_params.feature_data(1, SEG_LVL_ALT_Q), -200);
+    assert_eq!(segmentation_params.feature_data(3, SEG_LVL_ALT_Q), 255);
+    assert_eq!(segmentation_params.feature_data(2, 1), -63);
+    assert_eq!(segmentation_params.feature_data(2, SEG_LVL_REF_FRAME), 7);
+    assert!(segmentation_params.feature_active(4, 6));
+    assert!(!segmentation_params.feature_active(0, SEG_LVL_ALT_Q));
+    assert_eq!(segmentation_params.seg_id_pre_skip(), 1);
+    assert_eq!(segmentation_params.last_active_seg_id(), 4);
+
+    assert_eq!(frame_header.delta_q_params().delta_q_present(), 1);
+    assert_eq!(frame_header.delta_q_params().delta_q_res(), 2);
+    assert_eq!(frame_header.delta_lf_params().delta_lf_present(), 1);
+    assert_eq!(frame_header.delta_lf_params().delta_lf_res(), 1);
+    assert_eq!(frame_header.delta_lf_params().delta_lf_multi(), 1);
+
+    // 7.12.2 with and without the block level CurrentQIndex
+    assert_eq!(frame_header.get_qindex(true, 0, 40), 100);
+    assert_eq!(frame_header.get_qindex(false, 0, 40), 40);
+    assert_eq!(frame_header.get_qindex(true, 1, 40), 0);
+    assert_eq!(frame_header.get_qindex(true, 3, 40), 255);
+    assert_eq!(frame_header.get_qindex(false, 3, 40), 255);
+    assert_eq!(frame_header.get_qindex(false, 1, 240), 40);
+
+    // Segment 1 reaches qindex 0 but DeltaQYDc keeps it lossy
+    assert!(!frame_header.lossless(1));
+    assert!(!frame_header.coded_lossless());
+    assert_eq!(frame_header.qm_level(0, 0), 5);
+    assert_eq!(frame_header.qm_level(2, 0), 7);
+}
+
+#[test]
+fn segmentation_loaded_from_primary_ref_frame() {
+    let sequence_header = sequence_header();
+    let mut ref_frames = RefFrameStore::new();
+    let mut bits = Bits::default();
+    bits.key_frame().base_q_idx(60);
+    bits.f(1, 1); // segmentation_enabled
+    for segment_id in 0..8 {
+        for feature in 0..8 {
+            match (segment_id, feature) {
+                (5, 0) => bits.f(1, 1).su(9, -20),
+                _ => bits.f(1, 0),
+            };
+        }
+    }
+    bits.f(1, 0); // delta_q_present
+    bits.filters();
+    bits.tools(false);
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.get_qindex(true, 5, 0), 40);
+    assert_eq!(ref_frames.ref_frame(0).saved_segmentation_params(), frame_header.segmentation_params());
+
+    // segmentation_update_data 0 keeps the features of ref_frame_idx[ primary_ref_frame ]
+    let mut bits = Bits::default();
+    bits.inter_frame(1, 0x01).base_q_idx(80);
+    bits.f(1, 1); // segmentation_enabled
+    bits.f(1, 1).f(1, 1); // segmentation_update_map, segmentation_temporal_update
+    bits.f(1, 0); // segmentation_update_data
+    bits.f(1, 0); // delta_q_present
+    bits.filters();
+    bits.tools(true);
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    let segmentation_params = frame_header.segmentation_params();
+    assert_eq!(frame_header.primary_ref_frame(), 0);
+    assert_eq!(segmentation_params.segmentation_update_map(), 1);
+    assert_eq!(segmentation_params.segmentation_temporal_update(), 1);
+    assert_eq!(segmentation_params.segmentation_update_data(), 0);
+    assert_eq!(segmentation_params.feature_data(5, SEG_LVL_ALT_Q), -20);
+    assert_eq!(segmentation_params.last_active_seg_id(), 5);
+    assert_eq!(frame_header.get_qindex(true, 5, 0), 60);
+
+    // Disabling segmentation clears every feature
+    let mut bits = Bits::default();
+    bits.inter_frame(2, 0x01).base_q_idx(80);
+    bits.f(1, 0); // segmentation_enabled
+    bits.f(1, 0); // delta_q_present
+    bits.filters();
+    bits.tools(true);
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.segmentation_params().feature_data(5, SEG_LVL_ALT_Q), 0);
+    assert_eq!(frame_header.get_qindex(true, 5, 0), 80);
+}
+
+#[test]
+fn loop_filter_cdef_and_lr_params() {
+    let sequence_header = sequence_header();
+    let mut ref_frames = RefFrameStore::new();
+    let mut bits = Bits::default();
+    bits.key_frame().base_q_idx(120);
+    bits.f(1, 0); // segmentation_enabled
+    bits.f(1, 0); // delta_q_present
+
+    // loop_filter_params( ), chroma levels follow the non zero luma levels
+    bits.f(6, 10).f(6, 0).f(6, 4).f(6, 5);
+    bits.f(3, 2); // loop_filter_sharpness
+    bits.f(1, 1).f(1, 1); // loop_filter_delta_enabled, loop_filter_delta_update
+    for i in 0..8 {
+        match i {
+            LAST_FRAME => bits.f(1, 1).su(7, 5),
+            ALTREF_FRAME => bits.f(1, 1).su(7, -64),
+            _ => bits.f(1, 0),
+        };
+    }
+    bits.f(1, 0).f(1, 1).su(7, 63); // loop_filter_mode_deltas
+
+    // cdef_params( )
+    bits.f(2, 3).f(2, 1); // cdef_damping_minus_3, cdef_bits
+    bits.f(4, 15).f(2, 3).f(4, 1).f(2, 2);
+    bits.f(4, 7).f(2, 1).f(4, 0).f(2, 0);
+
+    // lr_params( ), lr_type 1 is RESTORE_SWITCHABLE and 3 is RESTORE_SGRPROJ
+    bits.f(2, 1).f(2, 0).f(2, 3);
+    bits.f(1, 1).f(1, 1); // lr_unit_shift, lr_unit_extra_shift
+    bits.f(1, 1); // lr_uv_shift
+    bits.tools(false);
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    let loop_filter_params = frame_header.loop_filter_params();
+    assert_eq!(loop_filter_params.loop_filter_level(), &[10, 0, 4, 5]);
+    assert_eq!(loop_filter_params.loop_filter_sharpness(), 2);
+    assert_eq!(loop_filter_params.loop_filter_delta_update(), 1);
+    assert_eq!(loop_filter_params.loop_filter_ref_deltas(), &[1, 5, 0, 0, -1, 0, -1, -64]);
+    assert_eq!(loop_filter_params.loop_filter_mode_deltas(), &[0, 63]);
+
+    let cdef_params = frame_header.cdef_params();
+    assert_eq!(cdef_params.cdef_damping(), 6);
+    assert_eq!(cdef_params.cdef_bits(), 1);
+    assert_eq!(cdef_params.cdef_y_pri_strength(), &[15, 7]);
+    assert_eq!(cdef_params.cdef_y_sec_strength(), &[4, 1]);
+    assert_eq!(cdef_params.cdef_uv_pri_strength(), &[1, 0]);
+    assert_eq!(cdef_params.cdef_uv_sec_strength(), &[2, 0]);
+
+    let lr_params = frame_header.lr_params();
+    assert_eq!(
+        lr_params.frame_restoration_type(),
+        &[FRAME_RESTORATION_TYPE::RESTORE_SWITCHABLE, FRAME_RESTORATION_TYPE::RESTORE_NONE, FRAME_RESTORATION_TYPE::RESTORE_SGRPROJ]
+    );
+    assert_eq!((lr_params.uses_lr(), lr_params.uses_chroma_lr()), (1, 1));
+    assert_eq!((lr_params.lr_unit_shift(), lr_params.lr_uv_shift()), (2, 1));
+    assert_eq!(lr_params.loop_restoration_size(), &[256, 128, 128]);
+
+    // The deltas of ref_frame_idx[ primary_ref_frame ] carry over, a zero luma level skips the chroma levels
+    let mut bits = Bits::default();
+    bits.inter_frame(1, 0x00).base_q_idx(120);
+    bits.f(1, 0); // segmentation_enabled
+    bits.f(1, 0); // delta_q_present
+    bits.f(6, 0).f(6, 0).f(3, 0);
+    bits.f(1, 1).f(1, 1); // loop_filter_delta_enabled, loop_filter_delta_update
+    for i in 0..8 {
+        match i {
+            GOLDEN_FRAME => bits.f(1, 1).su(7, 2),
+            _ => bits.f(1, 0),
+        };
+    }
+    bits.f(1, 0).f(1, 0);
+    bits.f(2, 0).f(2, 0).f(4, 0).f(2, 0).f(4, 0).f(2, 0);
+    bits.f(2, 2).f(2, 0).f(2, 0); // lr_type, only luma uses RESTORE_WIENER
+    bits.f(1, 0); // lr_unit_shift
+    bits.tools(true);
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.loop_filter_params().loop_filter_ref_deltas(), &[1, 5, 0, 0, 2, 0, -1, -64]);
+    assert_eq!(frame_header.loop_filter_params().loop_filter_mode_deltas(), &[0, 63]);
+    assert_eq!(frame_header.lr_params().frame_restoration_type()[0], FRAME_RESTORATION_TYPE::RESTORE_WIENER);
+    assert_eq!(frame_header.lr_params().uses_chroma_lr(), 0);
+    assert_eq!(frame_header.lr_params().loop_restoration_size(), &[64, 64, 64]);
+}
+
+#[test]
+fn global_motion_params() {
+    const IDENTITY: [i32; 6] = [0, 0, 1 << 16, 0, 0, 1 << 16];
+    let sequence_header = sequence_header();
+    let mut ref_frames = RefFrameStore::new();
+    let mut bits = Bits::default();
+    bits.key_frame().base_q_idx(100);
+    bits.f(1, 0).f(1, 0).filters();
+    bits.f(1, 1); // tx_mode_select
+    bits.f(1, 1); // reduced_tx_set
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.tx_mode(), TX_MODE::TX_MODE_SELECT);
+    assert_eq!(frame_header.reduced_tx_set(), 1);
+    assert_eq!(frame_header.global_motion_params().gm_params(), &[IDENTITY; 8]);
+    assert_eq!(ref_frames.ref_frame(0).saved_gm_params(), &[IDENTITY; 8]);
+
+    let rotzoom = [7 << 10, -3 << 10, (1 << 16) + 200, -100, 100, (1 << 16) + 200];
+    let translation = [20 << 13, -1 << 13, 1 << 16, 0, 0, 1 << 16];
+    let affine = [0, 5 << 10, (1 << 16) + 2, 4, -4, (1 << 16) - 2];
+
+    let mut bits = Bits::default();
+    bits.inter_frame(1, 0x01).base_q_idx(100);
+    bits.f(1, 0).f(1, 0).filters();
+    bits.f(1, 0); // tx_mode_select
+    bits.f(1, 0); // reference_select
+    bits.f(1, 1); // allow_warped_motion
+    bits.f(1, 0); // reduced_tx_set
+    for reference in LAST_FRAME..=ALTREF_FRAME {
+        match reference {
+            LAST_FRAME => {
+                bits.f(1, 1).f(1, 1); // is_global, is_rot_zoom
+                bits.global_param(12, 15, 2, 1 << 16, rotzoom[2]).global_param(12, 15, 3, 0, rotzoom[3]);
+                bits.global_param(12, 6, 0, 0, rotzoom[0]).global_param(12, 6, 1, 0, rotzoom[1]);
+            }
+            GOLDEN_FRAME => {
+                bits.f(1, 1).f(1, 0).f(1, 1); // is_global, is_rot_zoom, is_translation
+                bits.global_param(9, 3, 0, 0, translation[0]).global_param(9, 3, 1, 0, translation[1]);
+            }
+            ALTREF_FRAME => {
+                bits.f(1, 1).f(1, 0).f(1, 0);
+                for idx in 2..6 {
+                    bits.global_param(12, 15, idx, IDENTITY[idx], affine[idx]);
+                }
+                bits.global_param(12, 6, 0, 0, affine[0]).global_param(12, 6, 1, 0, affine[1]);
+            }
+            _ => {
+                bits.f(1, 0);
+            }
+        }
+    }
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.allow_warped_motion(), 1);
+    let global_motion_params = frame_header.global_motion_params();
+    assert_eq!(global_motion_params.prev_gm_params(), &[IDENTITY; 8]);
+    assert_eq!(global_motion_params.gm_type()[LAST_FRAME], GM_TYPE::ROTZOOM);
+    assert_eq!(global_motion_params.gm_type()[GOLDEN_FRAME], GM_TYPE::TRANSLATION);
+    assert_eq!(global_motion_params.gm_type()[ALTREF_FRAME], GM_TYPE::AFFINE);
+    assert_eq!(global_motion_params.gm_type()[2], GM_TYPE::IDENTITY);
+    assert_eq!(global_motion_params.gm_params()[LAST_FRAME], rotzoom);
+    assert_eq!(global_motion_params.gm_params()[GOLDEN_FRAME], translation);
+    assert_eq!(global_motion_params.gm_params()[ALTREF_FRAME], affine);
+    assert_eq!(global_motion_params.gm_params()[2], IDENTITY);
+    let saved_gm_params = *global_motion_params.gm_params();
+
+    // PrevGmParams come from ref_frame_idx[ primary_ref_frame ], the same model codes relative to it
+    let mut bits = Bits::default();
+    bits.inter_frame(2, 0x00).base_q_idx(100);
+    bits.f(1, 0).f(1, 0).filters();
+    bits.f(1, 0).f(1, 0).f(1, 0).f(1, 0);
+    bits.f(1, 1).f(1, 1);
+    let before = bits.bits.len();
+    bits.global_param(12, 15, 2, rotzoom[2], rotzoom[2]).global_param(12, 15, 3, rotzoom[3], rotzoom[3]);
+    bits.global_param(12, 6, 0, rotzoom[0], rotzoom[0]).global_param(12, 6, 1, rotzoom[1], rotzoom[1]);
+    let rotzoom_bits = bits.bits.len() - before;
+    for _ in LAST_FRAME + 1..=ALTREF_FRAME {
+        bits.f(1, 0); // is_global
+    }
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    let global_motion_params = frame_header.global_motion_params();
+    assert_eq!(global_motion_params.prev_gm_params(), &saved_gm_params);
+    assert_eq!(global_motion_params.gm_params()[LAST_FRAME], rotzoom);
+    assert_eq!(global_motion_params.gm_params()[GOLDEN_FRAME], IDENTITY);
+    // Unchanged parameters take the shortest code, subexp_more_bits and 3 subexp_bits
+    assert_eq!(rotzoom_bits, 4 * 4);
+}
+
+#[test]
+fn references_to_unset_slots() {
+    const IDENTITY: [i32; 6] = [0, 0, 1 << 16, 0, 0, 1 << 16];
+    let sequence_header = sequence_header();
+    let mut ref_frames = RefFrameStore::new();
+    assert_eq!(ref_frames.ref_frame(0).ref_valid(), 0);
+    assert_eq!(ref_frames.ref_frame(0).saved_gm_params(), &[IDENTITY; 8]);
+
+    let uncompressed_header = |bits: &Bits, ref_frames: &mut RefFrameStore| {
+        let data = bits.bytes();
+        let mut r = BitReader::endian(Cursor::new(&data), BigEndian);
+        FrameHeader::uncompressed_header(&mut r, &sequence_header, None, ref_frames)
+    };
+
+    // found_ref would copy the empty frame size of slot 0, which holds no superblocks
+    let mut bits = Bits::default();
+    bits.f(1, 0).f(2, 1).f(1, 1); // show_existing_frame, frame_type, show_frame
+    bits.f(1, 0).f(1, 0).f(1, 0); // error_resilient_mode, disable_cdf_update, allow_screen_content_tools
+    bits.f(1, 1); // frame_size_override_flag
+    bits.f(7, 1).f(3, 0).f(8, 0); // order_hint, primary_ref_frame, refresh_frame_flags
+    bits.f(1, 0); // frame_refs_short_signaling
+    for _ in 0..7 {
+        bits.f(3, 0); // ref_frame_idx
+    }
+    bits.f(1, 1); // found_ref
+    bits.f(8, 0); // superres_params( ) and tile_info( )
+    assert!(matches!(
+        uncompressed_header(&bits, &mut ref_frames),
+        Err(Error::ConformanceViolation { requirement: "ref_frame_idx references a valid reference frame" })
+    ));
+
+    // The references set_frame_refs( ) derives are checked as well
+    let mut bits = Bits::default();
+    bits.inter_frame_start(1, 0x00);
+    bits.f(1, 1).f(3, 1).f(3, 0); // frame_refs_short_signaling, last_frame_idx, gold_frame_idx
+    bits.inter_frame_end();
+    assert!(matches!(
+        uncompressed_header(&bits, &mut ref_frames),
+        Err(Error::ConformanceViolation { requirement: "ref_frame_idx references a valid reference frame" })
+    ));
+}
+
+#[test]
+fn film_grain_params() {
+    let sequence_header = parse_sequence_header(FILM_GRAIN_SEQUENCE_HEADER);
+    assert_eq!(sequence_header.film_grain_params_present(), 1);
+    let mut ref_frames = RefFrameStore::new();
+    let mut bits = Bits::default();
+    bits.key_frame().base_q_idx(100);
+    bits.f(1, 0).f(1, 0).filters().tools(false);
+    bits.f(1, 1).f(16, 0x1234); // apply_grain, grain_seed
+    bits.f(4, 2).f(8, 16).f(8, 40).f(8, 128).f(8, 90); // num_y_points, point_y_value, point_y_scaling
+    bits.f(1, 0); // chroma_scaling_from_luma
+    bits.f(4, 1).f(8, 64).f(8, 20); // num_cb_points, point_cb_value, point_cb_scaling
+    bits.f(4, 0); // num_cr_points
+    bits.f(2, 3).f(2, 1); // grain_scaling_minus_8, ar_coeff_lag
+    for coeff in [120, 130, 100, 160] {
+        bits.f(8, coeff); // ar_coeffs_y_plus_128, numPosLuma is 4
+    }
+    for coeff in [1, 2, 3, 4, 255] {
+        bits.f(8, coeff); // ar_coeffs_cb_plus_128, numPosChroma adds the luma coefficient
+    }
+    bits.f(2, 2).f(2, 1); // ar_coeff_shift_minus_6, grain_scale_shift
+    bits.f(8, 128).f(8, 192).f(9, 256); // cb_mult, cb_luma_mult, cb_offset
+    bits.f(1, 1).f(1, 0); // overlap_flag, clip_to_restricted_range
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    let film_grain_params = frame_header.film_grain_params().clone();
+    assert_eq!(film_grain_params.apply_grain(), 1);
+    assert_eq!(film_grain_params.grain_seed(), 0x1234);
+    assert_eq!(film_grain_params.update_grain(), 1);
+    assert_eq!(film_grain_params.num_y_points(), 2);
+    assert_eq!(film_grain_params.point_y_value(), &[16, 128]);
+    assert_eq!(film_grain_params.point_y_scaling(), &[40, 90]);
+    assert_eq!(film_grain_params.chroma_scaling_from_luma(), 0);
+    assert_eq!((film_grain_params.num_cb_points(), film_grain_params.num_cr_points()), (1, 0));
+    assert_eq!(film_grain_params.point_cb_value(), &[64]);
+    assert_eq!(film_grain_params.point_cb_scaling(), &[20]);
+    assert!(film_grain_params.point_cr_value().is_empty());
+    assert_eq!((film_grain_params.grain_scaling_minus_8(), film_grain_params.ar_coeff_lag()), (3, 1));
+    assert_eq!(film_grain_params.ar_coeffs_y_plus_128(), &[120, 130, 100, 160]);
+    assert_eq!(film_grain_params.ar_coeffs_cb_plus_128(), &[1, 2, 3, 4, 255]);
+    assert!(film_grain_params.ar_coeffs_cr_plus_128().is_empty());
+    assert_eq!((film_grain_params.ar_coeff_shift_minus_6(), film_grain_params.grain_scale_shift()), (2, 1));
+    assert_eq!((film_grain_params.cb_mult(), film_grain_params.cb_luma_mult(), film_grain_params.cb_offset()), (128, 192, 256));
+    assert_eq!((film_grain_params.cr_mult(), film_grain_params.cr_luma_mult(), film_grain_params.cr_offset()), (0, 0, 0));
+    assert_eq!((film_grain_params.overlap_flag(), film_grain_params.clip_to_restricted_range()), (1, 0));
+    assert_eq!(ref_frames.ref_frame(0).saved_film_grain_params(), &film_grain_params);
+
+    // update_grain 0 loads the parameters of film_grain_params_ref_idx with a new grain_seed
+    let mut inter_frame = Bits::default();
+    inter_frame.inter_frame(1, 0x02).base_q_idx(100);
+    inter_frame.f(1, 0).f(1, 0).filters().tools(true);
+    inter_frame.f(1, 1).f(16, 0x5678); // apply_grain, grain_seed
+    let mut bits = Bits { bits: inter_frame.bits.clone() };
+    bits.f(1, 0).f(3, 0); // update_grain, film_grain_params_ref_idx
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.film_grain_params().grain_seed(), 0x5678);
+    assert_eq!(frame_header.film_grain_params().update_grain(), 0);
+    assert_eq!(frame_header.film_grain_params().point_y_value(), film_grain_params.point_y_value());
+    assert_eq!(frame_header.film_grain_params().ar_coeffs_cb_plus_128(), film_grain_params.ar_coeffs_cb_plus_128());
+    assert_eq!(ref_frames.ref_frame(1).saved_film_grain_params(), frame_header.film_grain_params());
+
+    // film_grain_params_ref_idx has to be one of the references of the frame
+    let mut bits = Bits { bits: inter_frame.bits.clone() };
+    bits.f(1, 0).f(3, 1);
+    let data = bits.bytes();
+    let mut r = BitReader::endian(Cursor::new(&data), BigEndian);
+    assert!(matches!(
+        FrameHeader::uncompressed_header(&mut r, &sequence_header, None, &mut ref_frames),
+        Err(Error::ConformanceViolation { .. })
+    ));
+
+    // show_existing_frame outputs the grain of the shown frame
+    let mut bits = Bits::default();
+    bits.f(1, 1).f(3, 1); // show_existing_frame, frame_to_show_map_idx
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.film_grain_params().grain_seed(), 0x5678);
+    assert_eq!(frame_header.film_grain_params().point_cb_value(), &[64]);
+
+    // apply_grain 0 resets the film grain params
+    let mut bits = Bits::default();
+    bits.inter_frame(2, 0x00).base_q_idx(100);
+    bits.f(1, 0).f(1, 0).filters().tools(true);
+    bits.f(1, 0); // apply_grain
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.film_grain_params(), &Default::default());
+}
+
+#[test]
+fn relative_dist_wraps_around_order_hint_bits() {
+    let sequence_header = sequence_header();
+    assert_eq!(sequence_header.get_relative_dist(12, 8), 4);
+    assert_eq!(sequence_header.get_relative_dist(8, 12), -4);
+    assert_eq!(sequence_header.get_relative_dist(2, 126), 4);
+    assert_eq!(sequence_header.get_relative_dist(126, 2), -4);
+    assert_eq!(sequence_header.get_relative_dist(64, 0), -64);
+}
+
+// Key frame at order hint 0 in every slot, then slots 1 - 5 refreshed with order hints 8, 16, 24, 32 and 4
+fn ref_frames_with_order_hints(sequence_header: &OBU_Sequence_Header) -> RefFrameStore {
+    let mut ref_frames = RefFrameStore::new();
+    let mut bits = Bits::default();
+    bits.key_frame().base_q_idx(100);
+    bits.f(1, 0).f(1, 0).filters().tools(false);
+    parse(sequence_header, &mut ref_frames, &bits);
+
+    for (slot, order_hint) in [(1, 8), (2, 16), (3, 24), (4, 32), (5, 4)] {
+        let mut bits = Bits::default();
+        bits.inter_frame(order_hint, 1 << slot).base_q_idx(100);
+        bits.f(1, 0).f(1, 0).filters().tools(true);
+        let frame_header = parse(sequence_header, &mut ref_frames, &bits);
+        assert_eq!(frame_header.order_hints(), &[0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+    ref_frames
+}
+
+#[test]
+fn frame_refs_short_signaling() {
+    let sequence_header = sequence_header();
+    let mut ref_frames = ref_frames_with_order_hints(&sequence_header);
+    let order_hints: Vec<u8> = (0..8).map(|i| ref_frames.ref_frame(i).ref_order_hint()).collect();
+    assert_eq!(order_hints, [0, 8, 16, 24, 32, 4, 0, 0]);
+
+    let mut bits = Bits::default();
+    bits.inter_frame_start(12, 0x40);
+    bits.f(1, 1); // frame_refs_short_signaling
+    bits.f(3, 1).f(3, 0); // last_frame_idx, gold_frame_idx
+    bits.inter_frame_end().base_q_idx(100);
+    bits.f(1, 0).f(1, 0).filters().tools(true);
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.frame_refs_short_signaling(), 1);
+    assert_eq!(frame_header.last_frame_idx(), 1);
+    assert_eq!(frame_header.gold_frame_idx(), 0);
+    // Backward references by order hint to ALTREF, BWDREF and ALTREF2, the latest unused forward ones to LAST2 and LAST3
+    assert_eq!(frame_header.ref_frame_idx(), &[1, 5, 7, 0, 2, 3, 4]);
+    assert_eq!(frame_header.order_hints(), &[0, 8, 4, 0, 0, 16, 24, 32]);
+    assert_eq!(frame_header.ref_frame_sign_bias(), &[0, 0, 0, 0, 0, 1, 1, 1]);
+    assert_eq!(ref_frames.ref_frame(6).ref_order_hint(), 12);
+    assert_eq!(ref_frames.ref_frame(6).saved_order_hints(), frame_header.order_hints());
+
+    // last_frame_idx has to be a forward reference
+    let mut bits = Bits::default();
+    bits.inter_frame_start(12, 0x00);
+    bits.f(1, 1).f(3, 2).f(3, 0);
+    let data = bits.bytes();
+    let mut r = BitReader::endian(Cursor::new(&data), BigEndian);
+    assert!(matches!(
+        FrameHeader::uncompressed_header(&mut r, &sequence_header, None, &mut ref_frames),
+        Err(Error::ConformanceViolation { .. })
+    ));
+}
+
+#[test]
+fn skip_mode_params() {
+    let sequence_header = sequence_header();
+    let mut ref_frames = ref_frames_with_order_hints(&sequence_header);
+
+    // Nearest forward and backward references
+    let mut bits = Bits::default();
+    bits.inter_frame_with_refs(12, 0x00, &[1, 5, 0, 0, 2, 3, 4]).base_q_idx(100);
+    bits.f(1, 0).f(1, 0).filters();
+    bits.f(1, 0); // tx_mode_select
+    bits.f(1, 1); // reference_select
+    bits.f(1, 1); // skip_mode_present
+    bits.f(1, 0).f(1, 0); // allow_warped_motion, reduced_tx_set
+    for _ in 0..7 {
+        bits.f(1, 0); // is_global
+    }
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.reference_select(), 1);
+    assert_eq!(frame_header.skip_mode_present(), 1);
+    assert_eq!(frame_header.skip_mode_frame(), &[LAST_FRAME, BWDREF_FRAME]);
+
+    // Only forward references, the two nearest ones
+    let mut bits = Bits::default();
+    bits.inter_frame_with_refs(40, 0x00, &[0, 0, 0, 0, 1, 5, 0]).base_q_idx(100);
+    bits.f(1, 0).f(1, 0).filters();
+    bits.f(1, 0).f(1, 1).f(1, 0).f(1, 0).f(1, 0);
+    for _ in 0..7 {
+        bits.f(1, 0);
+    }
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.skip_mode_present(), 0);
+    assert_eq!(frame_header.skip_mode_frame(), &[BWDREF_FRAME, ALTREF2_FRAME]);
+
+    // A single distinct forward order hint leaves skip mode off without reading skip_mode_present
+    let mut bits = Bits::default();
+    bits.inter_frame(12, 0x00).base_q_idx(100);
+    bits.f(1, 0).f(1, 0).filters();
+    bits.f(1, 0).f(1, 1).f(1, 0).f(1, 0);
+    for _ in 0..7 {
+        bits.f(1, 0);
+    }
+
+    let frame_header = parse(&sequence_header, &mut ref_frames, &bits);
+    assert_eq!(frame_header.skip_mode_present(), 0);
+    assert_eq!(frame_header.skip_mode_frame(), &[0, 0]);
+}
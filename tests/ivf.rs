@@ -0,0 +1,98 @@
+use std::io::Cursor;
+
+use rust_av1_dec::{Error, IvfReader, IVF_FOURCC_AV1, IVF_SIGNATURE, OBU_TYPE};
+
+// 32 byte file header, 640x360 at a 1/30 timebase with 2 frames
+fn file_header(fourcc: [u8; 4], header_size: u16) -> Vec<u8> {
+    let mut data = IVF_SIGNATURE.to_vec();
+    data.extend(0u16.to_le_bytes()); // version
+    data.extend(header_size.to_le_bytes());
+    data.extend(fourcc);
+    data.extend(640u16.to_le_bytes());
+    data.extend(360u16.to_le_bytes());
+    data.extend(30u32.to_le_bytes()); // timebase_denominator
+    data.extend(1u32.to_le_bytes()); // timebase_numerator
+    data.extend(2u32.to_le_bytes()); // frame_count
+    data.extend([0u8; 4]);
+    data.resize(header_size as usize, 0);
+    data
+}
+
+fn frame(pts: u64, payload: &[u8]) -> Vec<u8> {
+    let mut data = (payload.len() as u32).to_le_bytes().to_vec();
+    data.extend(pts.to_le_bytes());
+    data.extend(payload);
+    data
+}
+
+// TD and a padding OBU
+const TEMPORAL_UNIT: [u8; 6] = [0x12, 0x00, 0x7a, 0x02, 0xaa, 0xbb];
+
+#[test]
+fn reads_header_and_frames() {
+    let mut data = file_header(IVF_FOURCC_AV1, 32);
+    data.extend(frame(0, &TEMPORAL_UNIT));
+    data.extend(frame(45, &TEMPORAL_UNIT[..2]));
+
+    let mut reader = IvfReader::new(Cursor::new(data)).unwrap();
+    let header = reader.header();
+    assert_eq!((header.width(), header.height()), (640, 360));
+    assert_eq!(header.timebase(), (1, 30));
+    assert_eq!(header.frame_count(), 2);
+    assert_eq!(header.fourcc(), IVF_FOURCC_AV1);
+
+    let first = reader.read_frame().unwrap().unwrap();
+    assert_eq!(first.pts(), 0);
+    assert_eq!(first.data(), TEMPORAL_UNIT);
+    let obu_types: Vec<_> = first.obus().map(|obu| obu.unwrap().obu_type()).collect();
+    assert_eq!(obu_types, [OBU_TYPE::OBU_TEMPORAL_DELIMITER, OBU_TYPE::OBU_PADDING]);
+
+    let second = reader.read_frame().unwrap().unwrap();
+    assert_eq!(second.pts(), 45);
+    assert_eq!(second.timestamp(), 1.5);
+    assert!(reader.read_frame().unwrap().is_none());
+}
+
+#[test]
+fn skips_header_extension() {
+    let mut data = file_header(IVF_FOURCC_AV1, 40);
+    data.extend(frame(7, &TEMPORAL_UNIT));
+    let frames: Vec<_> = IvfReader::new(Cursor::new(data)).unwrap().map(Result::unwrap).collect();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].pts(), 7);
+}
+
+#[test]
+fn invalid_file_headers() {
+    let mut data = file_header(IVF_FOURCC_AV1, 32);
+    data[0] = b'X';
+    assert!(matches!(IvfReader::new(Cursor::new(data)), Err(Error::InvalidSyntax { .. })));
+
+    let data = file_header(*b"VP90", 32);
+    assert!(matches!(IvfReader::new(Cursor::new(data)), Err(Error::Unsupported { .. })));
+
+    let data = file_header(IVF_FOURCC_AV1, 32);
+    assert!(matches!(IvfReader::new(Cursor::new(&data[..20])), Err(Error::UnexpectedEof)));
+}
+
+#[test]
+fn truncated_frames() {
+    // A frame header cut short is not a clean end of file
+    let mut data = file_header(IVF_FOURCC_AV1, 32);
+    data.extend(&frame(0, &TEMPORAL_UNIT)[..5]);
+    let mut reader = IvfReader::new(Cursor::new(data)).unwrap();
+    assert!(matches!(reader.read_frame(), Err(Error::UnexpectedEof)));
+
+    // Frame data shorter than frame_size
+    let mut data = file_header(IVF_FOURCC_AV1, 32);
+    data.extend(&frame(0, &TEMPORAL_UNIT)[..15]);
+    let mut reader = IvfReader::new(Cursor::new(data)).unwrap();
+    assert!(matches!(reader.read_frame(), Err(Error::UnexpectedEof)));
+
+    // An absurd frame_size fails before anything is allocated for it
+    let mut data = file_header(IVF_FOURCC_AV1, 32);
+    data.extend(u32::MAX.to_le_bytes());
+    data.extend(0u64.to_le_bytes());
+    let mut reader = IvfReader::new(Cursor::new(data)).unwrap();
+    assert!(matches!(reader.read_frame(), Err(Error::InvalidSyntax { element: "IVF frame size", .. })));
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use common::{obu, temporal_delimiter, Bits};
+use rust_av1_dec::{Level, LevelChecker, ObuReader, TemporalUnits};
+
+// One operating point with the coding tools and color config of basic_1080p in round_trip.rs,
+// frame_rate pictures per second in Timing_Info
+fn sequence_header(seq_level_idx: u32, seq_tier: u32, width: u32, height: u32, frame_rate: Option<u32>) -> Vec<u8> {
+    let mut bits = Bits::default();
+    bits.f(3, 0).f(1, 0).f(1, 0); // seq_profile, still_picture, reduced_still_picture_header
+    bits.f(1, frame_rate.is_some() as u32); // timing_info_present_flag
+    if let Some(frame_rate) = frame_rate {
+        bits.f(32, 1).f(32, frame_rate); // num_units_in_display_tick, time_scale
+        bits.f(1, 1).f(1, 1); // equal_picture_interval, num_ticks_per_picture_minus_1 0
+        bits.f(1, 0); // decoder_model_info_present_flag
+    }
+    bits.f(1, 0).f(5, 0).f(12, 0); // initial_display_delay_present_flag, operating_points_cnt_minus_1, operating_point_idc
+    bits.f(5, seq_level_idx);
+    if seq_level_idx > 7 {
+        bits.f(1, seq_tier);
+    }
+    bits.f(4, 15).f(4, 15).f(16, width - 1).f(16, height - 1); // frame_width_bits_minus_1, frame_height_bits_minus_1, max sizes
+    for bit in "001111111111111001100000000".chars() {
+        bits.f(1, (bit == '1') as u32); // coding tools and color_config
+    }
+    obu(0x0a, &bits.trailing_bits())
+}
+
+// Shown key frame at the sequence header size, uniformly spaced tiles of one byte tile_size_minus_1,
+// the last tile holds tile_size bytes and the others 1
+fn frame(tile_cols_log2: u32, tile_rows_log2: u32, num_tiles: usize, tile_size: usize) -> Vec<u8> {
+    let mut bits = Bits::default();
+    bits.f(1, 0).f(2, 0).f(1, 1); // show_existing_frame, frame_type, show_frame
+    bits.f(1, 0).f(1, 0).f(1, 0); // disable_cdf_update, allow_screen_content_tools, frame_size_override_flag
+    bits.f(7, 0); // order_hint
+    bits.f(1, 0).f(1, 0); // render_and_frame_size_different, disable_frame_end_update_cdf
+    bits.f(1, 1); // uniform_tile_spacing_flag
+    for _ in 0..tile_cols_log2 {
+        bits.f(1, 1); // increment_tile_cols_log2
+    }
+    bits.f(1, 0);
+    for _ in 0..tile_rows_log2 {
+        bits.f(1, 1); // increment_tile_rows_log2
+    }
+    bits.f(1, 0);
+    if tile_cols_log2 + tile_rows_log2 > 0 {
+        bits.f(tile_cols_log2 + tile_rows_log2, 0).f(2, 0); // context_update_tile_id, tile_size_bytes_minus_1
+    }
+    bits.f(8, 100).f(1, 0).f(1, 0).f(1, 0).f(1, 0); // base_q_idx, delta_coded x 3, using_qmatrix
+    bits.f(1, 0).f(1, 0); // segmentation_enabled, delta_q_present
+    bits.f(6, 0).f(6, 0).f(3, 0).f(1, 0); // loop_filter_level[ 0 ], [ 1 ], loop_filter_sharpness, loop_filter_delta_enabled
+    bits.f(2, 0).f(2, 0).f(4, 0).f(2, 0).f(4, 0).f(2, 0); // cdef_damping_minus_3, cdef_bits, strengths
+    bits.f(2, 0).f(2, 0).f(2, 0); // lr_type
+    bits.f(1, 0).f(1, 0); // tx_mode_select, reduced_tx_set
+
+    let mut payload = bits.bytes();
+    if num_tiles > 1 {
+        payload.push(0x00); // tile_start_and_end_present_flag
+    }
+    for _ in 1..num_tiles {
+        payload.extend([0x00, 0xaa]); // tile_size_minus_1, tile
+    }
+    payload.extend(vec![0xaa; tile_size]);
+    obu(0x32, &payload)
+}
+
+// Checks `count` temporal units of a TD, the sequence header and the frame
+fn check(sequence_header: &[u8], frame: &[u8], count: usize) -> LevelChecker {
+    let temporal_unit = [&temporal_delimiter(), sequence_header, frame].concat();
+    let stream = temporal_unit.repeat(count);
+    let mut temporal_units = TemporalUnits::new(ObuReader::new(&stream)).map(Result::unwrap).peekable();
+    let mut checker = LevelChecker::new(temporal_units.peek().unwrap().sequence_header().unwrap());
+    for temporal_unit in temporal_units {
+        checker.check_temporal_unit(&temporal_unit);
+    }
+    checker
+}
+
+fn violations(checker: &LevelChecker) -> Vec<(&'static str, u64, u64)> {
+    checker.violations().iter().map(|violation| (violation.limit(), violation.value(), violation.maximum())).collect()
+}
+
+#[test]
+fn level_table() {
+    let level = Level::from_seq_level_idx(0).unwrap();
+    assert_eq!((level.major(), level.minor()), (2, 0));
+    assert_eq!((level.max_pic_size(), level.max_h_size(), level.max_v_size()), (147_456, 2048, 1152));
+    assert_eq!((level.max_display_rate(), level.max_decode_rate(), level.max_header_rate()), (4_423_680, 5_529_600, 150));
+    assert_eq!((level.max_tiles(), level.max_tile_cols()), (8, 4));
+    assert_eq!((level.min_comp_basis(0), level.min_comp_basis(1)), (Some(2), None));
+    assert_eq!((level.max_bitrate(0, 0), level.max_bitrate(1, 0)), (Some(1_500_000), None));
+
+    let level = Level::from_seq_level_idx(13).unwrap();
+    assert_eq!((level.major(), level.minor()), (5, 1));
+    assert_eq!((level.min_comp_basis(0), level.min_comp_basis(1)), (Some(8), Some(4)));
+    assert_eq!(level.max_bitrate(0, 1), Some(80_000_000));
+    assert_eq!(level.max_bitrate(1, 2), Some(480_000_000));
+
+    let level = Level::from_seq_level_idx(19).unwrap();
+    assert_eq!((level.major(), level.minor()), (6, 3));
+    assert_eq!((level.max_tiles(), level.max_tile_cols()), (128, 16));
+
+    // Reserved levels and 31, which has no limits
+    for seq_level_idx in [2, 3, 6, 7, 10, 11, 20, 30, 31] {
+        assert_eq!(Level::from_seq_level_idx(seq_level_idx), None, "{seq_level_idx}");
+    }
+}
+
+#[test]
+fn sequence_header_frame_size() {
+    let checker = check(&sequence_header(0, 0, 4096, 2304, None), &frame(0, 0, 1, 1), 1);
+    assert_eq!(checker.level().unwrap().seq_level_idx(), 0);
+    assert_eq!(
+        violations(&checker),
+        [("MaxPicSize", 4096 * 2304, 147_456), ("MaxHSize", 4096, 2048), ("MaxVSize", 2304, 1152)]
+    );
+}
+
+#[test]
+fn tiles() {
+    // 8 tile columns and 2 tile rows of a 1080p frame
+    let checker = check(&sequence_header(0, 0, 1920, 1080, None), &frame(3, 1, 16, 1), 1);
+    assert_eq!(violations(&checker), [("MaxPicSize", 1920 * 1080, 147_456), ("MaxTiles", 16, 8), ("MaxTileCols", 8, 4)]);
+
+    // seq_level_idx 31 has no limits
+    let checker = check(&sequence_header(31, 0, 1920, 1080, None), &frame(3, 1, 16, 1), 1);
+    assert!(checker.level().is_none());
+    assert!(checker.violations().is_empty());
+}
+
+#[test]
+fn sample_and_header_rates() {
+    let checker = check(&sequence_header(8, 0, 1920, 1080, Some(30)), &frame(0, 0, 1, 1), 2);
+    assert!(checker.violations().is_empty());
+
+    let checker = check(&sequence_header(8, 0, 1920, 1080, Some(1000)), &frame(0, 0, 1, 1), 2);
+    assert_eq!(
+        violations(&checker),
+        [
+            ("MaxDisplayRate", 1920 * 1080 * 1000, 70_778_880),
+            ("MaxDecodeRate", 1920 * 1080 * 1000, 77_856_768),
+            ("MaxHeaderRate", 1000, 300)
+        ]
+    );
+}
+
+#[test]
+fn bitrate() {
+    // About 14.4 Mbps at 30 temporal units per second, above the 12 Mbps of the main tier
+    let large_frame = frame(0, 0, 1, 60_000);
+    let checker = check(&sequence_header(8, 0, 1920, 1080, Some(30)), &large_frame, 3);
+    let violations = violations(&checker);
+    assert_eq!(violations.len(), 1);
+    assert_eq!((violations[0].0, violations[0].2), ("MaxBitrate", 12_000_000));
+    assert!(violations[0].1 > 14_400_000);
+
+    // The high tier allows 30 Mbps
+    let checker = check(&sequence_header(8, 1, 1920, 1080, Some(30)), &large_frame, 3);
+    assert!(checker.violations().is_empty());
+}
+
+#[test]
+fn compressed_ratio() {
+    // 256x128 at 30 pictures per second needs a ratio of 0.8, UncompressedSize is 61440 bytes
+    let large_frame = frame(0, 0, 1, 80_000);
+    let checker = check(&sequence_header(8, 1, 256, 128, Some(30)), &large_frame, 1);
+    assert_eq!(violations(&checker), [("MinCompBasis", 80_000, 61_440 * 5 / 4 + 128)]);
+
+    let checker = check(&sequence_header(8, 1, 256, 128, Some(30)), &frame(0, 0, 1, 76_000), 1);
+    assert!(checker.violations().is_empty());
+
+    // SpeedAdj needs the decoded sample rate
+    let checker = check(&sequence_header(8, 1, 256, 128, None), &large_frame, 1);
+    assert!(checker.violations().is_empty());
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use common::{hex, obu, temporal_delimiter, Bits};
+use rust_av1_dec::{DecodedObu, Decoder, Error, OBU_Metadata, OBU};
+
+#[test]
+fn hdr_cll() {
+    let OBU_Metadata::HDR_CLL(cll) = OBU_Metadata::metadata_obu(&hex("0103e8019080")).unwrap() else {
+        panic!("expected HDR_CLL");
+    };
+    assert_eq!((cll.max_cll(), cll.max_fall()), (1000, 400));
+}
+
+#[test]
+fn hdr_mdcv() {
+    // BT.2020 primaries and D65 in 0.16 fixed point, 1000 and 0.005 cd/m2
+    let payload = hex(&"02 b53f 4ac1 2b85 cc08 2189 0bc7 500d 5439 0003e800 00000052 80".replace(' ', ""));
+    let metadata = OBU_Metadata::metadata_obu(&payload).unwrap();
+    assert_eq!(metadata.metadata_type(), 2);
+    let OBU_Metadata::HDR_MDCV(mdcv) = metadata else {
+        panic!("expected HDR_MDCV");
+    };
+    assert_eq!(mdcv.primary_chromaticity_x(), [0xb53f, 0x2b85, 0x2189]);
+    assert_eq!(mdcv.primary_chromaticity_y(), [0x4ac1, 0xcc08, 0x0bc7]);
+    assert_eq!((mdcv.white_point_chromaticity_x(), mdcv.white_point_chromaticity_y()), (0x500d, 0x5439));
+    assert_eq!(mdcv.max_luminance(), 1000.0);
+    assert_eq!(mdcv.luminance_min(), 82);
+    assert!((mdcv.primaries()[0].0 - 0.708).abs() < 0.0001);
+    assert!((mdcv.white_point().1 - 0.3290).abs() < 0.0001);
+    assert!((mdcv.min_luminance() - 0.005).abs() < 0.0001);
+}
+
+#[test]
+fn scalability_structure() {
+    let mut bits = Bits::default();
+    bits.f(8, 3); // metadata_type
+    bits.f(8, 14); // scalability_mode_idc, SCALABILITY_SS
+    bits.f(2, 1).f(1, 1).f(1, 1).f(1, 1).f(3, 0); // spatial_layers_cnt_minus_1, present flags, reserved
+    bits.f(16, 640).f(16, 360).f(16, 1280).f(16, 720); // spatial_layer_max_width / height
+    bits.f(8, 0xff).f(8, 0); // spatial_layer_ref_id
+    bits.f(8, 1); // temporal_group_size
+    bits.f(3, 0).f(1, 1).f(1, 0).f(3, 2).f(8, 1).f(8, 2); // temporal_id, switching up points, ref_cnt, ref_pic_diff
+
+    let OBU_Metadata::SCALABILITY(scalability) = OBU_Metadata::metadata_obu(&bits.trailing_bits()).unwrap() else {
+        panic!("expected SCALABILITY");
+    };
+    assert_eq!(scalability.scalability_mode_idc(), 14);
+    let structure = scalability.scalability_structure().unwrap();
+    assert_eq!(structure.spatial_layers_cnt_minus_1(), 1);
+    assert_eq!(structure.spatial_layer_max_width(), [640, 1280]);
+    assert_eq!(structure.spatial_layer_max_height(), [360, 720]);
+    assert_eq!(structure.spatial_layer_ref_id(), [0xff, 0]);
+    assert_eq!(structure.temporal_group_size(), 1);
+    let entry = &structure.temporal_group()[0];
+    assert_eq!(entry.temporal_group_temporal_switching_up_point_flag(), 1);
+    assert_eq!(entry.temporal_group_ref_pic_diff(), [1, 2]);
+
+    // Other modes carry no structure
+    let OBU_Metadata::SCALABILITY(scalability) = OBU_Metadata::metadata_obu(&hex("030280")).unwrap() else {
+        panic!("expected SCALABILITY");
+    };
+    assert_eq!(scalability.scalability_structure(), None);
+}
+
+#[test]
+fn itut_t35() {
+    let OBU_Metadata::ITUT_T35(t35) = OBU_Metadata::metadata_obu(&hex("04b5003c0001040180")).unwrap() else {
+        panic!("expected ITUT_T35");
+    };
+    assert_eq!(t35.itu_t_t35_country_code(), 0xb5);
+    assert_eq!(t35.itu_t_t35_country_code_extension_byte(), None);
+    assert_eq!(t35.itu_t_t35_payload_bytes(), [0x00, 0x3c, 0x00, 0x01, 0x04, 0x01]);
+
+    let OBU_Metadata::ITUT_T35(t35) = OBU_Metadata::metadata_obu(&hex("04ff0a80")).unwrap() else {
+        panic!("expected ITUT_T35");
+    };
+    assert_eq!(t35.itu_t_t35_country_code_extension_byte(), Some(0x0a));
+    assert!(t35.itu_t_t35_payload_bytes().is_empty());
+
+    // The payload bytes have to end on a byte boundary before trailing_bits( )
+    assert!(matches!(OBU_Metadata::metadata_obu(&hex("04b50301")), Err(Error::PayloadSizeMismatch { .. })));
+}
+
+#[test]
+fn timecode() {
+    let mut bits = Bits::default();
+    bits.f(8, 5); // metadata_type
+    bits.f(5, 1).f(1, 1).f(1, 0).f(1, 0).f(9, 24); // counting_type, full_timestamp_flag, discontinuity_flag, cnt_dropped_flag, n_frames
+    bits.f(6, 30).f(6, 15).f(5, 10); // seconds_value, minutes_value, hours_value
+    bits.f(5, 0); // time_offset_length
+    let OBU_Metadata::TIMECODE(timecode) = OBU_Metadata::metadata_obu(&bits.trailing_bits()).unwrap() else {
+        panic!("expected TIMECODE");
+    };
+    assert_eq!(timecode.n_frames(), 24);
+    assert_eq!((timecode.hours_value(), timecode.minutes_value(), timecode.seconds_value()), (Some(10), Some(15), Some(30)));
+
+    let mut bits = Bits::default();
+    bits.f(8, 5);
+    bits.f(5, 0).f(1, 0).f(1, 1).f(1, 0).f(9, 3);
+    bits.f(1, 1).f(6, 5); // seconds_flag, seconds_value
+    bits.f(1, 0); // minutes_flag
+    bits.f(5, 8).f(8, 0xab); // time_offset_length, time_offset_value
+    let OBU_Metadata::TIMECODE(timecode) = OBU_Metadata::metadata_obu(&bits.trailing_bits()).unwrap() else {
+        panic!("expected TIMECODE");
+    };
+    assert_eq!(timecode.discontinuity_flag(), 1);
+    assert_eq!((timecode.hours_value(), timecode.minutes_value(), timecode.seconds_value()), (None, None, Some(5)));
+    assert_eq!((timecode.time_offset_length(), timecode.time_offset_value()), (8, 0xab));
+}
+
+#[test]
+fn unregistered_and_malformed() {
+    let metadata = OBU_Metadata::metadata_obu(&hex("06dead80")).unwrap();
+    assert_eq!(metadata, OBU_Metadata::OTHER { metadata_type: 6, payload: vec![0xde, 0xad] });
+
+    // Cut short, and one byte too many before trailing_bits( )
+    assert!(matches!(OBU_Metadata::metadata_obu(&hex("0103e880")), Err(Error::UnexpectedEof)));
+    assert!(matches!(OBU_Metadata::metadata_obu(&hex("0103e801900080")), Err(Error::PayloadSizeMismatch { .. })));
+}
+
+#[test]
+fn collected_per_temporal_unit() {
+    let mut decoder = Decoder::new();
+    let data = obu(0x2a, &hex("0103e8019080"));
+    let obu = OBU::open_bitstream_unit(&data).unwrap();
+    assert!(matches!(decoder.decode_obu(&obu).unwrap(), DecodedObu::Metadata(OBU_Metadata::HDR_CLL(_))));
+    assert_eq!(decoder.metadata().len(), 1);
+
+    let data = temporal_delimiter();
+    decoder.decode_obu(&OBU::open_bitstream_unit(&data).unwrap()).unwrap();
+    assert!(decoder.metadata().is_empty());
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::hex;
+use rust_av1_dec::{Error, ObuReader, OBU_TYPE};
+
+// TD, sequence header (basic_1080p of round_trip.rs) and a padding OBU, all with obu_size
+const STREAM: &str = "12000a0b00000042abbfc373ffe6017a03aabbcc";
+
+#[test]
+fn iterates_obus() {
+    let stream = hex(STREAM);
+    let mut reader = ObuReader::new(&stream);
+
+    let obu = reader.next().unwrap().unwrap();
+    assert_eq!(obu.obu_type(), OBU_TYPE::OBU_TEMPORAL_DELIMITER);
+    assert_eq!((obu.header_size(), obu.obu_size()), (2, 0));
+    assert_eq!(reader.position(), 2);
+
+    let obu = reader.next().unwrap().unwrap();
+    assert_eq!(obu.obu_type(), OBU_TYPE::OBU_SEQUENCE_HEADER);
+    assert_eq!(obu.obu_size(), 11);
+    assert_eq!(obu.payload(), &stream[4..15]);
+    assert_eq!(reader.position(), 15);
+
+    let obu = reader.next().unwrap().unwrap();
+    assert_eq!(obu.obu_type(), OBU_TYPE::OBU_PADDING);
+    assert_eq!(obu.payload(), [0xaa, 0xbb, 0xcc]);
+    assert_eq!(reader.position(), stream.len());
+
+    assert!(reader.next().is_none());
+    assert!(ObuReader::new(&[]).next().is_none());
+}
+
+#[test]
+fn trailing_garbage() {
+    // A sequence header OBU claiming 5 payload bytes with only 1 left
+    let mut stream = hex(STREAM);
+    stream.extend([0x0a, 0x05, 0x00]);
+
+    let obus: Vec<_> = ObuReader::new(&stream).collect();
+    assert_eq!(obus.len(), 4);
+    assert!(obus[..3].iter().all(Result::is_ok));
+    let error = obus[3].as_ref().unwrap_err();
+    assert!(matches!(error.kind(), Error::UnexpectedEof));
+    assert_eq!(error.obu_index(), Some(3));
+    assert_eq!(error.bit_offset(), Some(16));
+
+    // obu_size cut off in the middle of its leb128 bytes
+    let mut stream = hex(STREAM);
+    stream.extend([0x0a, 0x80]);
+    let mut reader = ObuReader::new(&stream);
+    let error = reader.nth(3).unwrap().unwrap_err();
+    assert!(matches!(error.kind(), Error::UnexpectedEof));
+    assert_eq!(error.obu_index(), Some(3));
+    // The reader stops after an error
+    assert!(reader.next().is_none());
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use common::{hex, key_frame_header, obu, temporal_delimiter, tile_group, Bits, BASIC_1080P};
+use rust_av1_dec::{DecodeStatus, DecodedFrame, Decoder, Error, FrameHeader, ObuReader, TemporalUnits};
+
+// Frame header OBU of a two tile key frame
+fn frame_header() -> Vec<u8> {
+    obu(0x1a, &key_frame_header(1).trailing_bits())
+}
+
+// Three temporal units: a two tile frame in two tile groups, a frame OBU, and show_existing_frame of slot 0
+fn stream() -> Vec<u8> {
+    let mut frame = key_frame_header(0).bytes();
+    frame.extend([0xcc, 0xcd]);
+    [
+        temporal_delimiter(),
+        obu(0x0a, &hex(BASIC_1080P)),
+        hex("2a060103e8019080"), // HDR_CLL metadata
+        frame_header(),
+        tile_group(0, 0, &[0xaa]),
+        tile_group(1, 1, &[0xbb]),
+        temporal_delimiter(),
+        obu(0x32, &frame),
+        temporal_delimiter(),
+        obu(0x1a, &Bits::default().f(1, 1).f(3, 0).f(1, 1).bytes()),
+    ]
+    .concat()
+}
+
+// Frame header and tile data of each frame
+fn summary(frame_header: &FrameHeader, tiles: Vec<Vec<u8>>) -> (FrameHeader, Vec<Vec<u8>>) {
+    (frame_header.clone(), tiles)
+}
+
+// The frames the temporal unit assembler finds in the stream
+fn pulled_frames(stream: &[u8]) -> Vec<(FrameHeader, Vec<Vec<u8>>)> {
+    TemporalUnits::new(ObuReader::new(stream))
+        .map(Result::unwrap)
+        .flat_map(|temporal_unit| {
+            temporal_unit
+                .frames()
+                .iter()
+                .map(|frame| {
+                    let tiles = frame.tile_groups().iter().flat_map(|tile_group| tile_group.tiles().iter().map(|tile| tile.data().to_vec())).collect();
+                    summary(frame.frame_header(), tiles)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn pushed_frames<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> Vec<DecodedFrame> {
+    let mut decoder = Decoder::new();
+    let mut frames = Vec::new();
+    for chunk in chunks {
+        if decoder.send_data(chunk).unwrap() == DecodeStatus::FrameReady {
+            frames.extend(std::iter::from_fn(|| decoder.get_frame()));
+        }
+        assert!(decoder.get_frame().is_none());
+    }
+    assert_eq!(decoder.buffered(), 0);
+    frames
+}
+
+fn summaries(frames: &[DecodedFrame]) -> Vec<(FrameHeader, Vec<Vec<u8>>)> {
+    frames
+        .iter()
+        .map(|frame| summary(frame.frame_header(), frame.tiles().map(|tile| tile.data().to_vec()).collect()))
+        .collect()
+}
+
+#[test]
+fn whole_stream() {
+    let stream = stream();
+    let expected = pulled_frames(&stream);
+    assert_eq!(expected.len(), 3);
+
+    let frames = pushed_frames([stream.as_slice()]);
+    assert_eq!(summaries(&frames), expected);
+    assert_eq!(frames[0].num_tiles(), 2);
+    assert_eq!(frames[0].metadata().len(), 1);
+    assert!(frames[1].metadata().is_empty());
+    assert_eq!(frames[2].frame_header().show_existing_frame(), 1);
+    assert!(frames.iter().all(DecodedFrame::is_shown));
+}
+
+#[test]
+fn byte_by_byte() {
+    let stream = stream();
+    let frames = pushed_frames(stream.chunks(1));
+    assert_eq!(summaries(&frames), pulled_frames(&stream));
+}
+
+#[test]
+fn split_mid_obu() {
+    // Every split point, inside OBU headers, obu_size fields and payloads
+    let stream = stream();
+    let expected = pulled_frames(&stream);
+    for split in 0..=stream.len() {
+        let (first, second) = stream.split_at(split);
+        assert_eq!(summaries(&pushed_frames([first, second])), expected, "split at {split}");
+    }
+}
+
+#[test]
+fn partial_obu_is_buffered() {
+    let stream = stream();
+    let mut decoder = Decoder::new();
+    // TD, sequence header and the first 3 bytes of the metadata OBU
+    assert_eq!(decoder.send_data(&stream[..18]).unwrap(), DecodeStatus::NeedMoreData);
+    assert_eq!(decoder.buffered(), 3);
+    assert!(decoder.sequence_header().is_some());
+    assert!(decoder.metadata().is_empty());
+    assert_eq!(decoder.send_data(&stream[18..23]).unwrap(), DecodeStatus::NeedMoreData);
+    assert_eq!(decoder.buffered(), 0);
+    assert_eq!(decoder.metadata().len(), 1);
+}
+
+#[test]
+fn obu_without_size_field() {
+    let mut decoder = Decoder::new();
+    let error = decoder.send_data(&[0x12, 0x00, 0x10]).unwrap_err();
+    assert_eq!(error.obu_index(), Some(1));
+    assert!(matches!(
+        error.kind(),
+        Error::ConformanceViolation { requirement: "obu_has_size_field is 1 in a low overhead bitstream" }
+    ));
+    assert_eq!(decoder.buffered(), 0);
+}
+
+#[test]
+fn incomplete_frame_before_next_frame_header() {
+    let mut decoder = Decoder::new();
+    let start = [temporal_delimiter(), obu(0x0a, &hex(BASIC_1080P)), frame_header(), tile_group(0, 0, &[0xaa])].concat();
+    assert_eq!(decoder.send_data(&start).unwrap(), DecodeStatus::NeedMoreData);
+
+    // The next temporal unit starts another frame before the first one got its last tile
+    let next = [temporal_delimiter(), frame_header()].concat();
+    let error = decoder.send_data(&next).unwrap_err();
+    assert_eq!(error.obu_index(), Some(5));
+    assert!(matches!(
+        error.kind(),
+        Error::ConformanceViolation { requirement: "all tile groups of a frame precede the next frame header" }
+    ));
+
+    // The new frame takes the place of the dropped one
+    let tiles = [tile_group(0, 0, &[0xa0]), tile_group(1, 1, &[0xb0])].concat();
+    assert_eq!(decoder.send_data(&tiles).unwrap(), DecodeStatus::FrameReady);
+    let frame = decoder.get_frame().unwrap();
+    let tiles: Vec<_> = frame.tiles().map(|tile| tile.data().to_vec()).collect();
+    assert_eq!(tiles, [[0xa0], [0xb0]]);
+    assert!(decoder.get_frame().is_none());
+}
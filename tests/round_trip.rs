@@ -0,0 +1,225 @@
+mod common;
+
+use std::io::Cursor;
+
+use bitstream_io::{BigEndian, BitRead, BitReader, BitWrite, BitWriter, FromBitStream, ToBitStream};
+use common::hex;
+use rust_av1_dec::{
+    leb_128, uvlc, AV1CodecConfigurationRecord, Error, ObuReader, OperatingPoint, COLOR_PRIMARIES, MATRIX_COEFFICIENTS, OBU_Header, OBU_Sequence_Header,
+    TRANSFER_CHARACTERISTICS,
+};
+
+// Sequence header OBU payloads including trailing bits
+const SEQUENCE_HEADERS: &[(&str, &str)] = &[
+    ("basic_1080p", "00000042abbfc373ffe601"),
+    ("timing_decoder_model", "0400000fa40003a983a400000fa6948840d3be95e66202504009190c82abbfc373ffe601"),
+    ("timing_no_equal_interval", "040000000400000064000010aaeff0dcfff99091009a40"),
+    ("reduced_still_picture", "1b2627fefb6010"),
+    ("frame_ids_screen_content", "00000042efbfe1bf8ff91804"),
+    ("no_screen_content", "00000042abbfc373fe66c0404078"),
+    ("srgb_identity_profile_1", "20000042abbfc373ffe780868010"),
+    ("reserved_color_codes", "00000042abbfc373ffe640ca3211"),
+];
+
+// TD, sequence header with a padded 2 byte obu_size, metadata with an extension header
+// and a padding OBU without obu_size
+const STREAM: &str = "12000a8b0000000042abbfc373ffe6012e20060103e801908078aabb";
+
+// TD followed by reserved OBU types 9 and 0
+const RESERVED_STREAM: &str = "12004a030102030200";
+
+fn write<T: ToBitStream<Error = std::io::Error>>(value: &T) -> Vec<u8> {
+    let mut w = BitWriter::endian(Vec::new(), BigEndian);
+    value.to_writer(&mut w).unwrap();
+    w.byte_align().unwrap();
+    w.into_writer()
+}
+
+#[test]
+fn sequence_header_round_trip() {
+    for (name, payload) in SEQUENCE_HEADERS {
+        let payload = hex(payload);
+        let mut r = BitReader::endian(Cursor::new(&payload), BigEndian);
+        let sequence_header = OBU_Sequence_Header::sequence_header_obu(&mut r, &OperatingPoint::default()).unwrap();
+        let bits_read = r.position_in_bits().unwrap();
+
+        let mut w = BitWriter::endian(Vec::new(), BigEndian);
+        sequence_header.to_writer(&mut w).unwrap();
+        // trailing_bits( )
+        w.write_bit(true).unwrap();
+        w.byte_align().unwrap();
+        assert_eq!(w.into_writer(), payload, "{name}");
+
+        let mut r = BitReader::endian(Cursor::new(write(&sequence_header)), BigEndian);
+        let reparsed = OBU_Sequence_Header::sequence_header_obu(&mut r, &OperatingPoint::default()).unwrap();
+        assert_eq!(reparsed, sequence_header, "{name}");
+        assert_eq!(r.position_in_bits().unwrap(), bits_read, "{name}");
+    }
+}
+
+#[test]
+fn av1c_round_trip() {
+    for (name, payload) in SEQUENCE_HEADERS {
+        let payload = hex(payload);
+        let mut r = BitReader::endian(Cursor::new(&payload), BigEndian);
+        let sequence_header = OBU_Sequence_Header::sequence_header_obu(&mut r, &OperatingPoint::default()).unwrap();
+
+        let record = AV1CodecConfigurationRecord::from_sequence_header(&sequence_header).unwrap();
+        // configOBUs is the sequence header OBU, header 0x0a and a one byte obu_size
+        assert_eq!(record.config_obus()[..2], [0x0a, payload.len() as u8], "{name}");
+        assert_eq!(record.config_obus()[2..], payload, "{name}");
+
+        let written = write(&record);
+        assert_eq!(written[0], 0x81, "{name}");
+        let parsed = AV1CodecConfigurationRecord::from_bytes(&written).unwrap();
+        assert_eq!(parsed, record, "{name}");
+        assert_eq!(parsed.sequence_header().unwrap(), Some(sequence_header), "{name}");
+    }
+}
+
+#[test]
+fn av1c_errors() {
+    assert!(matches!(AV1CodecConfigurationRecord::from_bytes(&[0x01, 0x00, 0x00, 0x00]), Err(Error::InvalidSyntax { element: "marker", value: 0 })));
+    assert!(matches!(AV1CodecConfigurationRecord::from_bytes(&[0x82, 0x00, 0x00, 0x00]), Err(Error::Unsupported { .. })));
+    assert!(matches!(AV1CodecConfigurationRecord::from_bytes(&[0x81, 0x00, 0x00]), Err(Error::UnexpectedEof)));
+
+    // No configOBUs, and a sequence header OBU cut short
+    let record = AV1CodecConfigurationRecord::from_bytes(&[0x81, 0x00, 0x0c, 0x00]).unwrap();
+    assert_eq!(record.sequence_header().unwrap(), None);
+    let record = AV1CodecConfigurationRecord::from_bytes(&hex("81000c0012000a0b00000042")).unwrap();
+    let error = record.sequence_header().unwrap_err();
+    assert_eq!(error.obu_index(), Some(1));
+    assert!(matches!(error.kind(), Error::UnexpectedEof));
+}
+
+#[test]
+fn codec_string() {
+    let expected = [
+        ("basic_1080p", "av01.0.08M.08.0.110.02.02.02.0"),
+        ("timing_decoder_model", "av01.0.09H.08.0.110.02.02.02.0"),
+        ("timing_no_equal_interval", "av01.0.08M.08.0.111.09.16.09.1"),
+        ("reduced_still_picture", "av01.0.12M.08.0.110.02.02.02.0"),
+        ("frame_ids_screen_content", "av01.0.08M.08.0.110.02.02.02.0"),
+        ("no_screen_content", "av01.0.08M.08.1.110.01.01.01.1"),
+        ("srgb_identity_profile_1", "av01.1.08M.10.0.000.01.13.00.1"),
+        ("reserved_color_codes", "av01.0.08M.08.0.112.03.40.200.0"),
+    ];
+
+    for ((name, payload), (expected_name, codec_string)) in SEQUENCE_HEADERS.iter().zip(expected) {
+        assert_eq!(*name, expected_name);
+        let payload = hex(payload);
+        let mut r = BitReader::endian(Cursor::new(&payload), BigEndian);
+        let sequence_header = OBU_Sequence_Header::sequence_header_obu(&mut r, &OperatingPoint::default()).unwrap();
+        assert_eq!(sequence_header.codec_string(), codec_string, "{name}");
+
+        // The av1C record read back carries the same profile, level, tier, bit depth and chroma fields
+        let record = AV1CodecConfigurationRecord::from_bytes(&write(&AV1CodecConfigurationRecord::from_sequence_header(&sequence_header).unwrap())).unwrap();
+        let chroma_sample_position = if record.chroma_subsampling_x() == 1 && record.chroma_subsampling_y() == 1 { record.chroma_sample_position() as u8 } else { 0 };
+        let from_record = format!(
+            "av01.{}.{:02}{}.{:02}.{}.{}{}{}",
+            record.seq_profile(),
+            record.seq_level_idx_0(),
+            if record.seq_tier_0() == 0 { 'M' } else { 'H' },
+            record.bit_depth(),
+            record.monochrome(),
+            record.chroma_subsampling_x(),
+            record.chroma_subsampling_y(),
+            chroma_sample_position,
+        );
+        assert!(codec_string.starts_with(&from_record), "{name}");
+    }
+}
+
+#[test]
+fn obu_stream_round_trip() {
+    for stream in [STREAM, RESERVED_STREAM] {
+        let stream = hex(stream);
+        let mut written = Vec::new();
+        for obu in ObuReader::new(&stream) {
+            written.extend(write(&obu.unwrap()));
+        }
+        assert_eq!(written, stream);
+    }
+}
+
+#[test]
+fn obu_header_round_trip() {
+    for header in [[0x12u8, 0x00], [0x0a, 0x00], [0x2e, 0x20], [0x7a, 0x00], [0x36, 0xd0]] {
+        let obu_header = OBU_Header::from_reader(&mut BitReader::endian(Cursor::new(&header), BigEndian)).unwrap();
+        let written = write(&obu_header);
+        let size = if obu_header.obu_extension_flag() == 1 { 2 } else { 1 };
+        assert_eq!(written, header[..size]);
+    }
+}
+
+#[test]
+fn leb_128_encoding() {
+    let cases: &[(u64, u8, &[u8])] = &[
+        (0, 1, &[0x00]),
+        (127, 1, &[0x7f]),
+        (128, 1, &[0x80, 0x01]),
+        (300, 1, &[0xac, 0x02]),
+        (0xffff_ffff, 1, &[0xff, 0xff, 0xff, 0xff, 0x0f]),
+        (0, 4, &[0x80, 0x80, 0x80, 0x00]),
+        (11, 2, &[0x8b, 0x00]),
+        (300, 2, &[0xac, 0x02]),
+        (300, 8, &[0xac, 0x82, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]),
+    ];
+
+    for (value, min_bytes, encoded) in cases {
+        let leb = leb_128::with_min_bytes(*value, *min_bytes);
+        assert_eq!(leb.size(), encoded.len());
+        assert_eq!(write(&leb), *encoded);
+
+        let read = leb_128::from_reader(&mut BitReader::endian(Cursor::new(encoded), BigEndian)).unwrap();
+        assert_eq!(read.value(), *value);
+        assert_eq!(write(&read), *encoded);
+    }
+
+    assert!(leb_128::new(1 << 56).to_writer(&mut BitWriter::endian(Vec::new(), BigEndian)).is_err());
+
+    // Padding is not part of the value
+    assert_eq!(leb_128::with_min_bytes(11, 2), leb_128::new(11));
+    assert_ne!(leb_128::with_min_bytes(11, 2), leb_128::new(12));
+}
+
+#[test]
+fn uvlc_round_trip() {
+    for value in (0..1000).chain([65535, 1 << 20, u32::MAX - 1, u32::MAX]) {
+        let mut w = BitWriter::endian(Vec::new(), BigEndian);
+        uvlc::new(value).to_writer(&mut w).unwrap();
+        // make sure the reader stops where the writer did
+        w.write::<8, u8>(0xa5).unwrap();
+        w.byte_align().unwrap();
+        let data = w.into_writer();
+
+        let mut r = BitReader::endian(Cursor::new(&data), BigEndian);
+        assert_eq!(uvlc::from_reader(&mut r).unwrap().value(), value);
+        assert_eq!(r.read::<8, u8>().unwrap(), 0xa5);
+    }
+
+    // 0 -> 1, 1 -> 010, 2 -> 011, 3 -> 00100
+    assert_eq!(write(&uvlc::new(0)), [0x80]);
+    assert_eq!(write(&uvlc::new(1)), [0x40]);
+    assert_eq!(write(&uvlc::new(2)), [0x60]);
+    assert_eq!(write(&uvlc::new(3)), [0x20]);
+}
+
+#[test]
+fn color_codes_round_trip() {
+    // Every code reads back to one variant and writes the same byte, reserved codes only as *_RESERVED( v )
+    for code in 0..=255u8 {
+        let data = [code];
+        let color_primaries = COLOR_PRIMARIES::from_reader(&mut BitReader::endian(Cursor::new(&data), BigEndian)).unwrap();
+        assert_eq!(write(&color_primaries), data);
+        assert_eq!(matches!(color_primaries, COLOR_PRIMARIES::CP_RESERVED(_)), matches!(code, 0 | 3 | 13..=21 | 23..), "{code}");
+
+        let transfer_characteristics = TRANSFER_CHARACTERISTICS::from_reader(&mut BitReader::endian(Cursor::new(&data), BigEndian)).unwrap();
+        assert_eq!(write(&transfer_characteristics), data);
+        assert_eq!(matches!(transfer_characteristics, TRANSFER_CHARACTERISTICS::TC_RESERVED(_)), matches!(code, 0 | 3 | 19..), "{code}");
+
+        let matrix_coefficients = MATRIX_COEFFICIENTS::from_reader(&mut BitReader::endian(Cursor::new(&data), BigEndian)).unwrap();
+        assert_eq!(write(&matrix_coefficients), data);
+        assert_eq!(matches!(matrix_coefficients, MATRIX_COEFFICIENTS::MC_RESERVED(_)), matches!(code, 3 | 15..), "{code}");
+    }
+}
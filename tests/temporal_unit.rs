@@ -0,0 +1,123 @@
+mod common;
+
+use common::{hex, key_frame_header, obu, temporal_delimiter, tile_group, BASIC_1080P};
+use rust_av1_dec::{Error, ObuReader, OBU_Metadata, TemporalUnits, OBU_TYPE};
+
+fn sequence_header() -> Vec<u8> {
+    obu(0x0a, &hex(BASIC_1080P))
+}
+
+// Frame header OBU of a two tile key frame
+fn frame_header() -> Vec<u8> {
+    obu(0x1a, &key_frame_header(1).trailing_bits())
+}
+
+// Frame OBU of a single tile key frame
+fn frame() -> Vec<u8> {
+    let mut payload = key_frame_header(0).bytes();
+    payload.push(0xcc);
+    obu(0x32, &payload)
+}
+
+#[test]
+fn assembles_temporal_units() {
+    let stream = [
+        temporal_delimiter(),
+        sequence_header(),
+        hex("2a060103e8019080"), // HDR_CLL metadata
+        frame_header(),
+        tile_group(0, 0, &[0xaa]),
+        tile_group(1, 1, &[0xbb]),
+        temporal_delimiter(),
+        frame(),
+        obu(0x7a, &[0x00]),
+    ]
+    .concat();
+
+    let temporal_units: Vec<_> = TemporalUnits::new(ObuReader::new(&stream)).map(Result::unwrap).collect();
+    assert_eq!(temporal_units.len(), 2);
+
+    let first = &temporal_units[0];
+    assert_eq!(first.obus().len(), 6);
+    assert!(first.sequence_header().is_some());
+    assert!(first.new_coded_video_sequence());
+    assert!(matches!(first.metadata(), [OBU_Metadata::HDR_CLL(_)]));
+    assert_eq!(first.frames().len(), 1);
+    let coded_frame = &first.frames()[0];
+    assert!(coded_frame.is_complete());
+    assert_eq!(coded_frame.frame_header().tile_info().num_tiles(), 2);
+    let tiles: Vec<_> = coded_frame.tile_groups().iter().map(|tile_group| tile_group.tiles()[0].data()).collect();
+    assert_eq!(tiles, [[0xaa], [0xbb]]);
+    assert_eq!(first.shown_frames().count(), 1);
+
+    // The sequence header stays active without being repeated
+    let second = &temporal_units[1];
+    let obu_types: Vec<_> = second.obus().iter().map(|obu| obu.obu_type()).collect();
+    assert_eq!(obu_types, [OBU_TYPE::OBU_TEMPORAL_DELIMITER, OBU_TYPE::OBU_FRAME, OBU_TYPE::OBU_PADDING]);
+    assert!(second.sequence_header().is_none());
+    assert!(!second.new_coded_video_sequence());
+    assert!(second.metadata().is_empty());
+    assert_eq!(second.frames()[0].tile_groups()[0].tiles()[0].data(), [0xcc]);
+}
+
+#[test]
+fn starts_with_temporal_delimiter() {
+    let stream = [sequence_header(), temporal_delimiter()].concat();
+    let mut temporal_units = TemporalUnits::new(ObuReader::new(&stream));
+    let error = temporal_units.next().unwrap().unwrap_err();
+    assert_eq!(error.obu_index(), Some(0));
+    assert!(matches!(
+        error.kind(),
+        Error::ConformanceViolation { requirement: "a temporal unit starts with a temporal delimiter" }
+    ));
+    // No unit to resume at after an error
+    assert!(temporal_units.next().is_none());
+}
+
+#[test]
+fn incomplete_frames() {
+    // The second tile group is in the next temporal unit
+    let stream = [
+        temporal_delimiter(),
+        sequence_header(),
+        frame_header(),
+        tile_group(0, 0, &[0xaa]),
+        temporal_delimiter(),
+        tile_group(1, 1, &[0xbb]),
+    ]
+    .concat();
+    let error = TemporalUnits::new(ObuReader::new(&stream)).next().unwrap().unwrap_err();
+    assert_eq!(error.obu_index(), Some(4));
+    assert!(matches!(
+        error.kind(),
+        Error::ConformanceViolation { requirement: "all tile groups of a frame are in the temporal unit of its frame header" }
+    ));
+
+    // A frame ends the unit before its tiles are complete
+    let stream = [temporal_delimiter(), sequence_header(), frame_header(), tile_group(0, 0, &[0xaa])].concat();
+    let error = TemporalUnits::new(ObuReader::new(&stream)).next().unwrap().unwrap_err();
+    assert_eq!(error.obu_index(), Some(3));
+}
+
+#[test]
+fn sequence_header_after_frame_header() {
+    let stream = [temporal_delimiter(), sequence_header(), frame(), sequence_header()].concat();
+    let error = TemporalUnits::new(ObuReader::new(&stream)).next().unwrap().unwrap_err();
+    assert_eq!(error.obu_index(), Some(3));
+    assert!(matches!(
+        error.kind(),
+        Error::ConformanceViolation { requirement: "sequence headers precede the first frame header of a temporal unit" }
+    ));
+}
+
+#[test]
+fn stream_errors_end_the_units() {
+    // An OBU cut short after a complete temporal unit
+    let mut stream = [temporal_delimiter(), sequence_header(), frame()].concat();
+    stream.extend([0x12, 0x05]);
+    let mut temporal_units = TemporalUnits::new(ObuReader::new(&stream));
+    let error = temporal_units.next().unwrap().unwrap_err();
+    assert_eq!(error.obu_index(), Some(3));
+    assert!(matches!(error.kind(), Error::UnexpectedEof));
+    assert!(temporal_units.next().is_none());
+}